@@ -0,0 +1,427 @@
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::export::{CompressionPreset, ExportFormat, ExportOptions, ResolutionPreset};
+use crate::recording::{CaptureRegion, RecordingOptions, SourceType};
+
+pub const USAGE: &str = "\
+Usage: open-rec <command> [options]
+
+Commands:
+  list-sources [--type display|window|region|camera|microphone]
+  record --source <id> [--type display|window|region|camera|microphone]
+         [--region <x>,<y>,<width>,<height>] [--duration <seconds>]
+         [--camera-device <device>] [--microphone-device <device>] [--system-audio]
+  export <project-id> [--format mp4|mov|gif|wav|mp3] [--resolution 720p|1080p|4k]
+         [--fps <rate>] [--compression minimal|social|web|potato]
+         [--output-dir <dir>] [--chapter-list]
+  projects ls
+  projects rm <project-id>
+
+Options:
+  --data-dir <dir>  App data directory; defaults to the desktop app's
+  --json            Print results as JSON
+";
+
+const COMMANDS: [&str; 6] = [
+    "list-sources",
+    "record",
+    "export",
+    "projects",
+    "help",
+    "--help",
+];
+/// Flags that take no value.
+const SWITCHES: [&str; 4] = ["json", "system-audio", "chapter-list", "help"];
+
+#[derive(Debug)]
+pub enum CliCommand {
+    Help,
+    ListSources(SourceType),
+    /// Records until `duration_seconds` or Ctrl+C.
+    Record {
+        options: RecordingOptions,
+        duration_seconds: Option<f64>,
+    },
+    Export {
+        project_id: String,
+        options: ExportOptions,
+        output_dir: Option<PathBuf>,
+    },
+    ListProjects,
+    RemoveProject(String),
+}
+
+#[derive(Debug)]
+pub struct CliInvocation {
+    pub command: CliCommand,
+    pub data_dir: Option<PathBuf>,
+    pub json: bool,
+}
+
+/// Whether the arguments ask for a CLI command rather than the app, which may be handed
+/// project files to open.
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.first()
+        .is_some_and(|command| COMMANDS.contains(&command.as_str()))
+}
+
+#[derive(Debug, Default)]
+struct ParsedArgs {
+    positional: Vec<String>,
+    values: HashMap<String, String>,
+    switches: HashSet<String>,
+}
+
+impl ParsedArgs {
+    fn split(args: &[String]) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                parsed.positional.push(arg.clone());
+                continue;
+            };
+            if let Some((name, value)) = flag.split_once('=') {
+                parsed.values.insert(name.to_string(), value.to_string());
+            } else if SWITCHES.contains(&flag) {
+                parsed.switches.insert(flag.to_string());
+            } else {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("--{flag} needs a value"))?;
+                parsed.values.insert(flag.to_string(), value.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn value(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    fn choice<T: DeserializeOwned>(&mut self, name: &str, default: &str) -> Result<T, String> {
+        let value = self.value(name).unwrap_or_else(|| default.to_string());
+        serde_json::from_value(serde_json::Value::String(value.clone()))
+            .map_err(|_| format!("Invalid --{name} value \"{value}\""))
+    }
+
+    fn switch(&mut self, name: &str) -> bool {
+        self.switches.remove(name)
+    }
+
+    /// Fail on anything the command did not consume.
+    fn finish(self, positional_count: usize) -> Result<(), String> {
+        if let Some(extra) = self.positional.get(positional_count) {
+            return Err(format!("Unexpected argument \"{extra}\""));
+        }
+        if let Some(name) = self.values.keys().chain(&self.switches).min() {
+            return Err(format!("Unknown option --{name}"));
+        }
+        Ok(())
+    }
+}
+
+/// Parse `x,y,width,height` in pixels from the display's top-left corner.
+fn parse_region(value: &str) -> Result<CaptureRegion, String> {
+    let invalid = || format!("Invalid --region value \"{value}\", expected x,y,width,height");
+    let numbers = value
+        .split(',')
+        .map(|part| part.trim().parse::<u32>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let [x, y, width, height] = numbers[..] else {
+        return Err(invalid());
+    };
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok(CaptureRegion {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
+pub fn parse_cli_args(args: &[String]) -> Result<CliInvocation, String> {
+    let mut parsed = ParsedArgs::split(args)?;
+    let data_dir = parsed.value("data-dir").map(PathBuf::from);
+    let json = parsed.switch("json");
+    // `--help` anywhere prints the usage instead of running the command.
+    if parsed.switch("help") {
+        return Ok(CliInvocation {
+            command: CliCommand::Help,
+            data_dir,
+            json,
+        });
+    }
+    let positional = parsed.positional.clone();
+    let positional = positional.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let (command, positional_count) = match positional.as_slice() {
+        ["help", ..] => (CliCommand::Help, 1),
+        ["list-sources", ..] => (
+            CliCommand::ListSources(parsed.choice("type", "display")?),
+            1,
+        ),
+        ["record", ..] => {
+            let source_id = parsed
+                .value("source")
+                .ok_or_else(|| "record needs --source <id>".to_string())?;
+            let region = parsed
+                .value("region")
+                .map(|value| parse_region(&value))
+                .transpose()?;
+            let default_type = if region.is_some() {
+                "region"
+            } else {
+                "display"
+            };
+            let source_type: SourceType = parsed.choice("type", default_type)?;
+            if region.is_some() != (source_type == SourceType::Region) {
+                return Err("--region and --type region go together".to_string());
+            }
+            let duration_seconds = parsed
+                .value("duration")
+                .map(|value| {
+                    value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+                        .ok_or_else(|| format!("Invalid --duration value \"{value}\""))
+                })
+                .transpose()?;
+            let mut camera_device_id = parsed.value("camera-device");
+            let mut microphone_device_id = parsed.value("microphone-device");
+            // A camera or microphone source records that device alone, without the screen.
+            let device_source = match source_type {
+                SourceType::Camera => Some(("camera", &mut camera_device_id)),
+                SourceType::Microphone => Some(("microphone", &mut microphone_device_id)),
+                _ => None,
+            };
+            if let Some((device, device_id)) = device_source {
+                if device_id.is_some() {
+                    return Err(format!(
+                        "--type {device} records the --source device; drop --{device}-device"
+                    ));
+                }
+                *device_id = Some(source_id.clone());
+            }
+            // The macOS recorder leaves the camera and microphone to the app window.
+            if cfg!(target_os = "macos")
+                && (camera_device_id.is_some() || microphone_device_id.is_some())
+            {
+                return Err(
+                    "Recording a camera or microphone from the command line is only supported on Linux"
+                        .to_string(),
+                );
+            }
+            let options = serde_json::from_value(serde_json::json!({
+                "sourceId": source_id,
+                "sourceType": source_type,
+                "captureScreen": source_type.is_screen(),
+                "captureCamera": camera_device_id.is_some(),
+                "cameraDeviceId": camera_device_id,
+                "captureMicrophone": microphone_device_id.is_some(),
+                "microphoneDeviceId": microphone_device_id,
+                "captureSystemAudio": parsed.switch("system-audio"),
+                "region": region,
+            }))
+            .map_err(|error| format!("Invalid recording options: {error}"))?;
+            (
+                CliCommand::Record {
+                    options,
+                    duration_seconds,
+                },
+                1,
+            )
+        }
+        ["export", project_id, ..] => {
+            let format: ExportFormat = parsed.choice("format", "mp4")?;
+            let resolution: ResolutionPreset = parsed.choice("resolution", "1080p")?;
+            let compression: CompressionPreset = parsed.choice("compression", "social")?;
+            let frame_rate = match parsed.value("fps") {
+                Some(value) => value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|rate| *rate > 0)
+                    .ok_or_else(|| format!("Invalid --fps value \"{value}\""))?,
+                None => 30,
+            };
+            let options = ExportOptions {
+                format,
+                frame_rate,
+                compression,
+                resolution,
+                chapter_list: parsed.switch("chapter-list"),
+            };
+            (
+                CliCommand::Export {
+                    project_id: project_id.to_string(),
+                    options,
+                    output_dir: parsed.value("output-dir").map(PathBuf::from),
+                },
+                2,
+            )
+        }
+        ["export"] => return Err("export needs a project id".to_string()),
+        ["projects", "ls"] => (CliCommand::ListProjects, 2),
+        ["projects", "rm", project_id, ..] => {
+            (CliCommand::RemoveProject(project_id.to_string()), 3)
+        }
+        ["projects", "rm"] => return Err("projects rm needs a project id".to_string()),
+        ["projects", ..] => return Err("Use projects ls or projects rm <project-id>".to_string()),
+        [command, ..] => return Err(format!("Unknown command \"{command}\"")),
+        [] => return Err("Missing command".to_string()),
+    };
+    parsed.finish(positional_count)?;
+    Ok(CliInvocation {
+        command,
+        data_dir,
+        json,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn only_known_commands_start_the_cli() {
+        assert!(is_cli_invocation(&args("projects ls")));
+        assert!(!is_cli_invocation(&args("/tmp/demo/project.json")));
+        assert!(!is_cli_invocation(&[]));
+    }
+
+    #[test]
+    fn parses_export_flags_over_the_defaults() {
+        let invocation = parse_cli_args(&args(
+            "export take-1 --resolution=4k --fps 60 --chapter-list --data-dir /tmp/openrec --json",
+        ))
+        .unwrap();
+
+        assert_eq!(invocation.data_dir, Some(PathBuf::from("/tmp/openrec")));
+        assert!(invocation.json);
+        let CliCommand::Export {
+            project_id,
+            options,
+            output_dir,
+        } = invocation.command
+        else {
+            panic!("expected an export command");
+        };
+        assert_eq!(project_id, "take-1");
+        assert!(matches!(options.format, ExportFormat::Mp4));
+        assert!(matches!(options.resolution, ResolutionPreset::P4K));
+        assert!(matches!(options.compression, CompressionPreset::Social));
+        assert_eq!(options.frame_rate, 60);
+        assert!(options.chapter_list);
+        assert_eq!(output_dir, None);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn builds_recording_options_from_record_flags() {
+        let invocation = parse_cli_args(&args(
+            "record --source 1 --duration 12.5 --microphone-device default --system-audio",
+        ))
+        .unwrap();
+        let CliCommand::Record {
+            options,
+            duration_seconds,
+        } = invocation.command
+        else {
+            panic!("expected a record command");
+        };
+        assert_eq!(duration_seconds, Some(12.5));
+        assert_eq!(options.source_id, "1");
+        assert_eq!(options.source_type, SourceType::Display);
+        assert!(options.capture_screen);
+        assert!(!options.capture_camera);
+        assert!(options.capture_microphone);
+        assert_eq!(options.microphone_device_id.as_deref(), Some("default"));
+        assert!(options.capture_system_audio);
+    }
+
+    #[test]
+    fn builds_region_and_device_only_takes_from_record_flags() {
+        let region = parse_cli_args(&args("record --source 0 --region 10,20,640,360")).unwrap();
+        let CliCommand::Record { options, .. } = region.command else {
+            panic!("expected a record command");
+        };
+        assert_eq!(options.source_type, SourceType::Region);
+        assert_eq!(
+            options.region,
+            Some(CaptureRegion {
+                x: 10,
+                y: 20,
+                width: 640,
+                height: 360,
+            })
+        );
+
+        if cfg!(target_os = "macos") {
+            assert!(parse_cli_args(&args("record --source 1 --camera-device 0")).is_err());
+            return;
+        }
+        let camera = parse_cli_args(&args(
+            "record --type camera --source /dev/video0 --microphone-device default",
+        ))
+        .unwrap();
+        let CliCommand::Record { options, .. } = camera.command else {
+            panic!("expected a record command");
+        };
+        assert!(!options.capture_screen);
+        assert!(options.capture_camera);
+        assert_eq!(options.camera_device_id.as_deref(), Some("/dev/video0"));
+        assert!(options.capture_microphone);
+    }
+
+    #[test]
+    fn help_flag_prints_the_usage() {
+        assert!(is_cli_invocation(&args("--help")));
+        for raw in [
+            "--help",
+            "help",
+            "export take-1 --help",
+            "projects rm --help",
+        ] {
+            assert!(
+                matches!(
+                    parse_cli_args(&args(raw)).unwrap().command,
+                    CliCommand::Help
+                ),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_invocations() {
+        for raw in [
+            "record --duration 5",
+            "record --source 1 --duration -1",
+            "record --source 1 --region 10,20,0,360",
+            "record --source 1 --region 10,20,640",
+            "record --source 1 --type region",
+            "record --source 1 --type window --region 0,0,640,360",
+            "record --type camera --source /dev/video0 --camera-device /dev/video2",
+            "export take-1 --format webm",
+            "export take-1 --speed 2",
+            "export take-1 extra",
+            "projects rm",
+            "list-sources --type",
+        ] {
+            assert!(parse_cli_args(&args(raw)).is_err(), "{raw}");
+        }
+        assert!(matches!(
+            parse_cli_args(&args("projects rm take-1")).unwrap().command,
+            CliCommand::RemoveProject(id) if id == "take-1"
+        ));
+    }
+}
@@ -0,0 +1,362 @@
+//! Headless commands for scripts and CI boxes; nothing here starts Tauri or opens a window.
+
+mod args;
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::export::{
+    build_ffmpeg_args, export_output_duration, get_export_output_path, validate_export_inputs,
+    write_youtube_chapter_list, ExportOptions, ExportWorkDir,
+};
+use crate::project;
+use crate::recording::{
+    self, DiskSpaceLevel, DiskSpaceReport, DiskSpaceWatchdog, RecorderState, RecordingOptions,
+    SharedRecorderState,
+};
+use crate::{
+    ensure_recording_disk_headroom, finalize_recording_project, log_if_err, parse_ffmpeg_progress,
+    FfmpegLauncher,
+};
+pub use args::is_cli_invocation;
+use args::{parse_cli_args, CliCommand, USAGE};
+
+/// Must match `identifier` in `tauri.conf.json` so the CLI sees the app's recordings.
+const APP_IDENTIFIER: &str = "com.tommaso.open-rec";
+
+fn default_app_data_dir() -> Result<PathBuf, AppError> {
+    dirs::data_dir()
+        .map(|data_dir| data_dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| {
+            AppError::Message("Could not find the app data directory; pass --data-dir".to_string())
+        })
+}
+
+/// The bundled ffmpeg next to the executable, else `ffmpeg` from `PATH`.
+fn ffmpeg_binary() -> PathBuf {
+    let name = if cfg!(target_os = "windows") {
+        "ffmpeg.exe"
+    } else {
+        "ffmpeg"
+    };
+    std::env::current_exe()
+        .ok()
+        .and_then(|executable| Some(executable.parent()?.join(name)))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|error| AppError::Message(format!("Failed to serialize output: {}", error)))?;
+    println!("{json}");
+    Ok(())
+}
+
+async fn record(
+    app_data_dir: PathBuf,
+    options: RecordingOptions,
+    duration_seconds: Option<f64>,
+    json: bool,
+) -> Result<(), AppError> {
+    if !recording::check_screen_recording_permission() {
+        return Err(AppError::PermissionDenied(
+            "Screen recording permission is not granted".to_string(),
+        ));
+    }
+    let state: SharedRecorderState = Arc::new(Mutex::new(RecorderState::new(app_data_dir)));
+    let recordings_dir = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?
+        .recordings_dir
+        .clone();
+    options.disk_space.validate()?;
+    ensure_recording_disk_headroom(&recordings_dir, options.disk_space.min_free_bytes)?;
+
+    // Lock the take before any media lands in it, so an app started mid-take never offers to
+    // recover or discard it.
+    let project_id = Uuid::new_v4().to_string();
+    let project_dir = recordings_dir.join(&project_id);
+    tokio::fs::create_dir_all(&project_dir)
+        .await
+        .map_err(|error| AppError::Io(format!("Failed to create project dir: {}", error)))?;
+    let _recording_lock = recording::RecordingLock::acquire(&project_dir)?;
+    let disk_space = options.disk_space;
+    let started = recording::start_recording(&state, options, project_id)?;
+    let project_id = started.project_id;
+    // Nobody is around to resume an unattended take, so the floor stops it whatever its action.
+    let (disk_space_sender, mut disk_space_reports) = tokio::sync::mpsc::unbounded_channel();
+    let watchdog = DiskSpaceWatchdog::start(
+        project_id.clone(),
+        project_dir,
+        disk_space,
+        move |report: DiskSpaceReport| {
+            let _ = disk_space_sender.send(report);
+        },
+    );
+    log_if_err(
+        recording::attach_disk_space_watchdog(&state, &project_id, watchdog),
+        "Failed to watch disk space for the recording",
+    );
+    let floor_reached = async {
+        while let Some(report) = disk_space_reports.recv().await {
+            let free_gb = report.free_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+            match report.level {
+                DiskSpaceLevel::Ok => {}
+                DiskSpaceLevel::Low => eprintln!("Low disk space: {free_gb:.2} GB free"),
+                DiskSpaceLevel::Critical => {
+                    eprintln!("Stopping at the disk space floor: {free_gb:.2} GB free");
+                    return;
+                }
+            }
+        }
+        std::future::pending::<()>().await
+    };
+    let stop_after = async {
+        match duration_seconds {
+            Some(seconds) => {
+                eprintln!("Recording {project_id} for {seconds}s, press Ctrl+C to stop early");
+                tokio::time::sleep(std::time::Duration::from_secs_f64(seconds)).await;
+            }
+            None => {
+                eprintln!("Recording {project_id}, press Ctrl+C to stop");
+                std::future::pending::<()>().await
+            }
+        }
+    };
+    tokio::select! {
+        _ = stop_after => {}
+        result = tokio::signal::ctrl_c() => {
+            log_if_err(result, "Failed to wait for Ctrl+C, stopping now");
+        }
+        _ = floor_reached => {}
+    }
+
+    let mut stop_result = recording::stop_recording(&state, &project_id)?;
+    let ffmpeg = FfmpegLauncher::Process(ffmpeg_binary());
+    let finalized =
+        finalize_recording_project(&ffmpeg, &recordings_dir, &mut stop_result, |status| {
+            eprintln!("Finalizing: {status}");
+        })
+        .await;
+    if let Err(error) = finalized {
+        // The app offers to retry finalization from the journal on its next launch.
+        log_if_err(
+            recording::write_pending_finalization_journal(&stop_result),
+            "Failed to persist pending finalization",
+        );
+        return Err(error);
+    }
+
+    if json {
+        print_json(&serde_json::json!({ "projectId": project_id }))
+    } else {
+        println!("{project_id}");
+        Ok(())
+    }
+}
+
+/// Run ffmpeg for an export, drawing its progress on stderr.
+async fn run_export_ffmpeg(args: &[String], expected_duration: f64) -> Result<(), AppError> {
+    let mut child = tokio::process::Command::new(ffmpeg_binary())
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| AppError::Message(format!("Failed to spawn ffmpeg: {}", e)))?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| AppError::Message("Failed to read ffmpeg output".to_string()))?;
+
+    // ffmpeg rewrites its status line with carriage returns, so split on both line endings.
+    let mut pending = Vec::new();
+    let mut last_line = String::new();
+    let mut last_percent = None;
+    let mut buffer = [0; 4096];
+    loop {
+        let read = stderr
+            .read(&mut buffer)
+            .await
+            .map_err(|e| AppError::Io(format!("Failed to read ffmpeg output: {}", e)))?;
+        if read == 0 {
+            break;
+        }
+        pending.extend_from_slice(&buffer[..read]);
+        while let Some(end) = pending
+            .iter()
+            .position(|byte| matches!(byte, b'\r' | b'\n'))
+        {
+            let line = String::from_utf8_lossy(&pending[..end]).trim().to_string();
+            pending.drain(..=end);
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(seconds) = parse_ffmpeg_progress(&line) {
+                let percent = (seconds / expected_duration * 100.0)
+                    .clamp(0.0, 100.0)
+                    .floor();
+                if last_percent != Some(percent) {
+                    last_percent = Some(percent);
+                    eprint!("\rExporting {percent:>3.0}%");
+                }
+            }
+            last_line = line;
+        }
+    }
+    if last_percent.is_some() {
+        eprintln!();
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| AppError::Message(format!("Failed to wait for ffmpeg: {}", e)))?;
+    if status.success() {
+        return Ok(());
+    }
+    let exit_code = match status.code() {
+        Some(code) => code.to_string(),
+        None => "unknown (terminated by signal)".to_string(),
+    };
+    Err(AppError::Message(format!(
+        "ffmpeg failed with exit code {}: {}",
+        exit_code, last_line
+    )))
+}
+
+async fn export(
+    recordings_dir: &PathBuf,
+    project_id: &str,
+    options: ExportOptions,
+    output_dir: Option<PathBuf>,
+    json: bool,
+) -> Result<(), AppError> {
+    let project = project::load_project(recordings_dir, project_id).await?;
+    validate_export_inputs(&project, &options).await?;
+    let output_dir = output_dir
+        .or_else(dirs::download_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let output_path = get_export_output_path(&project, &options, &output_dir);
+    let work_dir = ExportWorkDir::create().await?;
+    let command = build_ffmpeg_args(&project, &options, &output_path, work_dir.path());
+    command.write_side_files().await?;
+    run_export_ffmpeg(&command.args, export_output_duration(&project).max(1.0)).await?;
+    let chapter_list_path = if options.chapter_list {
+        write_youtube_chapter_list(&project, &output_path).await?
+    } else {
+        None
+    };
+
+    if json {
+        return print_json(&serde_json::json!({
+            "outputPath": output_path,
+            "chapterListPath": chapter_list_path,
+        }));
+    }
+    println!("{}", output_path.display());
+    if let Some(chapter_list_path) = chapter_list_path {
+        println!("{}", chapter_list_path.display());
+    }
+    Ok(())
+}
+
+async fn list_projects(recordings_dir: &PathBuf, json: bool) -> Result<(), AppError> {
+    let projects = project::list_projects(recordings_dir).await?;
+    if json {
+        return print_json(&projects);
+    }
+    for project in projects {
+        println!(
+            "{}\t{}\t{:.1}s\t{}",
+            project.id,
+            project.created_at.format("%Y-%m-%d %H:%M"),
+            project.duration,
+            project.name
+        );
+    }
+    Ok(())
+}
+
+async fn run_command(command: CliCommand, app_data_dir: &Path, json: bool) -> Result<(), AppError> {
+    let recordings_dir = app_data_dir.join("recordings");
+    match command {
+        CliCommand::Help => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        CliCommand::ListSources(source_type) => {
+            let sources = recording::list_capture_sources(source_type)?;
+            if json {
+                return print_json(&sources);
+            }
+            for source in sources {
+                println!("{}\t{}", source.id, source.name);
+            }
+            Ok(())
+        }
+        CliCommand::Record {
+            options,
+            duration_seconds,
+        } => record(app_data_dir.to_path_buf(), options, duration_seconds, json).await,
+        CliCommand::Export {
+            project_id,
+            options,
+            output_dir,
+        } => export(&recordings_dir, &project_id, options, output_dir, json).await,
+        CliCommand::ListProjects => list_projects(&recordings_dir, json).await,
+        CliCommand::RemoveProject(project_id) => {
+            // Takes without a project file may still be recording or waiting for recovery.
+            project::validate_project_id(&project_id)?;
+            let project_file = recordings_dir.join(&project_id).join("project.json");
+            if !tokio::fs::try_exists(&project_file).await.unwrap_or(false) {
+                return Err(AppError::Message(format!(
+                    "Project not found: {}",
+                    project_id
+                )));
+            }
+            project::delete_project(&recordings_dir, &project_id).await?;
+            eprintln!("Deleted {project_id}");
+            Ok(())
+        }
+    }
+}
+
+/// Run a command-line invocation and return the process exit code.
+pub fn run_cli(args: &[String]) -> i32 {
+    let invocation = match parse_cli_args(args) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return 2;
+        }
+    };
+    let result = invocation
+        .data_dir
+        .map(Ok)
+        .unwrap_or_else(default_app_data_dir)
+        .and_then(|app_data_dir| {
+            let runtime = tokio::runtime::Runtime::new().map_err(|error| {
+                AppError::Io(format!("Failed to start the async runtime: {}", error))
+            })?;
+            runtime.block_on(run_command(
+                invocation.command,
+                &app_data_dir,
+                invocation.json,
+            ))
+        });
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("error: {error}");
+            1
+        }
+    }
+}
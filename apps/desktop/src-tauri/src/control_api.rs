@@ -0,0 +1,642 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+
+use crate::error::AppError;
+
+const CONTROL_API_SETTINGS_FILE_NAME: &str = "control-api.json";
+pub const DEFAULT_CONTROL_API_PORT: u16 = 47_210;
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// How long a client gets to send its whole request before the connection is dropped.
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Events streamed from `GET /events`, as the webview receives them.
+pub const CONTROL_API_EVENTS: [&str; 10] = [
+    "recording-started",
+    "recording-state-changed",
+    "recording-finalizing",
+    "recording-stopped",
+    "recording-stop-failed",
+    "export-started",
+    "export-progress",
+    "export-complete",
+    "export-error",
+    "export-cancelled",
+];
+
+const fn default_port() -> u16 {
+    DEFAULT_CONTROL_API_PORT
+}
+
+/// Saved next to the recordings so scripts can read the port and token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlApiSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Bearer token every request must carry.
+    pub token: String,
+}
+
+impl Default for ControlApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_CONTROL_API_PORT,
+            token: generate_control_api_token(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlApiStatus {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+    /// Where the settings, and so the token, are saved.
+    pub settings_path: String,
+    /// Port the server is listening on; `None` while it is stopped.
+    pub listening_port: Option<u16>,
+}
+
+pub fn generate_control_api_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub fn control_api_settings_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(CONTROL_API_SETTINGS_FILE_NAME)
+}
+
+/// Saved settings, or disabled defaults with a fresh token when none are readable.
+pub fn load_control_api_settings(path: &Path) -> ControlApiSettings {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return ControlApiSettings::default()
+        }
+        Err(error) => {
+            eprintln!(
+                "Failed to read control API settings {}: {}",
+                path.display(),
+                error
+            );
+            return ControlApiSettings::default();
+        }
+    };
+    match serde_json::from_str::<ControlApiSettings>(&contents) {
+        Ok(settings) if !settings.token.trim().is_empty() => settings,
+        Ok(_) => ControlApiSettings::default(),
+        Err(error) => {
+            eprintln!(
+                "Ignoring invalid control API settings {}: {}",
+                path.display(),
+                error
+            );
+            ControlApiSettings::default()
+        }
+    }
+}
+
+pub fn save_control_api_settings(
+    path: &Path,
+    settings: &ControlApiSettings,
+) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::Io(format!("Failed to create {}: {}", parent.display(), error))
+        })?;
+    }
+    let contents = serde_json::to_string_pretty(settings).map_err(|error| {
+        AppError::Message(format!(
+            "Failed to serialize control API settings: {}",
+            error
+        ))
+    })?;
+    // The file holds the bearer token, so only the user may read it.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                // Files written by older versions were created with the default mode.
+                file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+            }
+            std::io::Write::write_all(&mut file, contents.as_bytes())
+        })
+        .map_err(|error| {
+            AppError::Io(format!(
+                "Failed to write control API settings {}: {}",
+                path.display(),
+                error
+            ))
+        })
+}
+
+/// An app event forwarded to `/events` subscribers with its JSON payload.
+#[derive(Debug, Clone)]
+pub struct ControlEvent {
+    pub name: String,
+    pub payload: String,
+}
+
+pub fn control_event_channel() -> broadcast::Sender<ControlEvent> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
+
+/// A command the control API can run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlRoute {
+    ListSources(String),
+    StartRecording,
+    RecordingSnapshot(String),
+    StopRecording(String),
+    PauseRecording(String),
+    ResumeRecording(String),
+    ListProjects,
+    ExportProject(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Endpoint {
+    Command(ControlRoute),
+    Events,
+}
+
+fn route_request(method: &str, path: &str) -> Option<Endpoint> {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let route = match (method, segments.as_slice()) {
+        ("GET", ["events"]) => return Some(Endpoint::Events),
+        ("GET", ["sources", source_type]) => ControlRoute::ListSources(source_type.to_string()),
+        ("POST", ["recordings"]) => ControlRoute::StartRecording,
+        ("GET", ["recordings", id]) => ControlRoute::RecordingSnapshot(id.to_string()),
+        ("POST", ["recordings", id, "stop"]) => ControlRoute::StopRecording(id.to_string()),
+        ("POST", ["recordings", id, "pause"]) => ControlRoute::PauseRecording(id.to_string()),
+        ("POST", ["recordings", id, "resume"]) => ControlRoute::ResumeRecording(id.to_string()),
+        ("GET", ["projects"]) => ControlRoute::ListProjects,
+        ("POST", ["projects", id, "export"]) => ControlRoute::ExportProject(id.to_string()),
+        _ => return None,
+    };
+    Some(Endpoint::Command(route))
+}
+
+#[derive(Debug)]
+struct ControlRequest {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request(
+    reader: &mut BufReader<impl tokio::io::AsyncRead + Unpin>,
+) -> Result<ControlRequest, String> {
+    let mut header_bytes = 0;
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|error| error.to_string())?;
+        header_bytes += read;
+        if read == 0 || header_bytes > MAX_HEADER_BYTES {
+            return Err("Malformed request headers".to_string());
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    let mut request_line = lines
+        .first()
+        .map(|line| line.split_whitespace())
+        .into_iter()
+        .flatten();
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err("Malformed request line".to_string());
+    };
+    let mut authorization = None;
+    let mut content_length = 0;
+    for (name, value) in lines[1..].iter().filter_map(|line| line.split_once(':')) {
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse::<usize>()
+                .map_err(|_| "Invalid Content-Length".to_string())?;
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err("Request body is too large".to_string());
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(ControlRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        authorization,
+        body,
+    })
+}
+
+/// Compares without exiting early so response timing does not leak the token.
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let provided = provided.trim().as_bytes();
+    let expected = token.as_bytes();
+    provided.len() == expected.len()
+        && provided
+            .iter()
+            .zip(expected)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+fn error_status(error: &AppError) -> u16 {
+    match error {
+        AppError::Message(_) => 400,
+        AppError::PermissionDenied(_) => 403,
+        AppError::Io(_) | AppError::Lock(_) => 500,
+    }
+}
+
+fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+fn json_response(status: u16, body: &serde_json::Value) -> Vec<u8> {
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        status_reason(status),
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+fn error_response(error: &AppError) -> Vec<u8> {
+    let body = serde_json::to_value(error)
+        .unwrap_or_else(|_| serde_json::json!({ "message": error.to_string() }));
+    json_response(error_status(error), &body)
+}
+
+/// One Server-Sent Events frame; multi-line payloads become several `data:` lines.
+fn sse_frame(event: &ControlEvent) -> String {
+    let mut frame = format!("event: {}\n", event.name);
+    for line in event.payload.lines() {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    frame.push('\n');
+    frame
+}
+
+async fn stream_events(
+    stream: &mut (impl AsyncWrite + Unpin),
+    mut events: broadcast::Receiver<ControlEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+    stream.flush().await?;
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    stream.write_all(sse_frame(&event).as_bytes()).await?;
+                    stream.flush().await?;
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    stream
+                        .write_all(format!(": skipped {skipped} events\n\n").as_bytes())
+                        .await?;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = shutdown.changed() => return Ok(()),
+        }
+    }
+}
+
+async fn handle_connection<F, Fut>(
+    stream: TcpStream,
+    token: Arc<String>,
+    events: broadcast::Sender<ControlEvent>,
+    shutdown: watch::Receiver<bool>,
+    handler: Arc<F>,
+) -> std::io::Result<()>
+where
+    F: Fn(ControlRoute, Vec<u8>) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value, AppError>>,
+{
+    let mut reader = BufReader::new(stream);
+    // A client that stops sending mid-request would otherwise hold the connection forever.
+    let request = match tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(&mut reader)).await
+    {
+        Ok(Ok(request)) => request,
+        Ok(Err(message)) => {
+            let response = json_response(400, &serde_json::json!({ "message": message }));
+            return reader.get_mut().write_all(&response).await;
+        }
+        Err(_) => {
+            let response = json_response(
+                408,
+                &serde_json::json!({ "message": "Timed out reading the request" }),
+            );
+            return reader.get_mut().write_all(&response).await;
+        }
+    };
+    let stream = reader.get_mut();
+    if !is_authorized(request.authorization.as_deref(), &token) {
+        let response = json_response(401, &serde_json::json!({ "message": "Invalid token" }));
+        return stream.write_all(&response).await;
+    }
+    let response = match route_request(&request.method, &request.path) {
+        Some(Endpoint::Events) => {
+            return stream_events(stream, events.subscribe(), shutdown).await;
+        }
+        Some(Endpoint::Command(route)) => match handler(route, request.body).await {
+            Ok(body) => json_response(200, &body),
+            Err(error) => error_response(&error),
+        },
+        None => json_response(404, &serde_json::json!({ "message": "Not found" })),
+    };
+    stream.write_all(&response).await
+}
+
+/// Control server listening on the loopback interface.
+#[derive(Debug)]
+pub struct ControlServer {
+    port: u16,
+    shutdown: watch::Sender<bool>,
+}
+
+impl ControlServer {
+    /// Listen on `127.0.0.1:port` and run `handler` for each authorized command.
+    ///
+    /// Must be called from within the Tokio runtime.
+    pub fn start<F, Fut>(
+        port: u16,
+        token: String,
+        events: broadcast::Sender<ControlEvent>,
+        handler: F,
+    ) -> Result<Self, AppError>
+    where
+        F: Fn(ControlRoute, Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, AppError>> + Send + 'static,
+    {
+        let listener = std::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                TcpListener::from_std(listener)
+            })
+            .map_err(|error| {
+                AppError::Io(format!(
+                    "Failed to listen on 127.0.0.1:{} for the control API: {}",
+                    port, error
+                ))
+            })?;
+        let port = listener
+            .local_addr()
+            .map(|address| address.port())
+            .unwrap_or(port);
+        let (shutdown, mut shutdown_requested) = watch::channel(false);
+        let connection_shutdown = shutdown_requested.clone();
+        let token = Arc::new(token);
+        let handler = Arc::new(handler);
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(error) => {
+                            eprintln!("Control API failed to accept a connection: {}", error);
+                            continue;
+                        }
+                    },
+                    _ = shutdown_requested.changed() => break,
+                };
+                let token = Arc::clone(&token);
+                let events = events.clone();
+                let shutdown = connection_shutdown.clone();
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    if let Err(error) =
+                        handle_connection(stream, token, events, shutdown, handler).await
+                    {
+                        eprintln!("Control API connection failed: {}", error);
+                    }
+                });
+            }
+        });
+        Ok(Self { port, shutdown })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Stop accepting connections and end open event streams.
+    pub fn stop(self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
+/// Settings in effect and the server running them, if enabled.
+#[derive(Debug)]
+pub struct ControlApiState {
+    pub settings: ControlApiSettings,
+    pub settings_path: PathBuf,
+    pub server: Option<ControlServer>,
+    pub events: broadcast::Sender<ControlEvent>,
+}
+
+impl ControlApiState {
+    pub fn status(&self) -> ControlApiStatus {
+        ControlApiStatus {
+            enabled: self.settings.enabled,
+            port: self.settings.port,
+            token: self.settings.token.clone(),
+            settings_path: self.settings_path.to_string_lossy().to_string(),
+            listening_port: self.server.as_ref().map(ControlServer::port),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn routes_commands_and_the_event_stream() {
+        assert_eq!(
+            route_request("POST", "/recordings/abc/stop"),
+            Some(Endpoint::Command(ControlRoute::StopRecording(
+                "abc".to_string()
+            )))
+        );
+        assert_eq!(
+            route_request("GET", "/projects?limit=5"),
+            Some(Endpoint::Command(ControlRoute::ListProjects))
+        );
+        assert_eq!(
+            route_request("GET", "/sources/display"),
+            Some(Endpoint::Command(ControlRoute::ListSources(
+                "display".to_string()
+            )))
+        );
+        assert_eq!(
+            route_request("POST", "/recordings/abc/resume"),
+            Some(Endpoint::Command(ControlRoute::ResumeRecording(
+                "abc".to_string()
+            )))
+        );
+        assert_eq!(route_request("GET", "/events"), Some(Endpoint::Events));
+        assert_eq!(route_request("GET", "/recordings/abc/stop"), None);
+        assert_eq!(route_request("DELETE", "/projects"), None);
+    }
+
+    #[test]
+    fn accepts_only_the_exact_bearer_token() {
+        assert!(is_authorized(Some("Bearer secret"), "secret"));
+        assert!(!is_authorized(Some("Bearer secre"), "secret"));
+        assert!(!is_authorized(Some("secret"), "secret"));
+        assert!(!is_authorized(None, "secret"));
+    }
+
+    #[test]
+    fn frames_multi_line_payloads_as_separate_data_lines() {
+        let frame = sse_frame(&ControlEvent {
+            name: "export-progress".to_string(),
+            payload: "{\n\"progress\": 0.5\n}".to_string(),
+        });
+        assert_eq!(
+            frame,
+            "event: export-progress\ndata: {\ndata: \"progress\": 0.5\ndata: }\n\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saves_the_token_readable_by_the_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir()
+            .join(format!("openrec-control-{}", generate_control_api_token()))
+            .join(CONTROL_API_SETTINGS_FILE_NAME);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let settings = ControlApiSettings::default();
+        save_control_api_settings(&path, &settings).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let reloaded = load_control_api_settings(&path);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(reloaded.token, settings.token);
+    }
+
+    async fn send(port: u16, request: &str) -> String {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+            .await
+            .unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_authorized_commands_over_loopback() {
+        let server = ControlServer::start(
+            0,
+            "secret".to_string(),
+            control_event_channel(),
+            |route, body| async move {
+                match route {
+                    ControlRoute::StopRecording(id) => Ok(serde_json::json!({
+                        "stopped": id,
+                        "body": String::from_utf8_lossy(&body),
+                    })),
+                    _ => Err(AppError::PermissionDenied("nope".to_string())),
+                }
+            },
+        )
+        .unwrap();
+        let port = server.port();
+
+        let response = send(
+            port,
+            "POST /recordings/take-1/stop HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: 2\r\n\r\n{}",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(r#"{"body":"{}","stopped":"take-1"}"#));
+
+        let response = send(
+            port,
+            "GET /projects HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403"));
+        assert!(response.contains(r#""kind":"permissionDenied""#));
+
+        let response = send(
+            port,
+            "GET /projects HTTP/1.1\r\nAuthorization: Bearer wrong\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 401"));
+
+        server.stop();
+    }
+}
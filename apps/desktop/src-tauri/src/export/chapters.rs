@@ -0,0 +1,283 @@
+use std::path::{Path, PathBuf};
+
+use super::{
+    build_timeline_pieces, map_source_range_to_output, output_timeline_duration, ExportSideFile,
+    TimelinePiece,
+};
+use crate::error::AppError;
+use crate::project::Project;
+
+const CHAPTER_METADATA_FILE_NAME: &str = "chapters.ffmetadata";
+const CHAPTER_LIST_EXTENSION: &str = "chapters.txt";
+/// Chapters closer together than this on the output timeline collapse into the later one.
+const MIN_CHAPTER_SECONDS: f64 = 0.001;
+
+/// A project chapter placed on the output timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct OutputChapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+/// Output time of a source time; a time inside a cut lands where the timeline resumes.
+fn map_source_time_to_output(pieces: &[TimelinePiece], time: f64) -> Option<f64> {
+    map_source_range_to_output(pieces, time, f64::INFINITY)
+        .first()
+        .map(|(start, _)| *start)
+}
+
+/// Chapters in output order, each running until the next one or the end of the export.
+pub(super) fn output_chapters(project: &Project, pieces: &[TimelinePiece]) -> Vec<OutputChapter> {
+    let mut sources = project
+        .edits
+        .chapters
+        .iter()
+        .enumerate()
+        .collect::<Vec<_>>();
+    sources.sort_by(|(_, a), (_, b)| a.start_time.total_cmp(&b.start_time));
+    let mut starts = sources
+        .into_iter()
+        .filter_map(|(index, chapter)| {
+            let start = map_source_time_to_output(pieces, chapter.start_time)?;
+            let title = chapter
+                .title
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let title = if title.is_empty() {
+                format!("Chapter {}", index + 1)
+            } else {
+                title
+            };
+            Some((start, title))
+        })
+        .collect::<Vec<_>>();
+    starts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let duration = output_timeline_duration(pieces);
+    let mut chapters: Vec<OutputChapter> = Vec::with_capacity(starts.len());
+    for (start, title) in starts {
+        if start >= duration - MIN_CHAPTER_SECONDS {
+            continue;
+        }
+        // Cuts can fold several chapters onto one instant; the latest one starts there.
+        match chapters.last_mut() {
+            Some(previous) if start - previous.start < MIN_CHAPTER_SECONDS => {
+                previous.title = title;
+            }
+            _ => chapters.push(OutputChapter {
+                start,
+                end: duration,
+                title,
+            }),
+        }
+    }
+    for index in 1..chapters.len() {
+        chapters[index - 1].end = chapters[index].start;
+    }
+    chapters
+}
+
+fn escape_ffmetadata_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+fn build_ffmetadata(chapters: &[OutputChapter]) -> String {
+    let mut metadata = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        metadata.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (chapter.start * 1000.0).round() as u64,
+            (chapter.end * 1000.0).round() as u64,
+            escape_ffmetadata_value(&chapter.title)
+        ));
+    }
+    metadata
+}
+
+/// The chapters as an ffmetadata file in `work_dir`, for ffmpeg to read as an extra input.
+pub(super) fn chapter_metadata_side_file(
+    work_dir: &Path,
+    chapters: &[OutputChapter],
+) -> ExportSideFile {
+    ExportSideFile {
+        path: work_dir.join(CHAPTER_METADATA_FILE_NAME),
+        contents: build_ffmetadata(chapters),
+    }
+}
+
+fn format_chapter_timestamp(seconds: f64) -> String {
+    let total_seconds = seconds.max(0.0).floor() as u64;
+    let hours = total_seconds / 3600;
+    let minutes = total_seconds % 3600 / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+/// YouTube-style "00:00 Title" list of the export's chapters.
+///
+/// YouTube only picks up a list that starts at 00:00, so an "Intro" line covers any
+/// footage before the first chapter.
+fn youtube_chapter_list(project: &Project) -> Option<String> {
+    let chapters = output_chapters(project, &build_timeline_pieces(project));
+    let first = chapters.first()?;
+    let mut lines = Vec::with_capacity(chapters.len() + 1);
+    if first.start >= 1.0 {
+        lines.push(format!("{} Intro", format_chapter_timestamp(0.0)));
+    }
+    lines.extend(chapters.iter().map(|chapter| {
+        format!(
+            "{} {}",
+            format_chapter_timestamp(chapter.start),
+            chapter.title
+        )
+    }));
+    Some(lines.join("\n") + "\n")
+}
+
+/// Write the chapter list next to an export as `<name>.chapters.txt`.
+pub async fn write_youtube_chapter_list(
+    project: &Project,
+    output_path: &Path,
+) -> Result<Option<PathBuf>, AppError> {
+    let Some(chapter_list) = youtube_chapter_list(project) else {
+        return Ok(None);
+    };
+    let list_path = output_path.with_extension(CHAPTER_LIST_EXTENSION);
+    tokio::fs::write(&list_path, chapter_list)
+        .await
+        .map_err(|error| {
+            AppError::Io(format!(
+                "Failed to write chapter list {}: {}",
+                list_path.display(),
+                error
+            ))
+        })?;
+    Ok(Some(list_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Chapter, Segment, SpeedEffect};
+
+    /// 0-10s is cut and 20-30s plays at double speed, so the export is 15 seconds long.
+    fn chaptered_project(chapters: &[(&str, f64)]) -> Project {
+        let mut project = Project::new(
+            "chapters-project".to_string(),
+            Some(PathBuf::from("/tmp/chapters/screen.mp4")),
+            None,
+            None,
+            30.0,
+            1920,
+            1080,
+            None,
+            None,
+        );
+        project.edits.segments = vec![
+            Segment {
+                id: "cut".to_string(),
+                start_time: 0.0,
+                end_time: 10.0,
+                enabled: false,
+            },
+            Segment {
+                id: "kept".to_string(),
+                start_time: 10.0,
+                end_time: 30.0,
+                enabled: true,
+            },
+        ];
+        project.edits.speed.push(SpeedEffect {
+            id: "fast".to_string(),
+            start_time: 20.0,
+            end_time: 30.0,
+            speed: 2.0,
+        });
+        project.edits.chapters = chapters
+            .iter()
+            .enumerate()
+            .map(|(index, (title, start_time))| Chapter {
+                id: format!("chapter-{index}"),
+                title: title.to_string(),
+                start_time: *start_time,
+            })
+            .collect();
+        project
+    }
+
+    #[test]
+    fn remaps_chapters_onto_the_output_timeline() {
+        let project = chaptered_project(&[
+            ("Wrap up", 24.0),
+            ("Setup", 2.0),
+            ("Cut aside", 6.0),
+            ("  Live\ndemo ", 12.0),
+            ("", 29.0),
+        ]);
+        let chapters = output_chapters(&project, &build_timeline_pieces(&project));
+
+        assert_eq!(
+            chapters,
+            vec![
+                OutputChapter {
+                    start: 0.0,
+                    end: 2.0,
+                    title: "Cut aside".to_string(),
+                },
+                OutputChapter {
+                    start: 2.0,
+                    end: 12.0,
+                    title: "Live demo".to_string(),
+                },
+                OutputChapter {
+                    start: 12.0,
+                    end: 14.5,
+                    title: "Wrap up".to_string(),
+                },
+                OutputChapter {
+                    start: 14.5,
+                    end: 15.0,
+                    title: "Chapter 5".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_ffmetadata_chapters_in_milliseconds() {
+        let metadata = build_ffmetadata(&[OutputChapter {
+            start: 1.0,
+            end: 12.5,
+            title: "Q&A; part=2".to_string(),
+        }]);
+
+        assert_eq!(
+            metadata,
+            ";FFMETADATA1\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=1000\nEND=12500\ntitle=Q&A\\; part\\=2\n"
+        );
+    }
+
+    #[test]
+    fn lists_chapters_from_zero_for_youtube() {
+        let project = chaptered_project(&[("Demo", 12.0), ("Wrap up", 24.0)]);
+        assert_eq!(
+            youtube_chapter_list(&project).as_deref(),
+            Some("00:00 Intro\n00:02 Demo\n00:12 Wrap up\n")
+        );
+        assert_eq!(format_chapter_timestamp(3_725.4), "1:02:05");
+        assert_eq!(youtube_chapter_list(&chaptered_project(&[])), None);
+    }
+}
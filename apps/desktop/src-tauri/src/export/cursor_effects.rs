@@ -0,0 +1,267 @@
+use std::path::Path;
+
+use super::ExportSideFile;
+use crate::project::{CursorEffectsSettings, CursorHighlightStyle, Project};
+use crate::recording::{read_cursor_events, CursorEvent, CursorEventKind};
+
+const CURSOR_EFFECTS_SCRIPT_FILE_NAME: &str = "cursor-effects.cmd";
+const CURSOR_OVERLAY_NAME: &str = "overlay@cursor";
+/// Minimum spacing between scripted cursor positions.
+const CURSOR_TRACK_INTERVAL_MS: u64 = 50;
+const CLICK_RIPPLE_SECONDS: f64 = 0.5;
+/// Each ripple is its own overlay, so very click-heavy recordings only ripple the first clicks.
+const MAX_CLICK_RIPPLES: usize = 200;
+
+fn escape_filter_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace(':', "\\:")
+        .replace('\'', "\\'")
+}
+
+fn cursor_effects_requested(settings: &CursorEffectsSettings) -> bool {
+    settings.highlight != CursorHighlightStyle::None || settings.click_ripples
+}
+
+/// Cursor log events for a project whose cursor effects are switched on.
+pub(super) fn load_export_cursor_events(project: &Project) -> Option<Vec<CursorEvent>> {
+    if !cursor_effects_requested(&project.edits.cursor_effects) {
+        return None;
+    }
+    let log_path = project.cursor_log_path.as_ref()?;
+    match read_cursor_events(Path::new(log_path)) {
+        Ok(events) if !events.is_empty() => Some(events),
+        Ok(_) => None,
+        Err(error) => {
+            eprintln!("Skipping cursor effects: {}", error);
+            None
+        }
+    }
+}
+
+/// Overlay position for the sprite, relative to the main frame, for one cursor sample.
+fn cursor_position_expressions(
+    style: CursorHighlightStyle,
+    event: &CursorEvent,
+) -> (String, String) {
+    if !event.is_inside_capture() {
+        return ("-w".to_string(), "-h".to_string());
+    }
+    match style {
+        CursorHighlightStyle::Enlarged => {
+            (format!("W*{:.6}", event.x), format!("H*{:.6}", event.y))
+        }
+        _ => (
+            format!("W*{:.6}-w/2", event.x),
+            format!("H*{:.6}-h/2", event.y),
+        ),
+    }
+}
+
+/// `sendcmd` script that moves the cursor sprite overlay along the recorded path.
+fn build_cursor_track_script(style: CursorHighlightStyle, events: &[CursorEvent]) -> String {
+    let mut script = String::new();
+    let mut last_emitted: Option<(u64, String, String)> = None;
+    for event in events {
+        let (x, y) = cursor_position_expressions(style, event);
+        if let Some((time_ms, last_x, last_y)) = &last_emitted {
+            if (*last_x == x && *last_y == y) || event.time_ms < time_ms + CURSOR_TRACK_INTERVAL_MS
+            {
+                continue;
+            }
+        }
+        script.push_str(&format!(
+            "{:.3} {} x {}, {} y {};\n",
+            event.time_ms as f64 / 1000.0,
+            CURSOR_OVERLAY_NAME,
+            x,
+            CURSOR_OVERLAY_NAME,
+            y
+        ));
+        last_emitted = Some((event.time_ms, x, y));
+    }
+    script
+}
+
+/// Generated RGBA sprite drawn at the cursor position.
+fn build_cursor_sprite_source(settings: &CursorEffectsSettings, frame_rate: u32) -> String {
+    let size = settings.size.clamp(8, 256);
+    match settings.highlight {
+        CursorHighlightStyle::Enlarged => {
+            let outline = (size as f64 / 16.0).max(1.5);
+            let outer = "gte(Y,X*1.03)*lte(Y,H-0.4*X)";
+            let inner = format!(
+                "gte(X,{outline:.2})*gte(Y,X*1.03+{:.2})*lte(Y,H-0.4*X-{:.2})",
+                outline * 1.45,
+                outline * 1.08
+            );
+            format!(
+                "color=c=white:s={}x{}:r={},format=rgba,geq=r='255*{inner}':g='255*{inner}':b='255*{inner}':a='255*{outer}'",
+                (size as f64 * 0.72).round() as u32 + 1,
+                size,
+                frame_rate
+            )
+        }
+        _ => format!(
+            "color=c={}:s={size}x{size}:r={},format=rgba,geq=r='r(X,Y)':g='g(X,Y)':b='b(X,Y)':a='255*{:.3}*lte(hypot(X-W/2,Y-H/2),W/2)'",
+            settings.color,
+            frame_rate,
+            settings.opacity.clamp(0.05, 1.0)
+        ),
+    }
+}
+
+fn click_ripple_events(events: &[CursorEvent]) -> Vec<&CursorEvent> {
+    let mut ripples: Vec<&CursorEvent> = Vec::new();
+    for event in events
+        .iter()
+        .filter(|event| event.kind == CursorEventKind::Down && event.is_inside_capture())
+    {
+        let overlaps_previous = ripples.last().is_some_and(|previous| {
+            event.time_ms.saturating_sub(previous.time_ms) as f64 / 1000.0 < CLICK_RIPPLE_SECONDS
+        });
+        if !overlaps_previous {
+            ripples.push(event);
+        }
+        if ripples.len() == MAX_CLICK_RIPPLES {
+            break;
+        }
+    }
+    ripples
+}
+
+/// Draw the cursor highlight and click ripples onto the untrimmed screen track.
+///
+/// Effects go in before the timeline is cut so cursor times stay on the recording clock
+/// and zooms scale the highlight together with the screen.
+pub(super) fn apply_cursor_effects(
+    filter_parts: &mut Vec<String>,
+    side_files: &mut Vec<ExportSideFile>,
+    work_dir: &Path,
+    input_label: &str,
+    project: &Project,
+    events: &[CursorEvent],
+    frame_rate: u32,
+) -> String {
+    let settings = &project.edits.cursor_effects;
+    let mut events = events.to_vec();
+    events.sort_by_key(|event| event.time_ms);
+    let mut current_label = input_label.to_string();
+
+    if settings.highlight != CursorHighlightStyle::None {
+        let script_path = work_dir.join(CURSOR_EFFECTS_SCRIPT_FILE_NAME);
+        side_files.push(ExportSideFile {
+            path: script_path.clone(),
+            contents: build_cursor_track_script(settings.highlight, &events),
+        });
+        filter_parts.push(format!(
+            "{}sendcmd=f='{}'[vcursorcmd]",
+            current_label,
+            escape_filter_path(&script_path)
+        ));
+        filter_parts.push(format!(
+            "{}[vcursorsprite]",
+            build_cursor_sprite_source(settings, frame_rate)
+        ));
+        filter_parts.push(format!(
+            "[vcursorcmd][vcursorsprite]{}=x=-w:y=-h:eval=frame:shortest=1[vcursor]",
+            CURSOR_OVERLAY_NAME
+        ));
+        current_label = "[vcursor]".to_string();
+    }
+
+    let ripples = if settings.click_ripples {
+        click_ripple_events(&events)
+    } else {
+        Vec::new()
+    };
+    if ripples.is_empty() {
+        return current_label;
+    }
+
+    let diameter = settings.size.clamp(8, 256) * 2;
+    let ripple_source_labels = (0..ripples.len())
+        .map(|index| format!("[cursorripplesrc{}]", index))
+        .collect::<Vec<_>>();
+    filter_parts.push(format!(
+        "color=c={}:s={diameter}x{diameter}:r={}:d={CLICK_RIPPLE_SECONDS},format=rgba,geq=r='r(X,Y)':g='g(X,Y)':b='b(X,Y)':a='230*(1-T/{CLICK_RIPPLE_SECONDS})*lte(abs(hypot(X-W/2,Y-H/2)-(W/2-3)*T/{CLICK_RIPPLE_SECONDS}),3)',split={}{}",
+        settings.color,
+        frame_rate,
+        ripples.len(),
+        ripple_source_labels.join("")
+    ));
+    for (index, (ripple, source_label)) in ripples.iter().zip(&ripple_source_labels).enumerate() {
+        let ripple_label = format!("[cursorripple{}]", index);
+        let output_label = format!("[vripple{}]", index);
+        filter_parts.push(format!(
+            "{}setpts=PTS+{:.6}/TB{}",
+            source_label,
+            ripple.time_ms as f64 / 1000.0,
+            ripple_label
+        ));
+        filter_parts.push(format!(
+            "{}{}overlay=x=W*{:.6}-w/2:y=H*{:.6}-h/2:eof_action=pass{}",
+            current_label, ripple_label, ripple.x, ripple.y, output_label
+        ));
+        current_label = output_label;
+    }
+
+    current_label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor_event(time_ms: u64, kind: CursorEventKind, x: f64, y: f64) -> CursorEvent {
+        CursorEvent {
+            time_ms,
+            kind,
+            x,
+            y,
+            button: (kind != CursorEventKind::Move).then_some(1),
+        }
+    }
+
+    #[test]
+    fn cursor_track_script_hides_sprite_outside_capture_and_thins_samples() {
+        let events = vec![
+            cursor_event(0, CursorEventKind::Move, 0.5, 0.5),
+            cursor_event(20, CursorEventKind::Move, 0.52, 0.5),
+            cursor_event(100, CursorEventKind::Move, 0.6, 0.4),
+            cursor_event(200, CursorEventKind::Move, 1.2, 0.4),
+        ];
+
+        let script = build_cursor_track_script(CursorHighlightStyle::Halo, &events);
+
+        assert_eq!(
+            script,
+            concat!(
+                "0.000 overlay@cursor x W*0.500000-w/2, overlay@cursor y H*0.500000-h/2;\n",
+                "0.100 overlay@cursor x W*0.600000-w/2, overlay@cursor y H*0.400000-h/2;\n",
+                "0.200 overlay@cursor x -w, overlay@cursor y -h;\n",
+            )
+        );
+    }
+
+    #[test]
+    fn click_ripples_skip_clicks_during_a_running_ripple() {
+        let events = vec![
+            cursor_event(1_000, CursorEventKind::Down, 0.5, 0.5),
+            cursor_event(1_200, CursorEventKind::Down, 0.5, 0.5),
+            cursor_event(1_300, CursorEventKind::Up, 0.5, 0.5),
+            cursor_event(2_000, CursorEventKind::Down, 0.3, 0.3),
+            cursor_event(3_000, CursorEventKind::Down, -0.3, 0.3),
+        ];
+
+        let ripples = click_ripple_events(&events);
+
+        assert_eq!(
+            ripples
+                .iter()
+                .map(|event| event.time_ms)
+                .collect::<Vec<_>>(),
+            vec![1_000, 2_000]
+        );
+    }
+}
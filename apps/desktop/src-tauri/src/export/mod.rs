@@ -1,8 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::project::{AnnotationMode, Project};
+use crate::project::{AnnotationMode, Project, ZoomEasing};
+
+mod chapters;
+mod cursor_effects;
+
+pub use chapters::write_youtube_chapter_list;
+use chapters::{chapter_metadata_side_file, output_chapters};
+use cursor_effects::{apply_cursor_effects, load_export_cursor_events};
 
 /// Export options from the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +20,9 @@ pub struct ExportOptions {
     pub frame_rate: u32,
     pub compression: CompressionPreset,
     pub resolution: ResolutionPreset,
+    /// Also write a YouTube-style chapter list next to the export.
+    #[serde(default)]
+    pub chapter_list: bool,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -112,11 +123,25 @@ fn target_video_bitrate_kbps(options: &ExportOptions) -> u32 {
     (base * multiplier).round() as u32
 }
 
+/// Longest edited timeline that can be exported as a GIF.
+const MAX_GIF_DURATION_SECONDS: f64 = 60.0;
+
 #[derive(Debug, Clone)]
 struct ActiveZoom {
     scale: f64,
     x: f64,
     y: f64,
+    start_time: f64,
+    end_time: f64,
+    ease_in: f64,
+    ease_out: f64,
+    easing: ZoomEasing,
+}
+
+impl ActiveZoom {
+    fn is_eased(&self) -> bool {
+        self.ease_in > 0.001 || self.ease_out > 0.001
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -174,7 +199,9 @@ pub async fn validate_export_inputs(
     project: &Project,
     options: &ExportOptions,
 ) -> Result<(), AppError> {
-    validate_media_file(&project.screen_video_path, "Screen recording").await?;
+    if let Some(screen_path) = &project.screen_video_path {
+        validate_media_file(screen_path, "Screen recording").await?;
+    }
 
     if let Some(camera_path) = &project.camera_video_path {
         validate_media_file(camera_path, "Camera recording").await?;
@@ -184,26 +211,54 @@ pub async fn validate_export_inputs(
         validate_media_file(mic_path, "Microphone recording").await?;
     }
 
+    if let Some(system_audio_path) = &project.system_audio_path {
+        validate_media_file(system_audio_path, "System audio recording").await?;
+    }
+
+    project.edits.cursor_effects.validate()?;
+
     if project.edits.segments.iter().all(|s| !s.enabled) {
         return Err(AppError::Message(
             "No enabled timeline segments to export".to_string(),
         ));
     }
 
-    if matches!(options.format, ExportFormat::Wav | ExportFormat::Mp3) {
-        let has_screen_audio = has_audio_stream(&project.screen_video_path);
-        let has_microphone_audio = project.microphone_audio_path.is_some();
-        if !has_screen_audio && !has_microphone_audio {
-            return Err(AppError::Message(
-                "Audio export requires at least one audio source (system or microphone)"
-                    .to_string(),
-            ));
+    if matches!(options.format, ExportFormat::Gif) {
+        let output_duration = export_output_duration(project);
+        if output_duration > MAX_GIF_DURATION_SECONDS {
+            return Err(AppError::Message(format!(
+                "GIF export is limited to {:.0} seconds; the edited timeline is {:.1} seconds",
+                MAX_GIF_DURATION_SECONDS, output_duration
+            )));
         }
     }
 
+    if matches!(options.format, ExportFormat::Wav | ExportFormat::Mp3)
+        && !has_audio_source(project, has_audio_stream)
+    {
+        return Err(AppError::Message(
+            "Audio export requires at least one audio source (system or microphone)".to_string(),
+        ));
+    }
+
     Ok(())
 }
 
+/// The main video of the export; camera-only takes export the camera instead of an overlay.
+fn primary_video_path(project: &Project) -> Option<&String> {
+    project
+        .screen_video_path
+        .as_ref()
+        .or(project.camera_video_path.as_ref())
+}
+
+/// Whether the export can mix any audio, with `has_audio` probing the main video for a track.
+fn has_audio_source(project: &Project, has_audio: impl Fn(&str) -> bool) -> bool {
+    project.system_audio_path.is_some()
+        || project.microphone_audio_path.is_some()
+        || primary_video_path(project).is_some_and(|path| has_audio(path))
+}
+
 fn atempo_chain(speed: f64) -> Option<String> {
     if !speed.is_finite() || speed <= 0.0 || (speed - 1.0).abs() < 0.01 {
         return None;
@@ -298,6 +353,11 @@ fn build_timeline_pieces(project: &Project) -> Vec<TimelinePiece> {
                             scale: effect.scale,
                             x: effect.x,
                             y: effect.y,
+                            start_time: effect.start_time,
+                            end_time: effect.end_time,
+                            ease_in: effect.ease_in.max(0.0),
+                            ease_out: effect.ease_out.max(0.0),
+                            easing: effect.easing,
                         })
                     } else {
                         None
@@ -325,6 +385,66 @@ fn build_timeline_pieces(project: &Project) -> Vec<TimelinePiece> {
     pieces
 }
 
+/// Output-timeline start offset of each piece, after cuts and speed changes.
+fn timeline_piece_output_offsets(pieces: &[TimelinePiece]) -> Vec<f64> {
+    let mut offsets = Vec::with_capacity(pieces.len());
+    let mut cursor = 0.0;
+    for piece in pieces {
+        offsets.push(cursor);
+        cursor += (piece.end - piece.start).max(0.0) / piece.speed.max(0.01);
+    }
+    offsets
+}
+
+/// Map a source-timeline range onto the output timeline.
+///
+/// The range is split across every piece it overlaps; parts that fall in disabled
+/// segments are dropped, and ranges that end up contiguous in the output are merged.
+fn map_source_range_to_output(pieces: &[TimelinePiece], start: f64, end: f64) -> Vec<(f64, f64)> {
+    let offsets = timeline_piece_output_offsets(pieces);
+    let mut ranges: Vec<(f64, f64)> = Vec::new();
+
+    for (piece, offset) in pieces.iter().zip(offsets) {
+        let overlap_start = start.max(piece.start);
+        let overlap_end = end.min(piece.end);
+        if overlap_end <= overlap_start {
+            continue;
+        }
+
+        let speed = piece.speed.max(0.01);
+        let mapped_start = offset + (overlap_start - piece.start) / speed;
+        let mapped_end = offset + (overlap_end - piece.start) / speed;
+        match ranges.last_mut() {
+            Some(previous) if (mapped_start - previous.1).abs() < 0.0005 => {
+                previous.1 = mapped_end;
+            }
+            _ => ranges.push((mapped_start, mapped_end)),
+        }
+    }
+
+    ranges
+}
+
+fn output_timeline_duration(pieces: &[TimelinePiece]) -> f64 {
+    pieces
+        .iter()
+        .map(|piece| (piece.end - piece.start).max(0.0) / piece.speed.max(0.01))
+        .sum()
+}
+
+/// Duration of the exported media once cuts and speed changes are applied.
+pub fn export_output_duration(project: &Project) -> f64 {
+    output_timeline_duration(&build_timeline_pieces(project))
+}
+
+fn build_enable_expression(ranges: &[(f64, f64)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| format!("between(t,{:.6},{:.6})", start, end))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
 fn timeline_is_edited(project: &Project, pieces: &[TimelinePiece]) -> bool {
     if pieces.len() != 1 {
         return true;
@@ -350,11 +470,26 @@ fn build_audio_timeline_filter(
 
     let mut filters = Vec::new();
     let mut labels = Vec::new();
-    for (idx, piece) in pieces.iter().enumerate() {
+    // Offset tracks are filter outputs, which can only feed one trim each.
+    let piece_inputs = if pieces.len() > 1 && !input_label.ends_with(":a]") {
+        let split_labels = (0..pieces.len())
+            .map(|idx| format!("[{}src{}]", prefix, idx))
+            .collect::<Vec<_>>();
+        filters.push(format!(
+            "{}asplit={}{}",
+            input_label,
+            pieces.len(),
+            split_labels.join("")
+        ));
+        split_labels
+    } else {
+        vec![input_label.to_string(); pieces.len()]
+    };
+    for (idx, (piece, piece_input)) in pieces.iter().zip(&piece_inputs).enumerate() {
         let label = format!("[{}{}]", prefix, idx);
         let mut filter = format!(
             "{}atrim=start={:.6}:end={:.6},asetpts=PTS-STARTPTS",
-            input_label, piece.start, piece.end
+            piece_input, piece.start, piece.end
         );
         if let Some(chain) = atempo_chain(piece.speed) {
             filter.push(',');
@@ -406,6 +541,27 @@ fn apply_audio_offset(input_label: &str, offset_ms: i64, prefix: &str) -> (Vec<S
     }
 }
 
+/// Offset, cut and level the system audio, whether it is its own track or inside the screen file.
+fn apply_system_audio(
+    filter_parts: &mut Vec<String>,
+    input: Option<&(String, i64)>,
+    project: &Project,
+    pieces: &[TimelinePiece],
+) -> Option<String> {
+    let (input_label, offset_ms) = input?;
+    let (offset_filters, offset_label) = apply_audio_offset(input_label, *offset_ms, "asystem");
+    filter_parts.extend(offset_filters);
+    let (audio_filters, audio_label) =
+        build_audio_timeline_filter(&offset_label, pieces, "ascreen");
+    filter_parts.extend(audio_filters);
+    Some(apply_audio_gain(
+        filter_parts,
+        audio_label,
+        project.edits.audio_mix.system_volume,
+        "ascreenvol",
+    ))
+}
+
 fn apply_audio_gain(
     filter_parts: &mut Vec<String>,
     input_label: String,
@@ -421,7 +577,71 @@ fn apply_audio_gain(
     output
 }
 
-fn append_zoom_piece_filter(filter: &mut String, zoom: &ActiveZoom, width: u32, height: u32) {
+fn easing_expression(easing: ZoomEasing, progress: &str) -> String {
+    match easing {
+        ZoomEasing::Linear => progress.to_string(),
+        ZoomEasing::EaseIn => format!("pow({progress},2)"),
+        ZoomEasing::EaseOut => format!("(1-pow(1-{progress},2))"),
+        ZoomEasing::EaseInOut => format!("(3*pow({progress},2)-2*pow({progress},3))"),
+    }
+}
+
+/// Eased zoom progress through a piece, from 0 (unzoomed) to 1 (fully zoomed).
+///
+/// The expression is written in terms of zoompan's `it`, the piece-local output time,
+/// and mapped back to source time so a zoom split across pieces ramps continuously.
+fn zoom_progress_expression(zoom: &ActiveZoom, piece: &TimelinePiece) -> String {
+    let source_time = format!("({:.6}+it*{:.6})", piece.start, piece.speed);
+    let ramp = |distance: String, duration: f64| {
+        if duration > 0.001 {
+            easing_expression(zoom.easing, &format!("clip({distance}/{duration:.6},0,1)"))
+        } else {
+            "1".to_string()
+        }
+    };
+    let ramp_in = ramp(
+        format!("({source_time}-{:.6})", zoom.start_time),
+        zoom.ease_in,
+    );
+    let ramp_out = ramp(
+        format!("({:.6}-{source_time})", zoom.end_time),
+        zoom.ease_out,
+    );
+    format!("min({ramp_in},{ramp_out})")
+}
+
+fn build_zoompan_filter(
+    zoom: &ActiveZoom,
+    piece: &TimelinePiece,
+    offset_x: f64,
+    offset_y: f64,
+    width: u32,
+    height: u32,
+    frame_rate: u32,
+) -> String {
+    let progress = zoom_progress_expression(zoom, piece);
+    format!(
+        "fps={frame_rate},zoompan=z='1+{:.6}*{progress}':x='(iw-iw/zoom)/2+{offset_x:.3}*{progress}':y='(ih-ih/zoom)/2+{offset_y:.3}*{progress}':d=1:s={width}x{height}:fps={frame_rate}",
+        zoom.scale.max(1.01) - 1.0
+    )
+}
+
+fn append_zoom_piece_filter(
+    filter: &mut String,
+    zoom: &ActiveZoom,
+    piece: &TimelinePiece,
+    width: u32,
+    height: u32,
+    frame_rate: u32,
+) {
+    if zoom.is_eased() {
+        filter.push(',');
+        filter.push_str(&build_zoompan_filter(
+            zoom, piece, zoom.x, zoom.y, width, height, frame_rate,
+        ));
+        return;
+    }
+
     let scale = zoom.scale.max(1.01);
     let crop_width = format!("iw/{scale:.6}");
     let crop_height = format!("ih/{scale:.6}");
@@ -432,6 +652,264 @@ fn append_zoom_piece_filter(filter: &mut String, zoom: &ActiveZoom, width: u32,
     ));
 }
 
+fn probe_video_dimensions(path: &str) -> Option<(u32, u32)> {
+    let output = std::process::Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-show_entries")
+        .arg("stream=width,height")
+        .arg("-of")
+        .arg("csv=s=x:p=0")
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (width, height) = stdout.lines().next()?.trim().split_once('x')?;
+    let width = width.trim().parse::<u32>().ok()?;
+    let height = height.trim().parse::<u32>().ok()?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+    Some((width, height))
+}
+
+fn even_dimension(value: f64) -> u32 {
+    ((value.round() as u32) / 2 * 2).max(2)
+}
+
+/// Cut the camera track into the same timeline pieces as the screen.
+///
+/// The camera offset shifts every piece onto the camera's own clock; time the camera
+/// has not started yet is padded with its first frame, and time after it ended with its
+/// last, so every piece keeps its length and later pieces stay in sync with the screen.
+/// Zoomed pieces punch into the centre of the camera when `follow_zoom` is set, which
+/// needs the probed camera size so every piece scales to the same overlay dimensions.
+fn build_camera_timeline_filter(
+    input_label: &str,
+    pieces: &[TimelinePiece],
+    offset_ms: i64,
+    overlay: &crate::project::CameraOverlaySettings,
+    camera_dimensions: Option<(u32, u32)>,
+    camera_duration: Option<f64>,
+    frame_rate: u32,
+    prefix: &str,
+) -> (Vec<String>, String) {
+    let camera_scale = overlay.scale.max(0.1);
+    let scale_filter = match camera_dimensions {
+        Some((width, height)) => format!(
+            "scale={}:{}",
+            even_dimension(width as f64 * camera_scale),
+            even_dimension(height as f64 * camera_scale)
+        ),
+        None => format!("scale=iw*{camera_scale}:ih*{camera_scale}"),
+    };
+    let offset_seconds = offset_ms as f64 / 1000.0;
+
+    let mut filters = Vec::new();
+    let mut labels = Vec::new();
+    for (idx, piece) in pieces.iter().enumerate() {
+        let label = format!("[{}{}]", prefix, idx);
+        let camera_start = piece.start - offset_seconds;
+        let camera_end = piece.end - offset_seconds;
+        let piece_duration = piece.end - piece.start;
+
+        let hold_piece = format!(
+            "setpts=PTS-STARTPTS,tpad=stop_duration={:.6}:stop_mode=clone,trim=duration={:.6}",
+            piece_duration, piece_duration
+        );
+        let mut filter = match camera_duration {
+            _ if camera_end <= 0.0 => {
+                // The camera starts after this piece ends, so its first frame fills the piece.
+                format!("{}trim=end_frame=1,{}", input_label, hold_piece)
+            }
+            Some(camera_duration) if camera_start >= camera_duration => {
+                // The camera ended before this piece starts, so its last frame fills the piece.
+                let last_frame_start = (camera_duration - 1.0 / frame_rate.max(1) as f64).max(0.0);
+                format!(
+                    "{}trim=start={:.6},{}",
+                    input_label, last_frame_start, hold_piece
+                )
+            }
+            _ => {
+                let mut filter = format!(
+                    "{}trim=start={:.6}:end={:.6},setpts=PTS-STARTPTS",
+                    input_label,
+                    camera_start.max(0.0),
+                    camera_end
+                );
+                if camera_start < 0.0 {
+                    filter.push_str(&format!(
+                        ",tpad=start_duration={:.6}:start_mode=clone",
+                        -camera_start
+                    ));
+                }
+                // A camera that ends inside the piece would shorten it and pull later pieces early.
+                filter.push_str(&format!(
+                    ",tpad=stop_duration={:.6}:stop_mode=clone,trim=duration={:.6}",
+                    piece_duration, piece_duration
+                ));
+                filter
+            }
+        };
+        filter.push_str(&format!(",setpts=PTS/{:.6}", piece.speed));
+        if let (Some(zoom), true, Some((width, height))) =
+            (&piece.zoom, overlay.follow_zoom, camera_dimensions)
+        {
+            if zoom.is_eased() {
+                filter.push(',');
+                filter.push_str(&build_zoompan_filter(
+                    zoom, piece, 0.0, 0.0, width, height, frame_rate,
+                ));
+            } else {
+                let zoom_scale = zoom.scale.max(1.01);
+                filter.push_str(&format!(",crop=w=iw/{zoom_scale:.6}:h=ih/{zoom_scale:.6}"));
+            }
+        }
+        filter.push(',');
+        filter.push_str(&scale_filter);
+        filter.push_str(&label);
+        filters.push(filter);
+        labels.push(label);
+    }
+
+    if labels.len() == 1 {
+        return (filters, labels[0].clone());
+    }
+
+    let output_label = format!("[{}out]", prefix);
+    filters.push(format!(
+        "{}concat=n={}:v=1:a=0{}",
+        labels.join(""),
+        labels.len(),
+        output_label
+    ));
+
+    (filters, output_label)
+}
+
+/// Trim, re-time and zoom the screen track into timeline pieces and join them.
+fn build_video_timeline_filter(
+    input_label: &str,
+    pieces: &[TimelinePiece],
+    width: u32,
+    height: u32,
+    frame_rate: u32,
+) -> (Vec<String>, String) {
+    let mut filters = Vec::new();
+    let mut labels = Vec::new();
+    // Input streams can feed several trims; filter outputs have to be split first.
+    let piece_inputs = if pieces.len() > 1 && !input_label.ends_with(":v]") {
+        let split_labels = (0..pieces.len())
+            .map(|idx| format!("[vpiecesrc{}]", idx))
+            .collect::<Vec<_>>();
+        filters.push(format!(
+            "{}split={}{}",
+            input_label,
+            pieces.len(),
+            split_labels.join("")
+        ));
+        split_labels
+    } else {
+        vec![input_label.to_string(); pieces.len()]
+    };
+    for (idx, (piece, piece_input)) in pieces.iter().zip(&piece_inputs).enumerate() {
+        let label = format!("[vpiece{}]", idx);
+        let mut filter = format!(
+            "{}trim=start={:.6}:end={:.6},setpts=(PTS-STARTPTS)/{:.6}",
+            piece_input, piece.start, piece.end, piece.speed
+        );
+        if let Some(zoom) = &piece.zoom {
+            append_zoom_piece_filter(&mut filter, zoom, piece, width, height, frame_rate);
+        }
+        filter.push_str(&label);
+        filters.push(filter);
+        labels.push(label);
+    }
+
+    if labels.len() == 1 {
+        return (filters, labels[0].clone());
+    }
+
+    let output_label = "[vconcat]".to_string();
+    filters.push(format!(
+        "{}concat=n={}:v=1:a=0{}",
+        labels.join(""),
+        labels.len(),
+        output_label
+    ));
+
+    (filters, output_label)
+}
+
+fn apply_camera_overlay(
+    filter_parts: &mut Vec<String>,
+    current_video_label: String,
+    project: &Project,
+    camera_path: &str,
+    pieces: &[TimelinePiece],
+    timeline_edited: bool,
+    frame_rate: u32,
+) -> String {
+    let camera_offset = project.camera_offset_ms.unwrap_or(0);
+    let camera_input = "[1:v]";
+    let camera_label = if timeline_edited {
+        let (camera_filters, camera_label) = build_camera_timeline_filter(
+            camera_input,
+            pieces,
+            camera_offset,
+            &project.edits.camera_overlay,
+            probe_video_dimensions(camera_path),
+            crate::probe_video_duration(&PathBuf::from(camera_path)).ok(),
+            frame_rate,
+            "campiece",
+        );
+        filter_parts.extend(camera_filters);
+        camera_label
+    } else {
+        let camera_label = "[cam]".to_string();
+        let camera_scale = project.edits.camera_overlay.scale.max(0.1);
+        let camera_scale_filter = format!("scale=iw*{camera_scale}:ih*{camera_scale}");
+
+        if camera_offset > 0 {
+            filter_parts.push(format!(
+                "{}setpts=PTS+{:.6}/TB,{}{}",
+                camera_input,
+                camera_offset as f64 / 1000.0,
+                camera_scale_filter,
+                camera_label
+            ));
+        } else if camera_offset < 0 {
+            filter_parts.push(format!(
+                "{}trim=start={:.6},setpts=PTS-STARTPTS,{}{}",
+                camera_input,
+                camera_offset.unsigned_abs() as f64 / 1000.0,
+                camera_scale_filter,
+                camera_label
+            ));
+        } else {
+            filter_parts.push(format!(
+                "{}{}{}",
+                camera_input, camera_scale_filter, camera_label
+            ));
+        }
+        camera_label
+    };
+
+    let overlay_coordinates = build_camera_overlay_coordinates(project);
+    let output_label = "[vwithcam]".to_string();
+    filter_parts.push(format!(
+        "{}{}overlay={}{}",
+        current_video_label, camera_label, overlay_coordinates, output_label
+    ));
+    output_label
+}
+
 fn build_camera_overlay_coordinates(project: &Project) -> String {
     let margin = project.edits.camera_overlay.margin;
     match project.edits.camera_overlay.position {
@@ -461,6 +939,7 @@ fn apply_video_annotations(
     filter_parts: &mut Vec<String>,
     mut current_video_label: String,
     project: &Project,
+    pieces: &[TimelinePiece],
 ) -> String {
     for (index, annotation) in project.edits.annotations.iter().enumerate() {
         let start_time = annotation.start_time.max(0.0);
@@ -468,6 +947,11 @@ fn apply_video_annotations(
         if end_time - start_time <= 0.01 {
             continue;
         }
+        let output_ranges = map_source_range_to_output(pieces, start_time, end_time);
+        if output_ranges.is_empty() {
+            continue;
+        }
+        let enable = build_enable_expression(&output_ranges);
 
         let width = annotation.width.clamp(0.02, 1.0);
         let height = annotation.height.clamp(0.02, 1.0);
@@ -497,8 +981,8 @@ fn apply_video_annotations(
                     blur_source_label, width, height, x, y, blur_label
                 ));
                 filter_parts.push(format!(
-                    "{}{}overlay=x=iw*{:.6}:y=ih*{:.6}:enable='between(t,{:.6},{:.6})'{}",
-                    base_label, blur_label, x, y, start_time, end_time, next_label
+                    "{}{}overlay=x=iw*{:.6}:y=ih*{:.6}:enable='{}'{}",
+                    base_label, blur_label, x, y, enable, next_label
                 ));
                 current_video_label = next_label;
             }
@@ -507,15 +991,14 @@ fn apply_video_annotations(
                     if !text.is_empty() {
                         let escaped_text = escape_drawtext_text(text);
                         filter_parts.push(format!(
-                            "{}drawtext=text='{}':x=iw*{:.6}:y=ih*{:.6}:fontsize=30:fontcolor={}@{:.3}:box=1:boxcolor=black@0.35:enable='between(t,{:.6},{:.6})'{}",
+                            "{}drawtext=text='{}':x=iw*{:.6}:y=ih*{:.6}:fontsize=30:fontcolor={}@{:.3}:box=1:boxcolor=black@0.35:enable='{}'{}",
                             current_video_label,
                             escaped_text,
                             x,
                             y,
                             color,
                             opacity,
-                            start_time,
-                            end_time,
+                            enable,
                             next_label
                         ));
                         current_video_label = next_label;
@@ -527,7 +1010,7 @@ fn apply_video_annotations(
                 let shaft_label = format!("[vannotshaft{}]", index);
                 let shaft_thickness = thickness.max(2);
                 filter_parts.push(format!(
-                    "{}drawbox=x=iw*{:.6}:y=ih*({:.6}+{:.6}*0.5)-{}*0.5:w=iw*{:.6}:h={}:color={}@{:.3}:t=fill:enable='between(t,{:.6},{:.6})'{}",
+                    "{}drawbox=x=iw*{:.6}:y=ih*({:.6}+{:.6}*0.5)-{}*0.5:w=iw*{:.6}:h={}:color={}@{:.3}:t=fill:enable='{}'{}",
                     current_video_label,
                     x,
                     y,
@@ -537,26 +1020,24 @@ fn apply_video_annotations(
                     shaft_thickness,
                     color,
                     opacity,
-                    start_time,
-                    end_time,
+                    enable,
                     shaft_label
                 ));
                 filter_parts.push(format!(
-                    "{}drawtext=text='➜':x=iw*{:.6}:y=ih*{:.6}:fontsize=40:fontcolor={}@{:.3}:enable='between(t,{:.6},{:.6})'{}",
+                    "{}drawtext=text='➜':x=iw*{:.6}:y=ih*{:.6}:fontsize=40:fontcolor={}@{:.3}:enable='{}'{}",
                     shaft_label,
                     x + width * 0.78,
                     y + height * 0.2,
                     color,
                     opacity,
-                    start_time,
-                    end_time,
+                    enable,
                     next_label
                 ));
                 current_video_label = next_label;
             }
             AnnotationMode::Outline => {
                 filter_parts.push(format!(
-                    "{}drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color={}@{:.3}:t={}:enable='between(t,{:.6},{:.6})'{}",
+                    "{}drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color={}@{:.3}:t={}:enable='{}'{}",
                     current_video_label,
                     x,
                     y,
@@ -565,8 +1046,7 @@ fn apply_video_annotations(
                     color,
                     opacity,
                     thickness,
-                    start_time,
-                    end_time,
+                    enable,
                     next_label
                 ));
                 current_video_label = next_label;
@@ -578,14 +1058,13 @@ fn apply_video_annotations(
                 let text_label = format!("[vannottxt{}]", index);
                 let escaped_text = escape_drawtext_text(text);
                 filter_parts.push(format!(
-                    "{}drawtext=text='{}':x=iw*{:.6}+10:y=ih*{:.6}+10:fontsize=28:fontcolor=white@{:.3}:box=1:boxcolor=black@0.35:enable='between(t,{:.6},{:.6})'{}",
+                    "{}drawtext=text='{}':x=iw*{:.6}+10:y=ih*{:.6}+10:fontsize=28:fontcolor=white@{:.3}:box=1:boxcolor=black@0.35:enable='{}'{}",
                     current_video_label,
                     escaped_text,
                     x,
                     y,
                     opacity,
-                    start_time,
-                    end_time,
+                    enable,
                     text_label
                 ));
                 current_video_label = text_label;
@@ -596,7 +1075,7 @@ fn apply_video_annotations(
     current_video_label
 }
 
-fn build_annotation_drawbox_chain(project: &Project) -> String {
+fn build_annotation_drawbox_chain(project: &Project, pieces: &[TimelinePiece]) -> String {
     project
         .edits
         .annotations
@@ -607,6 +1086,11 @@ fn build_annotation_drawbox_chain(project: &Project) -> String {
             if end_time - start_time <= 0.01 {
                 return None;
             }
+            let output_ranges = map_source_range_to_output(pieces, start_time, end_time);
+            if output_ranges.is_empty() {
+                return None;
+            }
+            let enable = build_enable_expression(&output_ranges);
 
             let width = annotation.width.clamp(0.02, 1.0);
             let height = annotation.height.clamp(0.02, 1.0);
@@ -622,8 +1106,8 @@ fn build_annotation_drawbox_chain(project: &Project) -> String {
 
             let base_chain = match annotation.mode {
                 AnnotationMode::Blur => format!(
-                    ",drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color=black@0.35:t=fill:enable='between(t,{:.6},{:.6})'",
-                    x, y, width, height, start_time, end_time
+                    ",drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color=black@0.35:t=fill:enable='{}'",
+                    x, y, width, height, enable
                 ),
                 AnnotationMode::Text => annotation
                     .text
@@ -633,15 +1117,15 @@ fn build_annotation_drawbox_chain(project: &Project) -> String {
                     .map(|text| {
                         let escaped_text = escape_drawtext_text(text);
                         format!(
-                            ",drawtext=text='{}':x=iw*{:.6}:y=ih*{:.6}:fontsize=22:fontcolor={}@{:.3}:box=1:boxcolor=black@0.35:enable='between(t,{:.6},{:.6})'",
-                            escaped_text, x, y, color, opacity, start_time, end_time
+                            ",drawtext=text='{}':x=iw*{:.6}:y=ih*{:.6}:fontsize=22:fontcolor={}@{:.3}:box=1:boxcolor=black@0.35:enable='{}'",
+                            escaped_text, x, y, color, opacity, enable
                         )
                     })
                     .unwrap_or_default(),
                 AnnotationMode::Arrow => {
                     let shaft_thickness = thickness.max(2);
                     format!(
-                        ",drawbox=x=iw*{:.6}:y=ih*({:.6}+{:.6}*0.5)-{}*0.5:w=iw*{:.6}:h={}:color={}@{:.3}:t=fill:enable='between(t,{:.6},{:.6})',drawtext=text='➜':x=iw*{:.6}:y=ih*{:.6}:fontsize=30:fontcolor={}@{:.3}:enable='between(t,{:.6},{:.6})'",
+                        ",drawbox=x=iw*{:.6}:y=ih*({:.6}+{:.6}*0.5)-{}*0.5:w=iw*{:.6}:h={}:color={}@{:.3}:t=fill:enable='{}',drawtext=text='➜':x=iw*{:.6}:y=ih*{:.6}:fontsize=30:fontcolor={}@{:.3}:enable='{}'",
                         x,
                         y,
                         height,
@@ -650,19 +1134,17 @@ fn build_annotation_drawbox_chain(project: &Project) -> String {
                         shaft_thickness,
                         color,
                         opacity,
-                        start_time,
-                        end_time,
+                        enable,
                         x + width * 0.78,
                         y + height * 0.2,
                         color,
                         opacity,
-                        start_time,
-                        end_time
+                        enable
                     )
                 }
                 AnnotationMode::Outline => format!(
-                    ",drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color={}@{:.3}:t={}:enable='between(t,{:.6},{:.6})'",
-                    x, y, width, height, color, opacity, thickness, start_time, end_time
+                    ",drawbox=x=iw*{:.6}:y=ih*{:.6}:w=iw*{:.6}:h=ih*{:.6}:color={}@{:.3}:t={}:enable='{}'",
+                    x, y, width, height, color, opacity, thickness, enable
                 ),
             };
 
@@ -674,8 +1156,8 @@ fn build_annotation_drawbox_chain(project: &Project) -> String {
                     if !text.is_empty() {
                         let escaped_text = escape_drawtext_text(text);
                         chain.push_str(&format!(
-                            ",drawtext=text='{}':x=iw*{:.6}+8:y=ih*{:.6}+8:fontsize=20:fontcolor=white@{:.3}:box=1:boxcolor=black@0.35:enable='between(t,{:.6},{:.6})'",
-                            escaped_text, x, y, opacity, start_time, end_time
+                            ",drawtext=text='{}':x=iw*{:.6}+8:y=ih*{:.6}+8:fontsize=20:fontcolor=white@{:.3}:box=1:boxcolor=black@0.35:enable='{}'",
+                            escaped_text, x, y, opacity, enable
                         ));
                     }
                 }
@@ -705,25 +1187,109 @@ fn build_color_correction_filter(project: &Project) -> Option<String> {
     }
 }
 
-/// Build ffmpeg arguments for export
+/// A generated input an export reads besides the project media.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSideFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// ffmpeg arguments for an export and the generated inputs they refer to.
+#[derive(Debug, Clone, Default)]
+pub struct ExportCommand {
+    pub args: Vec<String>,
+    pub side_files: Vec<ExportSideFile>,
+}
+
+impl ExportCommand {
+    /// Write the generated inputs; call before starting ffmpeg.
+    pub async fn write_side_files(&self) -> Result<(), AppError> {
+        for side_file in &self.side_files {
+            tokio::fs::write(&side_file.path, &side_file.contents)
+                .await
+                .map_err(|error| {
+                    AppError::Io(format!(
+                        "Failed to write export input {}: {}",
+                        side_file.path.display(),
+                        error
+                    ))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Scratch directory for one export's generated inputs, removed when dropped.
+pub struct ExportWorkDir {
+    path: PathBuf,
+}
+
+impl ExportWorkDir {
+    pub async fn create() -> Result<Self, AppError> {
+        let path = std::env::temp_dir().join(format!("openrec-export-{}", Uuid::new_v4()));
+        tokio::fs::create_dir_all(&path).await.map_err(|error| {
+            AppError::Io(format!(
+                "Failed to create export directory {}: {}",
+                path.display(),
+                error
+            ))
+        })?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ExportWorkDir {
+    fn drop(&mut self) {
+        if let Err(error) = std::fs::remove_dir_all(&self.path) {
+            if error.kind() != std::io::ErrorKind::NotFound {
+                eprintln!(
+                    "Failed to remove export directory {}: {}",
+                    self.path.display(),
+                    error
+                );
+            }
+        }
+    }
+}
+
+/// Build ffmpeg arguments for export.
+///
+/// Nothing is written here; generated inputs are placed in `work_dir` and listed in the
+/// command's `side_files` for the runner to write.
 pub fn build_ffmpeg_args(
     project: &Project,
     options: &ExportOptions,
     output_path: &PathBuf,
-) -> Vec<String> {
-    let camera_path = project.camera_video_path.as_ref().cloned();
+    work_dir: &Path,
+) -> ExportCommand {
+    let primary_video_path = primary_video_path(project).cloned();
+    let camera_path = project
+        .screen_video_path
+        .as_ref()
+        .and(project.camera_video_path.as_ref())
+        .cloned();
     let microphone_path = project.microphone_audio_path.as_ref().cloned();
-    let screen_has_audio = has_audio_stream(&project.screen_video_path);
+    let system_audio_path = project.system_audio_path.as_ref().cloned();
+    let screen_has_audio =
+        system_audio_path.is_none() && primary_video_path.as_deref().is_some_and(has_audio_stream);
     let timeline_pieces = build_timeline_pieces(project);
     let timeline_edited = timeline_is_edited(project, &timeline_pieces);
     let color_correction_filter = build_color_correction_filter(project);
 
     let mut args = Vec::new();
+    let mut side_files = Vec::new();
 
+    let cursor_events = load_export_cursor_events(project);
     let enabled_segments = enabled_segments(project);
     let only_single_segment = enabled_segments.len() == 1;
-    let use_input_seeking = camera_path.is_none()
+    let use_input_seeking = cursor_events.is_none()
+        && camera_path.is_none()
         && microphone_path.is_none()
+        && system_audio_path.is_none()
         && only_single_segment
         && (enabled_segments[0].0 > 0.0 || enabled_segments[0].1 < project.duration)
         && !timeline_edited
@@ -737,9 +1303,22 @@ pub fn build_ffmpeg_args(
         args.push(format!("{}", seg.1));
     }
 
-    // Input file - screen recording
-    args.push("-i".to_string());
-    args.push(project.screen_video_path.clone());
+    // Input file - screen recording, or a black canvas for audio-only takes
+    if let Some(ref video_path) = primary_video_path {
+        args.push("-i".to_string());
+        args.push(video_path.clone());
+    } else {
+        args.push("-f".to_string());
+        args.push("lavfi".to_string());
+        args.push("-i".to_string());
+        args.push(format!(
+            "color=c=black:s={}x{}:r={}:d={}",
+            project.resolution.width,
+            project.resolution.height,
+            options.frame_rate,
+            project.duration
+        ));
+    }
 
     // Input file - camera recording (if exists)
     if let Some(ref cam_path) = camera_path {
@@ -753,108 +1332,91 @@ pub fn build_ffmpeg_args(
     } else {
         None
     };
+    let system_audio_input = if let Some(ref system_audio_path) = system_audio_path {
+        args.push("-i".to_string());
+        args.push(system_audio_path.clone());
+        let index = 1 + usize::from(camera_path.is_some()) + usize::from(mic_index.is_some());
+        Some((
+            format!("[{}:a]", index),
+            project.system_audio_offset_ms.unwrap_or(0),
+        ))
+    } else if screen_has_audio {
+        Some(("[0:a]".to_string(), 0))
+    } else {
+        None
+    };
+    // Chapters come from an ffmetadata input placed after the media inputs.
+    let chapter_input_index = if matches!(options.format, ExportFormat::Mp4 | ExportFormat::Mov) {
+        let chapters = output_chapters(project, &timeline_pieces);
+        (!chapters.is_empty()).then(|| {
+            let metadata = chapter_metadata_side_file(work_dir, &chapters);
+            args.push("-f".to_string());
+            args.push("ffmetadata".to_string());
+            args.push("-i".to_string());
+            args.push(metadata.path.to_string_lossy().to_string());
+            side_files.push(metadata);
+            1 + usize::from(camera_path.is_some())
+                + usize::from(mic_index.is_some())
+                + usize::from(system_audio_path.is_some())
+        })
+    } else {
+        None
+    };
 
     match options.format {
         ExportFormat::Mp4 | ExportFormat::Mov => {
             let mut filter_parts: Vec<String> = Vec::new();
             let mut current_video_label = "[0:v]".to_string();
 
-            if !use_input_seeking {
-                let mut video_piece_labels = Vec::new();
-                if timeline_edited {
-                    let output_width = project.resolution.width;
-                    let output_height = project.resolution.height;
-                    for (idx, piece) in timeline_pieces.iter().enumerate() {
-                        let label = format!("[vpiece{}]", idx);
-                        let mut piece_filter = format!(
-                            "[0:v]trim=start={:.6}:end={:.6},setpts=(PTS-STARTPTS)/{:.6}",
-                            piece.start, piece.end, piece.speed
-                        );
-                        if let Some(zoom) = &piece.zoom {
-                            append_zoom_piece_filter(
-                                &mut piece_filter,
-                                zoom,
-                                output_width,
-                                output_height,
-                            );
-                        }
-                        piece_filter.push_str(&label);
-                        filter_parts.push(piece_filter);
-                        video_piece_labels.push(label);
-                    }
-
-                    if video_piece_labels.len() == 1 {
-                        current_video_label = video_piece_labels[0].clone();
-                    } else {
-                        let concat_label = "[vconcat]".to_string();
-                        filter_parts.push(format!(
-                            "{}concat=n={}:v=1:a=0{}",
-                            video_piece_labels.join(""),
-                            video_piece_labels.len(),
-                            concat_label
-                        ));
-                        current_video_label = concat_label;
-                    }
-                }
+            if let Some(events) = cursor_events.as_ref() {
+                current_video_label = apply_cursor_effects(
+                    &mut filter_parts,
+                    &mut side_files,
+                    work_dir,
+                    &current_video_label,
+                    project,
+                    events,
+                    options.frame_rate,
+                );
             }
 
-            if camera_path.is_some() {
-                let camera_offset = project.camera_offset_ms.unwrap_or(0);
-                let camera_input = "[1:v]";
-                let camera_label = "[cam]";
-                let camera_scale = project.edits.camera_overlay.scale.max(0.1);
-                let camera_scale_filter = format!("scale=iw*{camera_scale}:ih*{camera_scale}");
-
-                if camera_offset > 0 {
-                    filter_parts.push(format!(
-                        "{}setpts=PTS+{:.6}/TB,{}{}",
-                        camera_input,
-                        camera_offset as f64 / 1000.0,
-                        camera_scale_filter,
-                        camera_label
-                    ));
-                } else if camera_offset < 0 {
-                    filter_parts.push(format!(
-                        "{}trim=start={:.6},setpts=PTS-STARTPTS,{}{}",
-                        camera_input,
-                        camera_offset.unsigned_abs() as f64 / 1000.0,
-                        camera_scale_filter,
-                        camera_label
-                    ));
-                } else {
-                    filter_parts.push(format!(
-                        "{}{}{}",
-                        camera_input, camera_scale_filter, camera_label
-                    ));
-                }
+            if !use_input_seeking && timeline_edited {
+                let (video_filters, video_label) = build_video_timeline_filter(
+                    &current_video_label,
+                    &timeline_pieces,
+                    project.resolution.width,
+                    project.resolution.height,
+                    options.frame_rate,
+                );
+                filter_parts.extend(video_filters);
+                current_video_label = video_label;
+            }
 
-                let overlay_coordinates = build_camera_overlay_coordinates(project);
-                filter_parts.push(format!(
-                    "{}{}overlay={}[vwithcam]",
-                    current_video_label, camera_label, overlay_coordinates
-                ));
-                current_video_label = "[vwithcam]".to_string();
+            if let Some(cam_path) = camera_path.as_ref() {
+                current_video_label = apply_camera_overlay(
+                    &mut filter_parts,
+                    current_video_label,
+                    project,
+                    cam_path,
+                    &timeline_pieces,
+                    timeline_edited,
+                    options.frame_rate,
+                );
             }
 
-            current_video_label =
-                apply_video_annotations(&mut filter_parts, current_video_label, project);
+            current_video_label = apply_video_annotations(
+                &mut filter_parts,
+                current_video_label,
+                project,
+                &timeline_pieces,
+            );
 
-            let screen_audio_label = if screen_has_audio {
-                let (audio_filters, audio_label) =
-                    build_audio_timeline_filter("[0:a]", &timeline_pieces, "ascreen");
-                filter_parts.extend(audio_filters);
-                Some(audio_label)
-            } else {
-                None
-            };
-            let screen_audio_label = screen_audio_label.map(|label| {
-                apply_audio_gain(
-                    &mut filter_parts,
-                    label,
-                    project.edits.audio_mix.system_volume,
-                    "ascreenvol",
-                )
-            });
+            let screen_audio_label = apply_system_audio(
+                &mut filter_parts,
+                system_audio_input.as_ref(),
+                project,
+                &timeline_pieces,
+            );
 
             let microphone_audio_label = if let Some(idx) = mic_index {
                 let input_label = format!("[{}:a]", idx);
@@ -1019,68 +1581,88 @@ pub fn build_ffmpeg_args(
                 args.push("-map".to_string());
                 args.push("0:a?".to_string());
             }
+
+            if let Some(index) = chapter_input_index {
+                args.push("-map_chapters".to_string());
+                args.push(index.to_string());
+            }
         }
         ExportFormat::Gif => {
-            let annotation_chain = build_annotation_drawbox_chain(project);
-            let correction_chain = color_correction_filter
-                .as_ref()
-                .map(|value| format!(",{value}"))
-                .unwrap_or_default();
-            if camera_path.is_some() {
-                let camera_scale = project.edits.camera_overlay.scale.max(0.1);
-                let overlay_coordinates = build_camera_overlay_coordinates(project);
-                args.push("-filter_complex".to_string());
-                args.push(format!(
-                    "[1:v]scale=iw*{camera_scale}:ih*{camera_scale}[cam];[0:v][cam]overlay={overlay_coordinates}{annotation_chain}{correction_chain},fps={},scale=-1:{}:flags=lanczos,split[s0][s1];[s0]palettegen=stats_mode=diff[p];[s1][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle",
+            let mut filter_parts: Vec<String> = Vec::new();
+            let mut current_video_label = "[0:v]".to_string();
+
+            if let Some(events) = cursor_events.as_ref() {
+                current_video_label = apply_cursor_effects(
+                    &mut filter_parts,
+                    &mut side_files,
+                    work_dir,
+                    &current_video_label,
+                    project,
+                    events,
                     options.frame_rate.min(30),
-                    options.resolution.height().min(720)
-                ));
-            } else {
-                let annotation_prefix = annotation_chain
-                    .strip_prefix(',')
-                    .unwrap_or(&annotation_chain);
-                let correction_prefix = correction_chain
-                    .strip_prefix(',')
-                    .unwrap_or(&correction_chain);
-                args.push("-vf".to_string());
-                args.push(format!(
-                    "{}fps={},scale=-1:{}:flags=lanczos,split[s0][s1];[s0]palettegen=stats_mode=diff[p];[s1][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle",
-                    if annotation_prefix.is_empty() && correction_prefix.is_empty() {
-                        String::new()
-                    } else {
-                        let mut prefix_parts: Vec<&str> = Vec::new();
-                        if !annotation_prefix.is_empty() {
-                            prefix_parts.push(annotation_prefix);
-                        }
-                        if !correction_prefix.is_empty() {
-                            prefix_parts.push(correction_prefix);
-                        }
-                        format!("{},", prefix_parts.join(","))
-                    },
+                );
+            }
+
+            if !use_input_seeking && timeline_edited {
+                let (video_filters, video_label) = build_video_timeline_filter(
+                    &current_video_label,
+                    &timeline_pieces,
+                    project.resolution.width,
+                    project.resolution.height,
                     options.frame_rate.min(30),
-                    options.resolution.height().min(720)
-                ));
+                );
+                filter_parts.extend(video_filters);
+                current_video_label = video_label;
             }
+
+            if let Some(cam_path) = camera_path.as_ref() {
+                current_video_label = apply_camera_overlay(
+                    &mut filter_parts,
+                    current_video_label,
+                    project,
+                    cam_path,
+                    &timeline_pieces,
+                    timeline_edited,
+                    options.frame_rate.min(30),
+                );
+            }
+
+            let annotation_chain = build_annotation_drawbox_chain(project, &timeline_pieces);
+            let mut gif_chain: Vec<String> = Vec::new();
+            if let Some(annotations) = annotation_chain.strip_prefix(',') {
+                gif_chain.push(annotations.to_string());
+            }
+            if let Some(correction) = color_correction_filter.as_ref() {
+                gif_chain.push(correction.clone());
+            }
+            gif_chain.push(format!("fps={}", options.frame_rate.min(30)));
+            gif_chain.push(format!(
+                "scale=-1:{}:flags=lanczos",
+                options.resolution.height().min(720)
+            ));
+            filter_parts.push(format!(
+                "{}{},split[s0][s1]",
+                current_video_label,
+                gif_chain.join(",")
+            ));
+            filter_parts.push("[s0]palettegen=stats_mode=diff[p]".to_string());
+            filter_parts.push(
+                "[s1][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle".to_string(),
+            );
+
+            args.push("-filter_complex".to_string());
+            args.push(filter_parts.join(";"));
+            args.push("-an".to_string());
         }
         ExportFormat::Wav | ExportFormat::Mp3 => {
             let mut filter_parts: Vec<String> = Vec::new();
 
-            let screen_audio_label = if screen_has_audio {
-                let (audio_filters, audio_label) =
-                    build_audio_timeline_filter("[0:a]", &timeline_pieces, "ascreen");
-                filter_parts.extend(audio_filters);
-                Some(audio_label)
-            } else {
-                None
-            };
-            let screen_audio_label = screen_audio_label.map(|label| {
-                apply_audio_gain(
-                    &mut filter_parts,
-                    label,
-                    project.edits.audio_mix.system_volume,
-                    "ascreenvol",
-                )
-            });
+            let screen_audio_label = apply_system_audio(
+                &mut filter_parts,
+                system_audio_input.as_ref(),
+                project,
+                &timeline_pieces,
+            );
 
             let microphone_audio_label = if let Some(idx) = mic_index {
                 let input_label = format!("[{}:a]", idx);
@@ -1187,7 +1769,7 @@ pub fn build_ffmpeg_args(
     args.push("-y".to_string());
     args.push(output_path.to_string_lossy().to_string());
 
-    args
+    ExportCommand { args, side_files }
 }
 
 /// Get default export output path
@@ -1248,7 +1830,7 @@ pub fn get_export_output_path(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::project::SpeedEffect;
+    use crate::project::{Annotation, Chapter, Segment, SpeedEffect, ZoomEffect};
     use std::path::{Path, PathBuf};
     use uuid::Uuid;
 
@@ -1278,6 +1860,7 @@ mod tests {
             frame_rate: 30,
             compression: CompressionPreset::Social,
             resolution: ResolutionPreset::P1080,
+            chapter_list: false,
         }
     }
 
@@ -1293,7 +1876,7 @@ mod tests {
     ) -> Project {
         Project::new(
             id.to_string(),
-            screen_path,
+            Some(screen_path),
             camera_path,
             microphone_path,
             12.0,
@@ -1427,4 +2010,552 @@ mod tests {
         assert!(combined.contains("atempo=1.50000"));
         assert!(output_label.starts_with("[speed"));
     }
+
+    fn cut_and_speed_project() -> Project {
+        let mut project = build_test_project(
+            "remap-project",
+            PathBuf::from("/tmp/remap-screen.mp4"),
+            None,
+            None,
+        );
+        project.duration = 30.0;
+        project.edits.segments = vec![
+            Segment {
+                id: "cut".to_string(),
+                start_time: 0.0,
+                end_time: 10.0,
+                enabled: false,
+            },
+            Segment {
+                id: "kept".to_string(),
+                start_time: 10.0,
+                end_time: 30.0,
+                enabled: true,
+            },
+        ];
+        project.edits.speed.push(SpeedEffect {
+            id: "fast".to_string(),
+            start_time: 20.0,
+            end_time: 30.0,
+            speed: 2.0,
+        });
+        project
+    }
+
+    #[test]
+    fn maps_source_times_through_cuts_and_speed_changes() {
+        let project = cut_and_speed_project();
+        let pieces = build_timeline_pieces(&project);
+
+        assert_eq!(timeline_piece_output_offsets(&pieces), vec![0.0, 10.0]);
+        assert_eq!(output_timeline_duration(&pieces), 15.0);
+        assert_eq!(
+            map_source_range_to_output(&pieces, 15.0, 26.0),
+            vec![(5.0, 13.0)]
+        );
+        assert!(map_source_range_to_output(&pieces, 2.0, 8.0).is_empty());
+    }
+
+    #[test]
+    fn remaps_source_ranges_across_disabled_and_repeated_segments() {
+        let mut project = cut_and_speed_project();
+        project.edits.speed.clear();
+        let segment = |id: &str, start_time: f64, end_time: f64, enabled: bool| Segment {
+            id: id.to_string(),
+            start_time,
+            end_time,
+            enabled,
+        };
+        project.edits.segments = vec![
+            segment("a", 0.0, 10.0, true),
+            segment("b", 10.0, 20.0, false),
+            segment("c", 20.0, 30.0, true),
+        ];
+        let pieces = build_timeline_pieces(&project);
+        assert_eq!(
+            map_source_range_to_output(&pieces, 5.0, 25.0),
+            vec![(5.0, 15.0)]
+        );
+
+        project.edits.segments = vec![segment("a", 0.0, 10.0, true), segment("b", 5.0, 15.0, true)];
+        let pieces = build_timeline_pieces(&project);
+        let ranges = map_source_range_to_output(&pieces, 6.0, 8.0);
+        assert_eq!(ranges, vec![(6.0, 8.0), (11.0, 13.0)]);
+        assert_eq!(
+            build_enable_expression(&ranges),
+            "between(t,6.000000,8.000000)+between(t,11.000000,13.000000)"
+        );
+    }
+
+    #[test]
+    fn annotations_use_output_timeline_and_skip_cut_ranges() {
+        let mut project = cut_and_speed_project();
+        let annotation = |id: &str, start_time: f64, end_time: f64| Annotation {
+            id: id.to_string(),
+            start_time,
+            end_time,
+            x: 0.1,
+            y: 0.1,
+            width: 0.2,
+            height: 0.2,
+            color: "#ff0000".to_string(),
+            opacity: 1.0,
+            thickness: 4,
+            text: None,
+            mode: AnnotationMode::Outline,
+        };
+        project.edits.annotations =
+            vec![annotation("cut", 2.0, 8.0), annotation("kept", 22.0, 24.0)];
+        let pieces = build_timeline_pieces(&project);
+
+        let mut filter_parts = Vec::new();
+        let label =
+            apply_video_annotations(&mut filter_parts, "[v]".to_string(), &project, &pieces);
+
+        assert_eq!(filter_parts.len(), 1);
+        assert_eq!(label, "[vannot1]");
+        assert!(filter_parts[0].contains("enable='between(t,11.000000,12.000000)'"));
+    }
+
+    #[test]
+    fn camera_pieces_follow_cuts_speed_offset_and_zoom() {
+        let mut project = cut_and_speed_project();
+        project.edits.camera_overlay.follow_zoom = true;
+        project.edits.zoom.push(ZoomEffect {
+            id: "zoom".to_string(),
+            start_time: 20.0,
+            end_time: 25.0,
+            scale: 2.0,
+            x: 0.0,
+            y: 0.0,
+            ease_in: 0.0,
+            ease_out: 0.0,
+            easing: ZoomEasing::EaseInOut,
+        });
+        let pieces = build_timeline_pieces(&project);
+
+        let (filters, label) = build_camera_timeline_filter(
+            "[1:v]",
+            &pieces,
+            12_000,
+            &project.edits.camera_overlay,
+            Some((1280, 720)),
+            None,
+            30,
+            "campiece",
+        );
+
+        assert_eq!(label, "[campieceout]");
+        assert_eq!(filters.len(), 4);
+        assert_eq!(
+            filters[0],
+            "[1:v]trim=start=0.000000:end=8.000000,setpts=PTS-STARTPTS,tpad=start_duration=2.000000:start_mode=clone,tpad=stop_duration=10.000000:stop_mode=clone,trim=duration=10.000000,setpts=PTS/1.000000,scale=320:180[campiece0]"
+        );
+        assert_eq!(
+            filters[1],
+            "[1:v]trim=start=8.000000:end=13.000000,setpts=PTS-STARTPTS,tpad=stop_duration=5.000000:stop_mode=clone,trim=duration=5.000000,setpts=PTS/2.000000,crop=w=iw/2.000000:h=ih/2.000000,scale=320:180[campiece1]"
+        );
+        assert!(!filters[2].contains("crop="));
+        assert_eq!(
+            filters[3],
+            "[campiece0][campiece1][campiece2]concat=n=3:v=1:a=0[campieceout]"
+        );
+    }
+
+    #[test]
+    fn camera_pieces_before_the_camera_starts_hold_its_first_frame() {
+        let project = cut_and_speed_project();
+        let pieces = build_timeline_pieces(&project);
+
+        let (filters, _) = build_camera_timeline_filter(
+            "[1:v]",
+            &pieces,
+            25_000,
+            &project.edits.camera_overlay,
+            Some((1280, 720)),
+            None,
+            30,
+            "campiece",
+        );
+
+        assert_eq!(
+            filters[0],
+            "[1:v]trim=end_frame=1,setpts=PTS-STARTPTS,tpad=stop_duration=10.000000:stop_mode=clone,trim=duration=10.000000,setpts=PTS/1.000000,scale=320:180[campiece0]"
+        );
+        assert_eq!(
+            filters[1],
+            "[1:v]trim=start=0.000000:end=5.000000,setpts=PTS-STARTPTS,tpad=start_duration=5.000000:start_mode=clone,tpad=stop_duration=10.000000:stop_mode=clone,trim=duration=10.000000,setpts=PTS/2.000000,scale=320:180[campiece1]"
+        );
+    }
+
+    #[test]
+    fn camera_pieces_after_the_camera_ends_keep_their_length() {
+        let mut project = cut_and_speed_project();
+        project.edits.zoom.push(ZoomEffect {
+            id: "zoom".to_string(),
+            start_time: 25.0,
+            end_time: 30.0,
+            scale: 1.5,
+            x: 0.0,
+            y: 0.0,
+            ease_in: 0.0,
+            ease_out: 0.0,
+            easing: ZoomEasing::EaseInOut,
+        });
+        let pieces = build_timeline_pieces(&project);
+
+        let (filters, _) = build_camera_timeline_filter(
+            "[1:v]",
+            &pieces,
+            0,
+            &project.edits.camera_overlay,
+            Some((1280, 720)),
+            Some(22.0),
+            30,
+            "campiece",
+        );
+
+        assert_eq!(
+            filters[1],
+            "[1:v]trim=start=20.000000:end=25.000000,setpts=PTS-STARTPTS,tpad=stop_duration=5.000000:stop_mode=clone,trim=duration=5.000000,setpts=PTS/2.000000,scale=320:180[campiece1]"
+        );
+        assert_eq!(
+            filters[2],
+            "[1:v]trim=start=21.966667,setpts=PTS-STARTPTS,tpad=stop_duration=5.000000:stop_mode=clone,trim=duration=5.000000,setpts=PTS/2.000000,scale=320:180[campiece2]"
+        );
+    }
+
+    #[test]
+    fn camera_pieces_stay_unzoomed_when_follow_zoom_is_off() {
+        let mut project = cut_and_speed_project();
+        project.edits.camera_overlay.follow_zoom = false;
+        project.edits.zoom.push(ZoomEffect {
+            id: "zoom".to_string(),
+            start_time: 10.0,
+            end_time: 30.0,
+            scale: 1.5,
+            x: 0.0,
+            y: 0.0,
+            ease_in: 0.0,
+            ease_out: 0.0,
+            easing: ZoomEasing::EaseInOut,
+        });
+        let pieces = build_timeline_pieces(&project);
+
+        let (filters, _) = build_camera_timeline_filter(
+            "[1:v]",
+            &pieces,
+            0,
+            &project.edits.camera_overlay,
+            Some((1280, 720)),
+            None,
+            30,
+            "campiece",
+        );
+
+        assert!(filters.iter().all(|filter| !filter.contains("crop=")));
+    }
+
+    #[test]
+    fn gif_export_uses_timeline_pieces_before_palette_generation() {
+        let project = cut_and_speed_project();
+        let options = ExportOptions {
+            format: ExportFormat::Gif,
+            ..default_options()
+        };
+
+        let args = build_ffmpeg_args(
+            &project,
+            &options,
+            &PathBuf::from("/tmp/out.gif"),
+            Path::new("/tmp"),
+        )
+        .args;
+        let filter_index = args
+            .iter()
+            .position(|arg| arg == "-filter_complex")
+            .expect("gif export should use a filter graph");
+        let filter = &args[filter_index + 1];
+
+        assert!(filter.starts_with(
+            "[0:v]trim=start=10.000000:end=20.000000,setpts=(PTS-STARTPTS)/1.000000[vpiece0]"
+        ));
+        assert!(filter.contains("setpts=(PTS-STARTPTS)/2.000000[vpiece1]"));
+        assert!(filter.contains("[vconcat]fps=30,scale=-1:720:flags=lanczos,split[s0][s1]"));
+        assert!(
+            filter.ends_with("[s1][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle")
+        );
+    }
+
+    #[test]
+    fn cursor_effects_are_drawn_before_the_timeline_is_cut() {
+        let test_dir = TestDirectory::new();
+        let cursor_log = test_dir.path.join("cursor.jsonl");
+        std::fs::write(
+            &cursor_log,
+            concat!(
+                "{\"timeMs\":11000,\"kind\":\"move\",\"x\":0.25,\"y\":0.5}\n",
+                "{\"timeMs\":12000,\"kind\":\"down\",\"x\":0.25,\"y\":0.5,\"button\":1}\n",
+            ),
+        )
+        .expect("failed to write cursor log fixture");
+        let mut project = cut_and_speed_project();
+        project.cursor_log_path = Some(cursor_log.to_string_lossy().to_string());
+        project.edits.cursor_effects.highlight = crate::project::CursorHighlightStyle::Halo;
+        project.edits.cursor_effects.click_ripples = true;
+
+        let work_dir = test_dir.path.join("work");
+        let command = build_ffmpeg_args(
+            &project,
+            &default_options(),
+            &PathBuf::from("/tmp/out.mp4"),
+            &work_dir,
+        );
+        let args = &command.args;
+        let filter_index = args
+            .iter()
+            .position(|arg| arg == "-filter_complex")
+            .expect("cursor effects should use a filter graph");
+        let filter = &args[filter_index + 1];
+
+        assert!(
+            !work_dir.exists(),
+            "building arguments should not write anything"
+        );
+        let [script] = command.side_files.as_slice() else {
+            panic!("expected one cursor track script");
+        };
+        assert_eq!(script.path, work_dir.join("cursor-effects.cmd"));
+
+        assert!(filter.starts_with("[0:v]sendcmd=f='"));
+        assert!(filter.contains("[vcursorcmd][vcursorsprite]overlay@cursor=x=-w:y=-h"));
+        assert!(filter.contains(
+            "[vcursor][cursorripple0]overlay=x=W*0.250000-w/2:y=H*0.500000-h/2:eof_action=pass[vripple0]"
+        ));
+        assert!(filter.contains("[vripple0]split=2[vpiecesrc0][vpiecesrc1]"));
+        assert!(filter.contains("[vpiecesrc1]trim=start=20.000000"));
+        assert_eq!(
+            script.contents,
+            "11.000 overlay@cursor x W*0.250000-w/2, overlay@cursor y H*0.500000-h/2;\n"
+        );
+    }
+
+    #[test]
+    fn separate_system_audio_track_is_offset_before_the_timeline_is_cut() {
+        let mut project = cut_and_speed_project();
+        project.system_audio_path = Some("/tmp/system_audio.flac".to_string());
+        project.system_audio_offset_ms = Some(250);
+
+        let args = build_ffmpeg_args(
+            &project,
+            &default_options(),
+            &PathBuf::from("/tmp/out.mp4"),
+            Path::new("/tmp"),
+        )
+        .args;
+        let filter_index = args
+            .iter()
+            .position(|arg| arg == "-filter_complex")
+            .expect("a cut timeline should use a filter graph");
+        let filter = &args[filter_index + 1];
+
+        assert_eq!(
+            args[2..4],
+            ["-i".to_string(), "/tmp/system_audio.flac".to_string()]
+        );
+        assert!(filter.contains("[1:a]adelay=250|250[asystemoffset]"));
+        assert!(filter.contains("[asystemoffset]asplit=2[ascreensrc0][ascreensrc1]"));
+        assert!(filter.contains("[ascreensrc1]atrim=start=20.000000"));
+    }
+
+    #[test]
+    fn chapters_are_read_from_an_ffmetadata_input_after_the_media() {
+        let mut project = cut_and_speed_project();
+        project.microphone_audio_path = Some("/tmp/microphone.wav".to_string());
+        project.edits.chapters.push(Chapter {
+            id: "demo".to_string(),
+            title: "Demo".to_string(),
+            start_time: 12.0,
+        });
+
+        let work_dir = std::env::temp_dir().join(format!("openrec-export-{}", Uuid::new_v4()));
+        let command = build_ffmpeg_args(
+            &project,
+            &default_options(),
+            &PathBuf::from("/tmp/out.mp4"),
+            &work_dir,
+        );
+        let args = &command.args;
+        let metadata_index = args
+            .iter()
+            .position(|arg| arg == "ffmetadata")
+            .expect("chapters should add an ffmetadata input");
+        let [metadata] = command.side_files.as_slice() else {
+            panic!("expected one chapter metadata file");
+        };
+        let map_chapters_index = args
+            .iter()
+            .position(|arg| arg == "-map_chapters")
+            .expect("chapters should be mapped into the output");
+
+        assert!(
+            !work_dir.exists(),
+            "building arguments should not write anything"
+        );
+        assert_eq!(args[metadata_index - 1], "-f");
+        assert_eq!(
+            PathBuf::from(&args[metadata_index + 2]),
+            work_dir.join("chapters.ffmetadata")
+        );
+        assert_eq!(metadata.path, work_dir.join("chapters.ffmetadata"));
+        assert_eq!(args[map_chapters_index + 1], "2");
+        assert!(metadata
+            .contents
+            .contains("START=2000\nEND=15000\ntitle=Demo\n"));
+
+        let gif_args = build_ffmpeg_args(
+            &project,
+            &ExportOptions {
+                format: ExportFormat::Gif,
+                ..default_options()
+            },
+            &PathBuf::from("/tmp/out.gif"),
+            Path::new("/tmp"),
+        )
+        .args;
+        assert!(!gif_args.iter().any(|arg| arg == "-map_chapters"));
+    }
+
+    #[test]
+    fn screenless_takes_export_the_camera_or_a_black_canvas_as_the_main_video() {
+        let mut project = cut_and_speed_project();
+        project.screen_video_path = None;
+        project.camera_video_path = Some("/tmp/camera.webm".to_string());
+
+        let args = build_ffmpeg_args(
+            &project,
+            &default_options(),
+            &PathBuf::from("/tmp/out.mp4"),
+            Path::new("/tmp"),
+        )
+        .args;
+        assert_eq!(
+            args[0..2],
+            ["-i".to_string(), "/tmp/camera.webm".to_string()]
+        );
+        assert_eq!(args.iter().filter(|arg| *arg == "-i").count(), 1);
+
+        project.camera_video_path = None;
+        project.microphone_audio_path = Some("/tmp/microphone.wav".to_string());
+        let args = build_ffmpeg_args(
+            &project,
+            &default_options(),
+            &PathBuf::from("/tmp/out.mp4"),
+            Path::new("/tmp"),
+        )
+        .args;
+        assert_eq!(
+            args[0..6],
+            [
+                "-f".to_string(),
+                "lavfi".to_string(),
+                "-i".to_string(),
+                "color=c=black:s=1920x1080:r=30:d=30".to_string(),
+                "-i".to_string(),
+                "/tmp/microphone.wav".to_string(),
+            ]
+        );
+        assert!(args.iter().any(|arg| arg.contains("[1:a]")));
+    }
+
+    #[test]
+    fn camera_only_wav_export_finds_audio_in_the_camera_file() {
+        let mut project = build_test_project(
+            "camera-only-audio",
+            PathBuf::from("/tmp/screen.mp4"),
+            Some(PathBuf::from("/tmp/camera.webm")),
+            None,
+        );
+        project.screen_video_path = None;
+
+        assert!(has_audio_source(&project, |path| path == "/tmp/camera.webm"));
+        assert!(!has_audio_source(&project, |_| false));
+    }
+
+    #[tokio::test]
+    async fn validate_export_inputs_rejects_gif_longer_than_limit() {
+        let test_dir = TestDirectory::new();
+        let screen_path = test_dir.path.join("screen.mp4");
+        write_empty_file(&screen_path);
+        let mut project = build_test_project("long-gif", screen_path, None, None);
+        project.duration = 90.0;
+        project.edits.segments = vec![Segment {
+            id: "all".to_string(),
+            start_time: 0.0,
+            end_time: 90.0,
+            enabled: true,
+        }];
+        let options = ExportOptions {
+            format: ExportFormat::Gif,
+            ..default_options()
+        };
+
+        let result = validate_export_inputs(&project, &options).await;
+        assert!(matches!(
+            result,
+            Err(AppError::Message(message)) if message.contains("GIF export is limited")
+        ));
+
+        project.edits.speed.push(SpeedEffect {
+            id: "fast".to_string(),
+            start_time: 0.0,
+            end_time: 90.0,
+            speed: 2.0,
+        });
+        assert!(validate_export_inputs(&project, &options).await.is_ok());
+    }
+
+    #[test]
+    fn eased_zoom_interpolates_scale_and_offset_with_zoompan() {
+        let mut project = cut_and_speed_project();
+        project.edits.zoom.push(ZoomEffect {
+            id: "zoom".to_string(),
+            start_time: 12.0,
+            end_time: 18.0,
+            scale: 2.0,
+            x: 40.0,
+            y: -20.0,
+            ease_in: 0.5,
+            ease_out: 1.0,
+            easing: ZoomEasing::Linear,
+        });
+        let pieces = build_timeline_pieces(&project);
+        let zoomed = &pieces[1];
+        assert_eq!((zoomed.start, zoomed.end), (12.0, 18.0));
+
+        let (filters, _) = build_video_timeline_filter("[0:v]", &pieces, 1920, 1080, 30);
+        let progress = "min(clip(((12.000000+it*1.000000)-12.000000)/0.500000,0,1),clip((18.000000-(12.000000+it*1.000000))/1.000000,0,1))";
+
+        assert_eq!(
+            filters[1],
+            format!(
+                "[0:v]trim=start=12.000000:end=18.000000,setpts=(PTS-STARTPTS)/1.000000,fps=30,zoompan=z='1+1.000000*{progress}':x='(iw-iw/zoom)/2+40.000*{progress}':y='(ih-ih/zoom)/2+-20.000*{progress}':d=1:s=1920x1080:fps=30[vpiece1]"
+            )
+        );
+        assert!(!filters[0].contains("zoompan"));
+    }
+
+    #[test]
+    fn easing_curves_shape_zoom_progress() {
+        assert_eq!(easing_expression(ZoomEasing::Linear, "p"), "p");
+        assert_eq!(easing_expression(ZoomEasing::EaseIn, "p"), "pow(p,2)");
+        assert_eq!(
+            easing_expression(ZoomEasing::EaseOut, "p"),
+            "(1-pow(1-p,2))"
+        );
+        assert_eq!(
+            easing_expression(ZoomEasing::EaseInOut, "p"),
+            "(3*pow(p,2)-2*pow(p,3))"
+        );
+    }
 }
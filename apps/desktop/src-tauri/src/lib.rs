@@ -1,10 +1,19 @@
+mod cli;
+mod control_api;
 mod error;
 mod export;
 mod project;
 mod recording;
+mod shortcuts;
+pub use cli::{is_cli_invocation, run_cli};
+use control_api::{
+    control_api_settings_path, control_event_channel, generate_control_api_token,
+    load_control_api_settings, save_control_api_settings, ControlApiSettings, ControlApiState,
+    ControlApiStatus, ControlEvent, ControlRoute, ControlServer, CONTROL_API_EVENTS,
+};
 use error::AppError;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::future::Future;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
@@ -12,35 +21,47 @@ use std::sync::{Arc, Mutex};
 use tauri::{
     menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, RunEvent, WebviewUrl, WebviewWindowBuilder,
+    AppHandle, Emitter, Listener, Manager, RunEvent, WebviewUrl, WebviewWindowBuilder,
 };
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
 
-use export::{build_ffmpeg_args, get_export_output_path, validate_export_inputs, ExportOptions};
-use project::Project;
+use export::{
+    build_ffmpeg_args, export_output_duration, get_export_output_path, validate_export_inputs,
+    write_youtube_chapter_list, ExportOptions, ExportWorkDir,
+};
+use project::{Marker, NormalizedSegment, Project, ZoomEffect};
 use recording::{
-    check_screen_recording_permission, get_recording_snapshot as do_get_recording_snapshot,
+    add_recording_marker as do_add_recording_marker, check_screen_recording_permission,
+    get_recording_snapshot as do_get_recording_snapshot,
     get_recording_state as do_get_recording_state, pause_recording as do_pause_recording,
     request_screen_recording_permission, resume_recording as do_resume_recording,
-    set_media_offsets as do_set_media_offsets, start_recording as do_start_recording,
-    stop_recording as do_stop_recording, CaptureSource, RecorderState, RecordingOptions,
+    save_replay as do_save_replay, set_media_offsets as do_set_media_offsets,
+    start_recording as do_start_recording, stop_recording as do_stop_recording,
+    stop_replay_buffer as do_stop_replay_buffer, CaptureSource, DiskSpacePolicy, DiskSpaceReport,
+    DiskSpaceWatchdog, LowDiskSpaceAction, RecorderState, RecordingOptions,
     RecordingSessionSnapshot, RecordingSourceStatus, RecordingState as RecorderRecordingState,
-    SharedRecorderState, SourceType, StartRecordingResult, StopRecordingResult,
+    RecoverableRecording, SegmentNormalizationPlan, SegmentOffsets, SharedRecorderState,
+    SourceType, StartRecordingResult, StopRecordingResult,
+};
+use shortcuts::{
+    load_shortcut_bindings, save_shortcut_bindings, shortcut_bindings_path, GlobalShortcutState,
+    ShortcutAction, ShortcutBindingStatus, ShortcutBindings,
 };
 use uuid::Uuid;
 
 type SharedExportJobs = Arc<Mutex<HashMap<String, u32>>>;
 type SharedPendingFinalizations = Arc<Mutex<HashMap<String, StopRecordingResult>>>;
-const START_STOP_SHORTCUT: &str = "CmdOrCtrl+Shift+2";
-const PAUSE_RESUME_SHORTCUT: &str = "CmdOrCtrl+Shift+P";
-const MIN_RECORDING_FREE_SPACE_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+type SharedRecoverableRecordings = Arc<Mutex<Vec<RecoverableRecording>>>;
+type SharedGlobalShortcuts = Arc<Mutex<GlobalShortcutState>>;
+type SharedControlApi = Arc<Mutex<ControlApiState>>;
 const TRAY_MENU_OPEN_RECORDER: &str = "tray.open-recorder";
 const TRAY_MENU_OPEN_PROJECTS: &str = "tray.open-projects";
 const TRAY_MENU_QUICK_RECORD: &str = "tray.quick-record";
 const TRAY_MENU_START_STOP: &str = "tray.start-stop";
 const TRAY_MENU_PAUSE_RESUME: &str = "tray.pause-resume";
+const TRAY_MENU_ADD_MARKER: &str = "tray.add-marker";
 const TRAY_MENU_QUIT: &str = "tray.quit";
 const TRAY_MENU_RECENT_PREFIX: &str = "tray.recent.";
 const APP_MENU_NEW_WINDOW: &str = "app.new-window";
@@ -48,6 +69,10 @@ const APP_MENU_CHECK_UPDATES: &str = "app.check-updates";
 const APP_MENU_UNSIGNED_INSTALL_GUIDE: &str = "app.unsigned-install-guide";
 const STOP_RECORDING_FINALIZATION_TIMEOUT_SECS: u64 = 120;
 const FFMPEG_COMMAND_TIMEOUT_SECS: u64 = 120;
+const RECORDING_HEALTH_EVENT_INTERVAL_SECS: u64 = 2;
+const RECORDING_SOURCE_SUPERVISOR_INTERVAL_SECS: u64 = 2;
+/// Project canvas for audio-only takes, which have no video to size it from.
+const AUDIO_ONLY_CANVAS_SIZE: (u32, u32) = (1280, 720);
 const OPENREC_RELEASES_URL: &str = "https://github.com/TommyBez/open-rec/releases";
 const OPENREC_UNSIGNED_INSTALL_GUIDE_URL: &str =
     "https://github.com/TommyBez/open-rec/blob/main/docs/UNSIGNED_MAC_INSTALL.md";
@@ -400,12 +425,17 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
     manager: &M,
     recordings_dir: &PathBuf,
 ) -> Result<Menu<R>, AppError> {
+    // Show the global shortcut bound to each item.
+    let bindings = manager
+        .try_state::<SharedGlobalShortcuts>()
+        .and_then(|state| state.lock().ok().map(|guard| guard.bindings.clone()))
+        .unwrap_or_default();
     let open_recorder_item = MenuItem::with_id(
         manager,
         TRAY_MENU_OPEN_RECORDER,
         "Open Recorder",
         true,
-        None::<&str>,
+        bindings.accelerator(ShortcutAction::OpenRecorder),
     )
     .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
     let open_projects_item = MenuItem::with_id(
@@ -413,7 +443,7 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
         TRAY_MENU_OPEN_PROJECTS,
         "Open Projects",
         true,
-        None::<&str>,
+        bindings.accelerator(ShortcutAction::OpenProjects),
     )
     .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
     let quick_record_item = MenuItem::with_id(
@@ -421,7 +451,7 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
         TRAY_MENU_QUICK_RECORD,
         "Quick Record Last Settings",
         true,
-        None::<&str>,
+        bindings.accelerator(ShortcutAction::QuickRecord),
     )
     .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
     let start_stop_item = MenuItem::with_id(
@@ -429,7 +459,7 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
         TRAY_MENU_START_STOP,
         "Start/Stop Recording",
         true,
-        Some(START_STOP_SHORTCUT),
+        bindings.accelerator(ShortcutAction::StartStop),
     )
     .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
     let pause_resume_item = MenuItem::with_id(
@@ -437,7 +467,15 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
         TRAY_MENU_PAUSE_RESUME,
         "Pause/Resume Recording",
         true,
-        Some(PAUSE_RESUME_SHORTCUT),
+        bindings.accelerator(ShortcutAction::PauseResume),
+    )
+    .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
+    let add_marker_item = MenuItem::with_id(
+        manager,
+        TRAY_MENU_ADD_MARKER,
+        "Add Marker",
+        true,
+        bindings.accelerator(ShortcutAction::AddMarker),
     )
     .map_err(|error| AppError::Message(format!("Failed to build tray menu: {}", error)))?;
     let quit_item = MenuItem::with_id(manager, TRAY_MENU_QUIT, "Quit OpenRec", true, None::<&str>)
@@ -458,6 +496,7 @@ fn build_tray_menu<R: tauri::Runtime, M: Manager<R>>(
             &separator_top,
             &start_stop_item,
             &pause_resume_item,
+            &add_marker_item,
             &separator_bottom,
             &quit_item,
         ],
@@ -493,7 +532,10 @@ fn refresh_tray_menu(app: &AppHandle, recordings_dir: &PathBuf) {
     }
 }
 
-fn recording_disk_space_status(recordings_dir: &PathBuf) -> Result<DiskSpaceStatus, AppError> {
+fn recording_disk_space_status(
+    recordings_dir: &PathBuf,
+    minimum_required_bytes: u64,
+) -> Result<DiskSpaceStatus, AppError> {
     let free_bytes = fs2::available_space(recordings_dir).map_err(|error| {
         AppError::Io(format!(
             "Failed to check disk space for recordings directory: {}",
@@ -503,17 +545,21 @@ fn recording_disk_space_status(recordings_dir: &PathBuf) -> Result<DiskSpaceStat
 
     Ok(DiskSpaceStatus {
         free_bytes,
-        minimum_required_bytes: MIN_RECORDING_FREE_SPACE_BYTES,
-        sufficient: free_bytes >= MIN_RECORDING_FREE_SPACE_BYTES,
+        minimum_required_bytes,
+        sufficient: free_bytes >= minimum_required_bytes,
     })
 }
 
-fn ensure_recording_disk_headroom(recordings_dir: &PathBuf) -> Result<(), AppError> {
-    let status = recording_disk_space_status(recordings_dir)?;
+fn ensure_recording_disk_headroom(
+    recordings_dir: &PathBuf,
+    minimum_required_bytes: u64,
+) -> Result<(), AppError> {
+    let status = recording_disk_space_status(recordings_dir, minimum_required_bytes)?;
 
     if !status.sufficient {
         return Err(AppError::Message(format!(
-            "Insufficient disk space. At least 5 GB free is required (currently {:.2} GB).",
+            "Insufficient disk space. At least {:.2} GB free is required (currently {:.2} GB).",
+            status.minimum_required_bytes as f64 / (1024.0 * 1024.0 * 1024.0),
             status.free_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
         )));
     }
@@ -679,7 +725,20 @@ fn cleanup_active_exports(export_jobs: &SharedExportJobs) -> Result<(), AppError
     Ok(())
 }
 
-async fn run_ffmpeg_command(app: &AppHandle, args: &[String]) -> Result<(), AppError> {
+/// How finalization launches ffmpeg.
+#[derive(Clone)]
+enum FfmpegLauncher {
+    /// Through the shell plugin, preferring the bundled sidecar.
+    Shell(AppHandle),
+    /// As a plain child process, for the command-line interface.
+    Process(PathBuf),
+}
+
+async fn run_ffmpeg_command(ffmpeg: &FfmpegLauncher, args: &[String]) -> Result<(), AppError> {
+    let app = match ffmpeg {
+        FfmpegLauncher::Shell(app) => app,
+        FfmpegLauncher::Process(binary) => return run_ffmpeg_process(binary, args).await,
+    };
     let shell = app.shell();
     let ffmpeg_command = match shell.sidecar("ffmpeg") {
         Ok(command) => command,
@@ -737,6 +796,42 @@ async fn run_ffmpeg_command(app: &AppHandle, args: &[String]) -> Result<(), AppE
     ))
 }
 
+async fn run_ffmpeg_process(binary: &Path, args: &[String]) -> Result<(), AppError> {
+    let mut child = tokio::process::Command::new(binary)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| AppError::Message(format!("Failed to spawn ffmpeg: {}", e)))?;
+    let timeout_duration = std::time::Duration::from_secs(FFMPEG_COMMAND_TIMEOUT_SECS);
+    let status = match tokio::time::timeout(timeout_duration, child.wait()).await {
+        Ok(status) => {
+            status.map_err(|e| AppError::Message(format!("Failed to wait for ffmpeg: {}", e)))?
+        }
+        Err(_) => {
+            let pid = child.id().unwrap_or_default();
+            return Err(handle_ffmpeg_timeout(
+                pid,
+                FFMPEG_COMMAND_TIMEOUT_SECS,
+                terminate_process_by_pid,
+            ));
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+    let exit_code = match status.code() {
+        Some(code) => code.to_string(),
+        None => "unknown (terminated by signal)".to_string(),
+    };
+    Err(AppError::Message(format!(
+        "ffmpeg failed with exit code: {}",
+        exit_code
+    )))
+}
+
 fn handle_ffmpeg_timeout<F>(pid: u32, timeout_secs: u64, terminate_process: F) -> AppError
 where
     F: FnOnce(u32) -> Result<(), AppError>,
@@ -757,93 +852,416 @@ fn escape_concat_path(path: &PathBuf) -> String {
     path.to_string_lossy().replace('\'', "'\\''")
 }
 
+/// Probe the screen segments; a source re-resolved on resume can change size, rate or codec.
+fn plan_screen_segment_normalization(
+    segment_paths: &[PathBuf],
+) -> Option<SegmentNormalizationPlan> {
+    if segment_paths.len() <= 1 {
+        return None;
+    }
+    let mut segments = Vec::with_capacity(segment_paths.len());
+    for path in segment_paths {
+        match recording::probe_segment_video_params(path) {
+            Ok(params) => segments.push(params),
+            Err(error) => {
+                eprintln!(
+                    "Failed to probe screen segment {}, joining segments as-is: {}",
+                    path.display(),
+                    error
+                );
+                return None;
+            }
+        }
+    }
+    recording::plan_segment_normalization(&segments)
+}
+
+/// Join the screen segments; also returns the segments that had to be normalized onto a common
+/// canvas.
 async fn concatenate_screen_segments(
-    app: &AppHandle,
+    ffmpeg: &FfmpegLauncher,
     stop_result: &StopRecordingResult,
-) -> Result<(), AppError> {
+) -> Result<(Option<MergedTrack>, Vec<NormalizedSegment>), AppError> {
+    let Some(screen_video_path) = stop_result.screen_video_path.as_ref() else {
+        return Ok((None, Vec::new()));
+    };
+    let normalization = plan_screen_segment_normalization(&stop_result.screen_segment_paths);
+    let merged = concatenate_segments(
+        ffmpeg,
+        screen_video_path,
+        &stop_result.screen_segment_paths,
+        "screen",
+        normalization.as_ref(),
+        &[],
+    )
+    .await?;
+    Ok((
+        merged,
+        normalization
+            .map(|plan| plan.normalized_segments)
+            .unwrap_or_default(),
+    ))
+}
+
+/// Probe each screen segment's length before joining them deletes the segments.
+///
+/// Returns nothing when a segment cannot be read, leaving companion segments uncut.
+fn probe_screen_segment_durations(stop_result: &StopRecordingResult) -> Vec<f64> {
     if stop_result.screen_segment_paths.len() <= 1 {
-        return Ok(());
+        return Vec::new();
+    }
+    stop_result
+        .screen_segment_paths
+        .iter()
+        .map(probe_video_duration)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|error| {
+            eprintln!("Failed to probe screen segments, joining tracks uncut: {error}");
+            Vec::new()
+        })
+}
+
+async fn concatenate_microphone_segments(
+    ffmpeg: &FfmpegLauncher,
+    stop_result: &StopRecordingResult,
+    screen_durations: &[f64],
+) -> Result<Option<MergedTrack>, AppError> {
+    let Some(microphone_audio_path) = stop_result.microphone_audio_path.as_ref() else {
+        return Ok(None);
+    };
+    concatenate_segments(
+        ffmpeg,
+        microphone_audio_path,
+        &stop_result.microphone_segment_paths,
+        "microphone",
+        None,
+        &recording::companion_segment_outpoints(
+            screen_durations,
+            &stop_result.segment_offsets.microphone_ms,
+        ),
+    )
+    .await
+}
+
+async fn concatenate_system_audio_segments(
+    ffmpeg: &FfmpegLauncher,
+    stop_result: &StopRecordingResult,
+    screen_durations: &[f64],
+) -> Result<Option<MergedTrack>, AppError> {
+    let Some(system_audio_path) = stop_result.system_audio_path.as_ref() else {
+        return Ok(None);
+    };
+    concatenate_segments(
+        ffmpeg,
+        system_audio_path,
+        &stop_result.system_audio_segment_paths,
+        "system_audio",
+        None,
+        &recording::companion_segment_outpoints(
+            screen_durations,
+            &stop_result.segment_offsets.system_audio_ms,
+        ),
+    )
+    .await
+}
+
+async fn concatenate_camera_segments(
+    ffmpeg: &FfmpegLauncher,
+    stop_result: &StopRecordingResult,
+    screen_durations: &[f64],
+) -> Result<Option<MergedTrack>, AppError> {
+    let Some(camera_video_path) = stop_result.camera_video_path.as_ref() else {
+        return Ok(None);
+    };
+    concatenate_segments(
+        ffmpeg,
+        camera_video_path,
+        &stop_result.camera_segment_paths,
+        "camera",
+        None,
+        &recording::companion_segment_outpoints(
+            screen_durations,
+            &stop_result.segment_offsets.camera_ms,
+        ),
+    )
+    .await
+}
+
+/// A track joined into a temporary file that has not replaced its first segment yet.
+#[derive(Debug)]
+struct MergedTrack {
+    merged_path: PathBuf,
+    output_path: PathBuf,
+    track: &'static str,
+}
+
+/// Join paused-and-resumed segments into a temporary file next to `output_path`, which is also
+/// the first segment. The segments stay untouched until `commit_merged_tracks`.
+///
+/// Segments are stream-copied unless a normalization plan asks for them to be re-encoded.
+/// A stream-copied segment with an outpoint is cut there.
+async fn concatenate_segments(
+    ffmpeg: &FfmpegLauncher,
+    output_path: &Path,
+    segment_paths: &[PathBuf],
+    track: &'static str,
+    normalization: Option<&SegmentNormalizationPlan>,
+    outpoints: &[Option<f64>],
+) -> Result<Option<MergedTrack>, AppError> {
+    if segment_paths.len() <= 1 {
+        return Ok(None);
     }
 
-    let project_dir = stop_result
-        .screen_video_path
+    let project_dir = output_path
         .parent()
-        .ok_or_else(|| AppError::Message("Invalid screen video path".to_string()))?;
-    let concat_list_path = project_dir.join("segments_concat.txt");
-    let merged_path = project_dir.join("screen_merged.mp4");
+        .ok_or_else(|| AppError::Message(format!("Invalid {} video path", track)))?;
+    let concat_list_path = project_dir.join(format!("{}_segments_concat.txt", track));
+    let extension = output_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("mp4");
+    let merged_path = project_dir.join(format!("{}_merged.{}", track, extension));
 
-    let concat_manifest = stop_result
-        .screen_segment_paths
-        .iter()
-        .map(|path| format!("file '{}'", escape_concat_path(path)))
-        .collect::<Vec<_>>()
-        .join("\n");
-    tokio::fs::write(&concat_list_path, concat_manifest)
-        .await
-        .map_err(|e| AppError::Message(format!("Failed to write concat manifest: {}", e)))?;
+    let args = if let Some(plan) = normalization {
+        recording::normalized_concat_args(segment_paths, plan, &merged_path)
+    } else {
+        let concat_manifest = segment_paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let entry = format!("file '{}'", escape_concat_path(path));
+                match outpoints.get(index).copied().flatten() {
+                    Some(outpoint) => format!("{entry}\noutpoint {outpoint:.3}"),
+                    None => entry,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        tokio::fs::write(&concat_list_path, concat_manifest)
+            .await
+            .map_err(|e| AppError::Message(format!("Failed to write concat manifest: {}", e)))?;
+
+        let mut args = vec![
+            "-f".to_string(),
+            "concat".to_string(),
+            "-safe".to_string(),
+            "0".to_string(),
+            "-i".to_string(),
+            concat_list_path.to_string_lossy().to_string(),
+            "-c".to_string(),
+            "copy".to_string(),
+        ];
+        if extension == "mp4" {
+            args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+        }
+        args.extend(["-y".to_string(), merged_path.to_string_lossy().to_string()]);
+        args
+    };
 
-    let args = vec![
-        "-f".to_string(),
-        "concat".to_string(),
-        "-safe".to_string(),
-        "0".to_string(),
-        "-i".to_string(),
-        concat_list_path.to_string_lossy().to_string(),
-        "-c".to_string(),
-        "copy".to_string(),
-        "-y".to_string(),
-        merged_path.to_string_lossy().to_string(),
-    ];
+    let merge_result = run_ffmpeg_command(ffmpeg, &args).await;
+
+    if normalization.is_none() {
+        if let Err(error) = tokio::fs::remove_file(&concat_list_path).await {
+            eprintln!(
+                "Failed to remove concat manifest {}: {}",
+                concat_list_path.display(),
+                error
+            );
+        }
+    }
+
+    if let Err(error) = merge_result {
+        remove_merged_file(&merged_path).await;
+        return Err(error);
+    }
+    Ok(Some(MergedTrack {
+        merged_path,
+        output_path: output_path.to_path_buf(),
+        track,
+    }))
+}
+
+async fn remove_merged_file(merged_path: &Path) {
+    if tokio::fs::metadata(merged_path).await.is_ok() {
+        if let Err(error) = tokio::fs::remove_file(merged_path).await {
+            eprintln!(
+                "Failed to remove incomplete merged recording {}: {}",
+                merged_path.display(),
+                error
+            );
+        }
+    }
+}
 
+/// Join every multi-segment track, keeping all segments until each track has merged.
+async fn merge_track_segments(
+    ffmpeg: &FfmpegLauncher,
+    stop_result: &StopRecordingResult,
+    screen_durations: &[f64],
+) -> Result<(Vec<MergedTrack>, Vec<NormalizedSegment>), AppError> {
+    let mut merged_tracks = Vec::new();
     let merge_result = async {
-        run_ffmpeg_command(app, &args).await?;
+        let (screen, normalized_screen_segments) =
+            concatenate_screen_segments(ffmpeg, stop_result).await?;
+        merged_tracks.extend(screen);
+        merged_tracks
+            .extend(concatenate_camera_segments(ffmpeg, stop_result, screen_durations).await?);
+        merged_tracks
+            .extend(concatenate_microphone_segments(ffmpeg, stop_result, screen_durations).await?);
+        merged_tracks.extend(
+            concatenate_system_audio_segments(ffmpeg, stop_result, screen_durations).await?,
+        );
+        Ok::<_, AppError>(normalized_screen_segments)
+    }
+    .await;
+    match merge_result {
+        Ok(normalized_screen_segments) => Ok((merged_tracks, normalized_screen_segments)),
+        Err(error) => {
+            for merged in &merged_tracks {
+                remove_merged_file(&merged.merged_path).await;
+            }
+            Err(error)
+        }
+    }
+}
 
-        if tokio::fs::metadata(&stop_result.screen_video_path)
-            .await
-            .is_ok()
-        {
-            tokio::fs::remove_file(&stop_result.screen_video_path)
+/// Replace each track's first segment with its merged file and point `stop_result` at it, so a
+/// retry does not join a merged track again. Returns the segments that are no longer needed.
+async fn commit_merged_tracks(
+    stop_result: &mut StopRecordingResult,
+    merged_tracks: Vec<MergedTrack>,
+) -> Result<Vec<PathBuf>, AppError> {
+    let mut replaced_segments = Vec::new();
+    for merged in merged_tracks {
+        if tokio::fs::metadata(&merged.output_path).await.is_ok() {
+            tokio::fs::remove_file(&merged.output_path)
                 .await
                 .map_err(|e| {
-                    AppError::Message(format!("Failed to remove old screen recording: {}", e))
+                    AppError::Message(format!(
+                        "Failed to remove old {} recording: {}",
+                        merged.track, e
+                    ))
                 })?;
         }
-        tokio::fs::rename(&merged_path, &stop_result.screen_video_path)
+        tokio::fs::rename(&merged.merged_path, &merged.output_path)
             .await
-            .map_err(|e| AppError::Message(format!("Failed to finalize merged recording: {}", e)))
+            .map_err(|e| {
+                AppError::Message(format!("Failed to finalize merged recording: {}", e))
+            })?;
+
+        let segment_paths = [
+            (
+                &stop_result.screen_video_path,
+                &mut stop_result.screen_segment_paths,
+            ),
+            (
+                &stop_result.camera_video_path,
+                &mut stop_result.camera_segment_paths,
+            ),
+            (
+                &stop_result.microphone_audio_path,
+                &mut stop_result.microphone_segment_paths,
+            ),
+            (
+                &stop_result.system_audio_path,
+                &mut stop_result.system_audio_segment_paths,
+            ),
+        ]
+        .into_iter()
+        .find(|(track_path, _)| track_path.as_ref() == Some(&merged.output_path))
+        .map(|(_, segment_paths)| segment_paths);
+        if let Some(segment_paths) = segment_paths {
+            let segments = std::mem::replace(segment_paths, vec![merged.output_path.clone()]);
+            replaced_segments.extend(
+                segments
+                    .into_iter()
+                    .filter(|path| *path != merged.output_path),
+            );
+        }
     }
-    .await;
+    Ok(replaced_segments)
+}
 
-    if let Err(error) = tokio::fs::remove_file(&concat_list_path).await {
-        eprintln!(
-            "Failed to remove concat manifest {}: {}",
-            concat_list_path.display(),
-            error
-        );
+/// Rewrite a fragmented MP4 capture as a regular faststart MP4 in place.
+///
+/// Multi-segment tracks are skipped because concatenation already rewrote them.
+async fn remux_fragmented_capture(
+    ffmpeg: &FfmpegLauncher,
+    video_path: &Path,
+    segment_paths: &[PathBuf],
+    track: &str,
+) -> Result<(), AppError> {
+    let is_mp4 = video_path
+        .extension()
+        .is_some_and(|extension| extension == "mp4");
+    if !is_mp4 || segment_paths.len() > 1 || tokio::fs::metadata(video_path).await.is_err() {
+        return Ok(());
     }
+    let project_dir = video_path
+        .parent()
+        .ok_or_else(|| AppError::Message(format!("Invalid {} video path", track)))?;
+    let remuxed_path = project_dir.join(format!("{}_remuxed.mp4", track));
+    let args = vec![
+        "-i".to_string(),
+        video_path.to_string_lossy().to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        "-movflags".to_string(),
+        "+faststart".to_string(),
+        "-y".to_string(),
+        remuxed_path.to_string_lossy().to_string(),
+    ];
 
-    if merge_result.is_err() {
-        if tokio::fs::metadata(&merged_path).await.is_ok() {
-            if let Err(error) = tokio::fs::remove_file(&merged_path).await {
-                eprintln!(
-                    "Failed to remove incomplete merged recording {}: {}",
-                    merged_path.display(),
-                    error
-                );
-            }
+    let remux_result = async {
+        run_ffmpeg_command(ffmpeg, &args).await?;
+        tokio::fs::rename(&remuxed_path, video_path)
+            .await
+            .map_err(|e| AppError::Message(format!("Failed to finalize remuxed recording: {}", e)))
+    }
+    .await;
+    if remux_result.is_err() && tokio::fs::metadata(&remuxed_path).await.is_ok() {
+        if let Err(error) = tokio::fs::remove_file(&remuxed_path).await {
+            eprintln!(
+                "Failed to remove incomplete remuxed recording {}: {}",
+                remuxed_path.display(),
+                error
+            );
         }
-        return merge_result;
     }
+    remux_result
+}
 
-    for path in &stop_result.screen_segment_paths {
-        if path != &stop_result.screen_video_path {
-            if let Err(error) = tokio::fs::remove_file(path).await {
-                eprintln!("Failed to remove segment {}: {}", path.display(), error);
-            }
+/// Remux the Linux screen and native camera captures; the fragmented files stay usable on failure.
+async fn remux_fragmented_captures(ffmpeg: &FfmpegLauncher, stop_result: &StopRecordingResult) {
+    if !stop_result.fragmented_capture {
+        return;
+    }
+    let mut tracks = Vec::new();
+    if let Some(screen_video_path) = stop_result.screen_video_path.as_ref() {
+        tracks.push((
+            screen_video_path.as_path(),
+            &stop_result.screen_segment_paths,
+            "screen",
+        ));
+    }
+    if let Some(camera_video_path) = stop_result.camera_video_path.as_ref() {
+        if !stop_result.camera_segment_paths.is_empty() {
+            tracks.push((
+                camera_video_path.as_path(),
+                &stop_result.camera_segment_paths,
+                "camera",
+            ));
+        }
+    }
+    for (video_path, segment_paths, track) in tracks {
+        if let Err(error) = remux_fragmented_capture(ffmpeg, video_path, segment_paths, track).await
+        {
+            eprintln!(
+                "Failed to remux {} recording, keeping the fragmented file: {}",
+                track, error
+            );
         }
     }
-
-    merge_result
 }
 
 fn probe_video_dimensions(screen_video_path: &PathBuf) -> Result<(u32, u32), AppError> {
@@ -956,10 +1374,189 @@ fn request_permission() -> bool {
 #[tauri::command]
 fn check_recording_disk_space(
     state: tauri::State<'_, SharedRecorderState>,
+    disk_space: Option<DiskSpacePolicy>,
 ) -> Result<DiskSpaceStatus, AppError> {
     let recordings_dir = recordings_dir_from_managed_state(&state)?;
+    let disk_space = disk_space.unwrap_or_default();
+
+    recording_disk_space_status(&recordings_dir, disk_space.min_free_bytes)
+}
+
+/// Forward a watchdog report to the UI and pause or stop the take once the floor is reached.
+fn handle_disk_space_report(app: &AppHandle, report: DiskSpaceReport, replay_buffer: bool) {
+    emit_with_log(app, "recording-disk-space", &report);
+    let Some(action) = report.action else {
+        return;
+    };
+    let app = app.clone();
+    let project_id = report.project_id;
+    tauri::async_runtime::spawn(async move {
+        // The user may have stopped or paused the take since the watchdog sampled it.
+        match do_get_recording_state(&app.state::<SharedRecorderState>(), &project_id) {
+            Ok(Some(RecorderRecordingState::Recording)) => {}
+            Ok(Some(RecorderRecordingState::Paused))
+                if replay_buffer || action == LowDiskSpaceAction::Stop => {}
+            Ok(_) => return,
+            Err(error) => {
+                eprintln!("Failed to read recording state for low disk space: {error}");
+                return;
+            }
+        }
+        // A replay buffer cannot pause, and stopping it only drops footage nobody saved.
+        let result = match (replay_buffer, action) {
+            // Stopping is the async command the widget and control API await; it goes on to
+            // finalize the take through the shell plugin's ffmpeg.
+            (false, LowDiskSpaceAction::Stop) => {
+                stop_screen_recording(app.clone(), app.state(), app.state(), project_id).await
+            }
+            // Pausing and stopping a replay buffer are synchronous and wait on the capture
+            // processes, so keep them off the runtime.
+            _ => tauri::async_runtime::spawn_blocking(move || {
+                if replay_buffer {
+                    stop_replay_buffer(app.clone(), app.state(), project_id)
+                } else {
+                    pause_recording(app.clone(), app.state(), project_id)
+                }
+            })
+            .await
+            .map_err(|error| AppError::Message(format!("Low disk space task failed: {}", error)))
+            .and_then(|result| result),
+        };
+        log_if_err(result, "Failed to handle low disk space");
+    });
+}
+
+/// Emit `recording-health` for a session until it ends, so the widget can warn mid-take.
+fn spawn_recording_health_events(app: AppHandle, project_id: String) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            RECORDING_HEALTH_EVENT_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            let state = app.state::<SharedRecorderState>();
+            let snapshot = match do_get_recording_snapshot(&state, &project_id) {
+                Ok(Some(snapshot)) => snapshot,
+                Ok(None) => break,
+                Err(error) => {
+                    eprintln!(
+                        "Failed to read recording health for {}: {}",
+                        project_id, error
+                    );
+                    break;
+                }
+            };
+            if let Some(health) = snapshot.health {
+                emit_with_log(
+                    &app,
+                    "recording-health",
+                    serde_json::json!({
+                        "projectId": &project_id,
+                        "health": health
+                    }),
+                );
+            }
+        }
+    });
+}
+
+/// Keep a take running when its source disappears or ffmpeg dies, by moving it onto a new
+/// segment on the fallback source. A capture that keeps exiting is retried with backoff, then
+/// the take is stopped.
+fn spawn_recording_source_supervisor(app: AppHandle, project_id: String) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            RECORDING_SOURCE_SUPERVISOR_INTERVAL_SECS,
+        ));
+        let mut restart_backoff = recording::CaptureRestartBackoff::default();
+        loop {
+            interval.tick().await;
+            let state = app.state::<SharedRecorderState>();
+            let interruption = match recording::detect_capture_interruption(&state, &project_id) {
+                Ok(Some(interruption)) => interruption,
+                Ok(None) => match do_get_recording_state(&state, &project_id) {
+                    Ok(Some(_)) => continue,
+                    _ => break,
+                },
+                Err(error) => {
+                    eprintln!(
+                        "Failed to check the recording source for {}: {}",
+                        project_id, error
+                    );
+                    continue;
+                }
+            };
+
+            if interruption == recording::CaptureInterruption::CaptureExited {
+                let Some(delay) = restart_backoff.next_delay(std::time::Instant::now()) else {
+                    eprintln!(
+                        "Capture for {} keeps exiting; stopping the recording",
+                        project_id
+                    );
+                    log_if_err(
+                        stop_screen_recording(app.clone(), state, app.state(), project_id.clone())
+                            .await,
+                        "Failed to stop a recording whose capture keeps exiting",
+                    );
+                    break;
+                };
+                tokio::time::sleep(delay).await;
+            }
 
-    recording_disk_space_status(&recordings_dir)
+            let switch_app = app.clone();
+            let switch_project_id = project_id.clone();
+            let switched = tauri::async_runtime::spawn_blocking(move || {
+                recording::switch_capture_segment(
+                    &switch_app.state::<SharedRecorderState>(),
+                    &switch_project_id,
+                )
+            })
+            .await
+            .map_err(|error| AppError::Message(format!("Source switch task failed: {}", error)))
+            .and_then(|result| result);
+            match switched {
+                Ok(Some(fallback_update)) => {
+                    emit_with_log(
+                        &app,
+                        "recording-source-fallback",
+                        serde_json::json!({
+                            "projectId": &project_id,
+                            "sourceType": fallback_update.source_type,
+                            "sourceId": fallback_update.fallback_source.source_id,
+                            "sourceOrdinal": fallback_update.fallback_source.source_ordinal,
+                            "reason": interruption
+                        }),
+                    );
+                }
+                Ok(None) => {
+                    eprintln!(
+                        "Restarted capture for {} on the same source after {:?}",
+                        project_id, interruption
+                    );
+                }
+                Err(error) => {
+                    eprintln!(
+                        "Failed to move recording {} onto a new segment: {}",
+                        project_id, error
+                    );
+                    // The old segment is closed; leave the take paused so it can still be saved.
+                    if matches!(
+                        do_get_recording_state(&app.state::<SharedRecorderState>(), &project_id),
+                        Ok(Some(RecorderRecordingState::Paused))
+                    ) {
+                        emit_with_log(
+                            &app,
+                            "recording-state-changed",
+                            serde_json::json!({
+                                "state": "paused",
+                                "projectId": &project_id
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+    });
 }
 
 /// List available capture sources (displays or windows)
@@ -970,7 +1567,7 @@ fn list_capture_sources(source_type: SourceType) -> Result<Vec<CaptureSource>, A
 
 /// Start screen recording
 #[tauri::command]
-fn start_screen_recording(
+async fn start_screen_recording(
     app: AppHandle,
     state: tauri::State<'_, SharedRecorderState>,
     options: RecordingOptions,
@@ -982,10 +1579,38 @@ fn start_screen_recording(
     }
 
     let recordings_dir = recordings_dir_from_managed_state(&state)?;
-    ensure_recording_disk_headroom(&recordings_dir)?;
+    let disk_space = options.disk_space;
+    disk_space.validate()?;
+    ensure_recording_disk_headroom(&recordings_dir, disk_space.min_free_bytes)?;
 
     let source_type_for_event = options.source_type;
-    let result = do_start_recording(&state, options)?;
+    let replay_buffer = options.replay_buffer_seconds.is_some();
+    let auto_source_fallback = options.auto_source_fallback;
+    // Off the async runtime: on Wayland this waits for the user in the portal dialog.
+    let recorder_state = state.inner().clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        do_start_recording(&recorder_state, options, Uuid::new_v4().to_string())
+    })
+    .await
+    .map_err(|error| AppError::Message(format!("Recording start task failed: {}", error)))
+    .and_then(|result| result)?;
+    let watchdog = DiskSpaceWatchdog::start(
+        result.project_id.clone(),
+        recordings_dir.join(&result.project_id),
+        disk_space,
+        {
+            let app = app.clone();
+            move |report| handle_disk_space_report(&app, report, replay_buffer)
+        },
+    );
+    log_if_err(
+        recording::attach_disk_space_watchdog(&state, &result.project_id, watchdog),
+        "Failed to watch disk space for the recording",
+    );
+    spawn_recording_health_events(app.clone(), result.project_id.clone());
+    if auto_source_fallback {
+        spawn_recording_source_supervisor(app.clone(), result.project_id.clone());
+    }
 
     emit_with_log(&app, "recording-started", &result);
     emit_with_log(
@@ -1020,6 +1645,11 @@ fn store_pending_finalization(
         .lock()
         .map_err(|error| AppError::Lock(format!("Lock error: {}", error)))?;
     guard.insert(stop_result.project_id.clone(), stop_result.clone());
+    // The in-memory context still allows a retry this session if the journal cannot be written.
+    log_if_err(
+        recording::write_pending_finalization_journal(stop_result),
+        "Failed to persist pending finalization",
+    );
     Ok(())
 }
 
@@ -1037,10 +1667,18 @@ fn check_path_exists(path: &Path) -> Result<bool, AppError> {
 fn has_required_finalization_artifacts(
     stop_result: &StopRecordingResult,
 ) -> Result<bool, AppError> {
-    if !check_path_exists(&stop_result.screen_video_path)? {
-        return Ok(false);
+    if let Some(screen_video_path) = stop_result.screen_video_path.as_ref() {
+        if !check_path_exists(screen_video_path)? {
+            return Ok(false);
+        }
     }
-    for segment_path in &stop_result.screen_segment_paths {
+    for segment_path in stop_result
+        .screen_segment_paths
+        .iter()
+        .chain(&stop_result.camera_segment_paths)
+        .chain(&stop_result.microphone_segment_paths)
+        .chain(&stop_result.system_audio_segment_paths)
+    {
         if !check_path_exists(segment_path)? {
             return Ok(false);
         }
@@ -1082,7 +1720,9 @@ fn clear_pending_finalization(
     let mut guard = pending_finalizations
         .lock()
         .map_err(|error| AppError::Lock(format!("Lock error: {}", error)))?;
-    guard.remove(project_id);
+    if let Some(stop_result) = guard.remove(project_id) {
+        recording::remove_pending_finalization_journal(&stop_result);
+    }
     Ok(())
 }
 
@@ -1093,32 +1733,47 @@ fn has_pending_finalization(
     Ok(get_pending_finalization(pending_finalizations, project_id)?.is_some())
 }
 
-async fn finalize_stopped_recording(
-    app: &AppHandle,
-    state: &tauri::State<'_, SharedRecorderState>,
-    project_id: &str,
-    stop_result: &StopRecordingResult,
+/// Join, probe and save a stopped take as a project, reporting each step to `on_status`.
+///
+/// Once its tracks are joined `stop_result` lists the merged files, so callers should persist it
+/// again when a later step fails.
+async fn finalize_recording_project(
+    ffmpeg: &FfmpegLauncher,
+    recordings_dir: &PathBuf,
+    stop_result: &mut StopRecordingResult,
+    on_status: impl Fn(&str),
 ) -> Result<(), AppError> {
-    let emit_finalizing_status = |status: &str| {
-        emit_with_log(
-            app,
-            "recording-finalizing",
-            serde_json::json!({
-                "projectId": project_id,
-                "status": status
-            }),
+    on_status("concatenating-segments");
+    let screen_durations = probe_screen_segment_durations(stop_result);
+    let (merged_tracks, normalized_screen_segments) =
+        merge_track_segments(ffmpeg, stop_result, &screen_durations).await?;
+    // Before committing, so tracks that were just joined are not rewritten a second time.
+    remux_fragmented_captures(ffmpeg, stop_result).await;
+    if !merged_tracks.is_empty() {
+        let committed = commit_merged_tracks(stop_result, merged_tracks).await;
+        log_if_err(
+            recording::refresh_pending_finalization_journal(stop_result),
+            "Failed to update pending finalization",
         );
-    };
-
-    emit_finalizing_status("concatenating-segments");
-    concatenate_screen_segments(app, stop_result).await?;
-    emit_finalizing_status("verifying-duration");
+        for path in committed? {
+            if let Err(error) = tokio::fs::remove_file(&path).await {
+                eprintln!("Failed to remove segment {}: {}", path.display(), error);
+            }
+        }
+    }
+    on_status("verifying-duration");
 
-    let recordings_dir = recordings_dir_from_managed_state(state)?;
-    let duration = match probe_video_duration(&stop_result.screen_video_path) {
-        Ok(value) if value.is_finite() && value > 0.0 => value,
-        Ok(_) => stop_result.duration_seconds.max(0.1),
-        Err(error) => {
+    // Camera-only and audio-only takes are timed and sized by the tracks they did record.
+    let timing_path = stop_result
+        .screen_video_path
+        .as_ref()
+        .or(stop_result.camera_video_path.as_ref())
+        .or(stop_result.microphone_audio_path.as_ref())
+        .or(stop_result.system_audio_path.as_ref());
+    let duration = match timing_path.map(probe_video_duration) {
+        Some(Ok(value)) if value.is_finite() && value > 0.0 => value,
+        Some(Ok(_)) | None => stop_result.duration_seconds.max(0.1),
+        Some(Err(error)) => {
             eprintln!(
                 "Failed to probe recording duration, falling back to session timing: {error}"
             );
@@ -1126,16 +1781,21 @@ async fn finalize_stopped_recording(
         }
     };
 
-    emit_finalizing_status("verifying-dimensions");
-    let (width, height) = match probe_video_dimensions(&stop_result.screen_video_path) {
-        Ok(dimensions) => dimensions,
-        Err(error) => {
+    on_status("verifying-dimensions");
+    let video_path = stop_result
+        .screen_video_path
+        .as_ref()
+        .or(stop_result.camera_video_path.as_ref());
+    let (width, height) = match video_path.map(probe_video_dimensions) {
+        Some(Ok(dimensions)) => dimensions,
+        Some(Err(error)) => {
             eprintln!("Failed to probe recording dimensions, falling back to source size: {error}");
             (stop_result.source_width, stop_result.source_height)
         }
+        None => AUDIO_ONLY_CANVAS_SIZE,
     };
 
-    let project = Project::new(
+    let mut project = Project::new(
         stop_result.project_id.clone(),
         stop_result.screen_video_path.clone(),
         stop_result.camera_video_path.clone(),
@@ -1146,9 +1806,47 @@ async fn finalize_stopped_recording(
         stop_result.camera_offset_ms,
         stop_result.microphone_offset_ms,
     );
+    project.system_audio_path = stop_result
+        .system_audio_path
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string());
+    project.system_audio_offset_ms = stop_result.system_audio_offset_ms;
+    project.cursor_log_path = stop_result
+        .cursor_log_path
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string());
+    project.normalized_screen_segments = normalized_screen_segments;
+    project.edits.markers = recording_markers(&stop_result.marker_times_ms, duration);
+
+    on_status("saving-project");
+    project::save_project(recordings_dir, &project).await
+}
 
-    emit_finalizing_status("saving-project");
-    project::save_project(&recordings_dir, &project).await?;
+async fn finalize_stopped_recording(
+    app: &AppHandle,
+    state: &tauri::State<'_, SharedRecorderState>,
+    project_id: &str,
+    stop_result: &mut StopRecordingResult,
+) -> Result<(), AppError> {
+    let emit_finalizing_status = |status: &str| {
+        emit_with_log(
+            app,
+            "recording-finalizing",
+            serde_json::json!({
+                "projectId": project_id,
+                "status": status
+            }),
+        );
+    };
+
+    let recordings_dir = recordings_dir_from_managed_state(state)?;
+    finalize_recording_project(
+        &FfmpegLauncher::Shell(app.clone()),
+        &recordings_dir,
+        stop_result,
+        &emit_finalizing_status,
+    )
+    .await?;
     emit_finalizing_status("refreshing-ui");
     refresh_tray_menu(app, &recordings_dir);
     Ok(())
@@ -1179,7 +1877,7 @@ async fn stop_screen_recording(
             "status": "stopping-capture"
         }),
     );
-    let stop_result = match do_stop_recording(&state, &project_id) {
+    let mut stop_result = match do_stop_recording(&state, &project_id) {
         Ok(result) => result,
         Err(error) => {
             let _ = clear_pending_finalization(pending_finalizations.inner(), &project_id);
@@ -1228,7 +1926,7 @@ async fn stop_screen_recording(
 
     let finalization_result = tokio::time::timeout(
         std::time::Duration::from_secs(STOP_RECORDING_FINALIZATION_TIMEOUT_SECS),
-        finalize_stopped_recording(&app, &state, &project_id, &stop_result),
+        finalize_stopped_recording(&app, &state, &project_id, &mut stop_result),
     )
     .await
     .unwrap_or_else(|_| {
@@ -1239,6 +1937,11 @@ async fn stop_screen_recording(
     });
 
     if let Err(error) = finalization_result {
+        // Tracks joined before the failure are now single files; retry from those.
+        log_if_err(
+            store_pending_finalization(pending_finalizations.inner(), &stop_result),
+            "Failed to update pending finalization",
+        );
         emit_with_log(
             &app,
             "recording-stop-failed",
@@ -1293,7 +1996,26 @@ async fn retry_recording_finalization(
     project_id: String,
 ) -> Result<(), AppError> {
     let project_id = normalize_project_id_input(project_id, "retry recording finalization")?;
-    let Some(stop_result) = get_pending_finalization(pending_finalizations.inner(), &project_id)?
+    // A saved project means an earlier run finished; finalizing again would overwrite its edits.
+    let project_file = recordings_dir_from_managed_state(&state)?
+        .join(&project_id)
+        .join("project.json");
+    if check_path_exists(&project_file)? {
+        let _ = clear_pending_finalization(pending_finalizations.inner(), &project_id);
+        let message = format!("Recording {} is already finalized.", project_id);
+        emit_with_log(
+            &app,
+            "recording-finalization-retry-status",
+            serde_json::json!({
+                "projectId": &project_id,
+                "status": "failed",
+                "message": &message
+            }),
+        );
+        return Err(AppError::Message(message));
+    }
+    let Some(mut stop_result) =
+        get_pending_finalization(pending_finalizations.inner(), &project_id)?
     else {
         emit_with_log(
             &app,
@@ -1328,7 +2050,7 @@ async fn retry_recording_finalization(
 
     let retry_result = tokio::time::timeout(
         std::time::Duration::from_secs(STOP_RECORDING_FINALIZATION_TIMEOUT_SECS),
-        finalize_stopped_recording(&app, &state, &project_id, &stop_result),
+        finalize_stopped_recording(&app, &state, &project_id, &mut stop_result),
     )
     .await
     .unwrap_or_else(|_| {
@@ -1339,6 +2061,10 @@ async fn retry_recording_finalization(
     });
 
     if let Err(error) = retry_result {
+        log_if_err(
+            store_pending_finalization(pending_finalizations.inner(), &stop_result),
+            "Failed to update pending finalization",
+        );
         emit_with_log(
             &app,
             "recording-finalization-retry-status",
@@ -1379,69 +2105,525 @@ async fn retry_recording_finalization(
         }),
     );
 
-    emit_with_log(
-        &app,
-        "recording-state-changed",
-        serde_json::json!({
-            "state": "idle",
-            "projectId": &project_id
-        }),
+    emit_with_log(
+        &app,
+        "recording-state-changed",
+        serde_json::json!({
+            "state": "idle",
+            "projectId": &project_id
+        }),
+    );
+
+    prepare_main_window_for_post_recording(&app);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    emit_with_log(&app, "recording-stopped", &project_id);
+    close_recording_widget_window(&app);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn has_pending_recording_finalization(
+    pending_finalizations: tauri::State<'_, SharedPendingFinalizations>,
+    project_id: String,
+) -> Result<bool, AppError> {
+    let project_id = normalize_project_id_input(project_id, "check pending finalization")?;
+    has_pending_finalization(pending_finalizations.inner(), &project_id)
+}
+
+/// List every stopped recording whose finalization can still be retried
+#[tauri::command]
+fn list_pending_finalizations(
+    pending_finalizations: tauri::State<'_, SharedPendingFinalizations>,
+) -> Result<Vec<StopRecordingResult>, AppError> {
+    let mut project_ids = pending_finalizations
+        .lock()
+        .map_err(|error| AppError::Lock(format!("Lock error: {}", error)))?
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    project_ids.sort();
+    let mut stop_results = Vec::new();
+    for project_id in project_ids {
+        if let Some(stop_result) =
+            get_pending_finalization(pending_finalizations.inner(), &project_id)?
+        {
+            stop_results.push(stop_result);
+        }
+    }
+    Ok(stop_results)
+}
+
+/// Probe each segment's duration; `None` marks one ffprobe cannot read, e.g. cut short by a crash.
+fn probe_segment_durations(segment_paths: &[PathBuf]) -> Vec<(PathBuf, Option<f64>)> {
+    segment_paths
+        .iter()
+        .map(|path| {
+            let duration = match probe_video_duration(path) {
+                Ok(duration) if duration.is_finite() && duration > 0.0 => Some(duration),
+                Ok(_) => None,
+                Err(error) => {
+                    eprintln!("Skipping unreadable segment {}: {}", path.display(), error);
+                    None
+                }
+            };
+            (path.clone(), duration)
+        })
+        .collect()
+}
+
+/// Keep the segments usable on every track, dropping a segment index from all tracks when any
+/// track's segment at that index is unusable so the merged tracks stay in sync.
+fn keep_segments_usable_on_every_track<const TRACKS: usize>(
+    tracks: [Vec<(PathBuf, Option<f64>)>; TRACKS],
+) -> [Vec<(PathBuf, f64)>; TRACKS] {
+    let unusable_indices = tracks
+        .iter()
+        .flatten()
+        .filter(|(_, duration)| duration.is_none())
+        .map(|(path, _)| recording::segment_part_index(path))
+        .collect::<HashSet<_>>();
+    tracks.map(|segments| {
+        segments
+            .into_iter()
+            .filter(|(path, _)| !unusable_indices.contains(&recording::segment_part_index(path)))
+            .filter_map(|(path, duration)| Some((path, duration?)))
+            .collect()
+    })
+}
+
+/// Describe an orphaned recording as if it had been stopped, so the normal finalization applies.
+fn recovered_stop_result(
+    recording: &RecoverableRecording,
+) -> Result<StopRecordingResult, AppError> {
+    let [screen_segments, camera_segments, microphone_segments, system_audio_segments] =
+        keep_segments_usable_on_every_track([
+            probe_segment_durations(&recording.screen_segment_paths),
+            probe_segment_durations(&recording.camera_segment_paths),
+            probe_segment_durations(&recording.microphone_segment_paths),
+            probe_segment_durations(&recording.system_audio_segment_paths),
+        ]);
+    // The screen times the take; camera-only and audio-only takes use their first track instead.
+    let Some(timing_segments) = [
+        &screen_segments,
+        &camera_segments,
+        &microphone_segments,
+        &system_audio_segments,
+    ]
+    .into_iter()
+    .find(|segments| !segments.is_empty()) else {
+        return Err(AppError::Message(format!(
+            "Recording {} has no readable footage to recover",
+            recording.project_id
+        )));
+    };
+    let duration_seconds = timing_segments.iter().map(|(_, duration)| duration).sum();
+    let track = |segments: Vec<(PathBuf, f64)>| {
+        let segments = segments
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        (segments.first().cloned(), segments)
+    };
+    let (screen_video_path, screen_segment_paths) = track(screen_segments);
+    let (camera_video_path, camera_segment_paths) = track(camera_segments);
+    let (microphone_audio_path, microphone_segment_paths) = track(microphone_segments);
+    let (system_audio_path, system_audio_segment_paths) = track(system_audio_segments);
+    let (source_width, source_height) = screen_video_path
+        .as_ref()
+        .or(camera_video_path.as_ref())
+        .and_then(|path| probe_video_dimensions(path).ok())
+        .unwrap_or((0, 0));
+
+    Ok(StopRecordingResult {
+        project_id: recording.project_id.clone(),
+        screen_video_path,
+        screen_segment_paths,
+        camera_video_path,
+        camera_segment_paths,
+        microphone_audio_path,
+        microphone_segment_paths,
+        system_audio_path,
+        system_audio_segment_paths,
+        duration_seconds,
+        source_width,
+        source_height,
+        // The sync offsets and markers lived only in the crashed session.
+        camera_offset_ms: None,
+        microphone_offset_ms: None,
+        system_audio_offset_ms: None,
+        segment_offsets: SegmentOffsets::default(),
+        cursor_log_path: recording.cursor_log_path.clone(),
+        // Takes are recovered where they were recorded, and only the Linux recorder fragments.
+        fragmented_capture: cfg!(target_os = "linux"),
+        marker_times_ms: Vec::new(),
+    })
+}
+
+fn take_recoverable_recording(
+    recoverable_recordings: &SharedRecoverableRecordings,
+    project_id: &str,
+) -> Result<RecoverableRecording, AppError> {
+    let mut guard = recoverable_recordings
+        .lock()
+        .map_err(|error| AppError::Lock(format!("Lock error: {}", error)))?;
+    let index = guard
+        .iter()
+        .position(|recording| recording.project_id == project_id)
+        .ok_or_else(|| {
+            AppError::Message(format!("No recoverable recording found: {}", project_id))
+        })?;
+    Ok(guard.remove(index))
+}
+
+/// List recordings found without a project file when the app started
+#[tauri::command]
+fn list_recoverable_recordings(
+    recoverable_recordings: tauri::State<'_, SharedRecoverableRecordings>,
+) -> Result<Vec<RecoverableRecording>, AppError> {
+    let guard = recoverable_recordings
+        .lock()
+        .map_err(|error| AppError::Lock(format!("Lock error: {}", error)))?;
+    Ok(guard.clone())
+}
+
+/// Turn an orphaned recording into a project from its readable segments
+#[tauri::command]
+async fn recover_recording(
+    app: AppHandle,
+    state: tauri::State<'_, SharedRecorderState>,
+    recoverable_recordings: tauri::State<'_, SharedRecoverableRecordings>,
+    project_id: String,
+) -> Result<Project, AppError> {
+    let project_id = normalize_project_id_input(project_id, "recover recording")?;
+    let recording = take_recoverable_recording(recoverable_recordings.inner(), &project_id)?;
+    let recovered = match recovered_stop_result(&recording) {
+        Ok(mut stop_result) => {
+            finalize_stopped_recording(&app, &state, &project_id, &mut stop_result).await
+        }
+        Err(error) => Err(error),
+    };
+    if let Err(error) = recovered {
+        // Tracks joined before the failure replaced their segments, so list the take as it is now.
+        let recording = recordings_dir_from_managed_state(&state)
+            .ok()
+            .and_then(|recordings_dir| {
+                recording::find_recoverable_recordings(&recordings_dir)
+                    .into_iter()
+                    .find(|found| found.project_id == project_id)
+            })
+            .unwrap_or(recording);
+        if let Ok(mut guard) = recoverable_recordings.lock() {
+            guard.push(recording);
+        }
+        return Err(error);
+    }
+    let recordings_dir = recordings_dir_from_managed_state(&state)?;
+    project::load_project(&recordings_dir, &project_id).await
+}
+
+/// Delete an orphaned recording the user does not want to keep
+#[tauri::command]
+async fn discard_recoverable_recording(
+    state: tauri::State<'_, SharedRecorderState>,
+    recoverable_recordings: tauri::State<'_, SharedRecoverableRecordings>,
+    project_id: String,
+) -> Result<(), AppError> {
+    let project_id = normalize_project_id_input(project_id, "discard recording")?;
+    take_recoverable_recording(recoverable_recordings.inner(), &project_id)?;
+    let recordings_dir = recordings_dir_from_managed_state(&state)?;
+    project::delete_project(&recordings_dir, &project_id).await
+}
+
+/// Set media offsets gathered by frontend camera/mic recorders
+#[tauri::command]
+fn set_recording_media_offsets(
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+    camera_offset_ms: Option<i64>,
+    microphone_offset_ms: Option<i64>,
+) -> Result<(), AppError> {
+    let project_id = normalize_project_id_input(project_id, "set media offsets")?;
+    do_set_media_offsets(&state, &project_id, camera_offset_ms, microphone_offset_ms)
+}
+
+#[tauri::command]
+fn get_recording_state(
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<Option<RecorderRecordingState>, AppError> {
+    let project_id = normalize_project_id_input(project_id, "get recording state")?;
+    do_get_recording_state(&state, &project_id)
+}
+
+#[tauri::command]
+fn get_recording_snapshot(
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<Option<RecordingSessionSnapshot>, AppError> {
+    let project_id = normalize_project_id_input(project_id, "get recording snapshot")?;
+    do_get_recording_snapshot(&state, &project_id)
+}
+
+#[tauri::command]
+fn get_recording_source_status(
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<Option<RecordingSourceStatus>, AppError> {
+    let project_id = normalize_project_id_input(project_id, "get recording source status")?;
+    recording::get_recording_source_status(&state, &project_id)
+}
+
+/// Markers dropped during a take, kept inside its probed duration.
+fn recording_markers(marker_times_ms: &[u64], duration: f64) -> Vec<Marker> {
+    marker_times_ms
+        .iter()
+        .enumerate()
+        .map(|(index, time_ms)| Marker {
+            id: Uuid::new_v4().to_string(),
+            time: (*time_ms as f64 / 1000.0).min(duration),
+            label: Some(format!("Marker {}", index + 1)),
+        })
+        .collect()
+}
+
+fn record_marker(
+    app: &AppHandle,
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<f64, AppError> {
+    let time = do_add_recording_marker(state, project_id)?;
+    emit_with_log(
+        app,
+        "recording-marker-added",
+        serde_json::json!({
+            "projectId": project_id,
+            "time": time
+        }),
+    );
+    Ok(time)
+}
+
+/// Mark whichever take is active; the shortcut and tray item have no project id at hand.
+fn add_marker_to_active_recording(app: &AppHandle) {
+    let state = app.state::<SharedRecorderState>();
+    let project_id = match active_recording_project_id(&state) {
+        Ok(Some(project_id)) => project_id,
+        Ok(None) => return,
+        Err(error) => {
+            eprintln!(
+                "Failed to find the active recording for a marker: {}",
+                error
+            );
+            return;
+        }
+    };
+    if let Err(error) = record_marker(app, &state, &project_id) {
+        eprintln!(
+            "Failed to add marker to recording {}: {}",
+            project_id, error
+        );
+    }
+}
+
+/// Drop a marker at the current point of a recording
+#[tauri::command]
+fn add_recording_marker(
+    app: AppHandle,
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<f64, AppError> {
+    let project_id = normalize_project_id_input(project_id, "add recording marker")?;
+    record_marker(&app, &state, &project_id)
+}
+
+fn quick_record_last_settings(app: &AppHandle) {
+    show_main_window(app);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+        emit_with_log(&app, "tray-quick-record", ());
+    });
+}
+
+fn trigger_shortcut_action(app: &AppHandle, action: ShortcutAction) {
+    match action {
+        ShortcutAction::StartStop => emit_with_log(app, "global-shortcut-start-stop", ()),
+        ShortcutAction::PauseResume => emit_with_log(app, "global-shortcut-toggle-pause", ()),
+        ShortcutAction::AddMarker => add_marker_to_active_recording(app),
+        ShortcutAction::OpenRecorder => {
+            show_main_window(app);
+            emit_with_log(app, "tray-open-recorder", ());
+        }
+        ShortcutAction::OpenProjects => {
+            show_main_window(app);
+            emit_with_log(app, "tray-open-projects", ());
+        }
+        ShortcutAction::QuickRecord => quick_record_last_settings(app),
+    }
+}
+
+/// Register one shortcut; the error says why the OS refused it.
+fn register_global_shortcut(
+    app: &AppHandle,
+    action: ShortcutAction,
+    accelerator: &str,
+) -> Result<(), String> {
+    let shortcut = accelerator
+        .parse::<Shortcut>()
+        .map_err(|error| error.to_string())?;
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app_handle, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                trigger_shortcut_action(app_handle, action);
+            }
+        })
+        .map_err(|error| error.to_string())
+}
+
+/// Replace every registered shortcut with `bindings`; returns why each refused one failed.
+fn register_global_shortcuts(
+    app: &AppHandle,
+    bindings: &ShortcutBindings,
+) -> BTreeMap<ShortcutAction, String> {
+    log_if_err(
+        app.global_shortcut().unregister_all(),
+        "Failed to unregister global shortcuts",
     );
+    let mut registration_errors = BTreeMap::new();
+    for (action, accelerator) in bindings.bound() {
+        if let Err(error) = register_global_shortcut(app, action, accelerator) {
+            eprintln!(
+                "Failed to register global shortcut {} for {:?}: {}",
+                accelerator, action, error
+            );
+            registration_errors.insert(action, error);
+        }
+    }
+    registration_errors
+}
 
-    prepare_main_window_for_post_recording(&app);
-    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-    emit_with_log(&app, "recording-stopped", &project_id);
-    close_recording_widget_window(&app);
+/// Register `bindings` and make them the ones in effect, refreshing the menu accelerators.
+fn apply_shortcut_bindings(
+    app: &AppHandle,
+    bindings: ShortcutBindings,
+) -> Result<Vec<ShortcutBindingStatus>, AppError> {
+    let registration_errors = register_global_shortcuts(app, &bindings);
+    let statuses = {
+        let state = app.state::<SharedGlobalShortcuts>();
+        let mut state_guard = state
+            .lock()
+            .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+        state_guard.bindings = bindings;
+        state_guard.registration_errors = registration_errors;
+        state_guard.statuses()
+    };
+    if let Ok(recordings_dir) = recordings_dir_from_state(app) {
+        refresh_tray_menu(app, &recordings_dir);
+    }
+    emit_with_log(app, "shortcut-bindings-changed", statuses.clone());
+    Ok(statuses)
+}
 
-    Ok(())
+fn current_shortcut_bindings(
+    state: &tauri::State<'_, SharedGlobalShortcuts>,
+) -> Result<ShortcutBindings, AppError> {
+    let state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    Ok(state_guard.bindings.clone())
 }
 
-#[tauri::command]
-fn has_pending_recording_finalization(
-    pending_finalizations: tauri::State<'_, SharedPendingFinalizations>,
-    project_id: String,
-) -> Result<bool, AppError> {
-    let project_id = normalize_project_id_input(project_id, "check pending finalization")?;
-    has_pending_finalization(pending_finalizations.inner(), &project_id)
+/// `bindings` with `action` rebound, checked against what the shortcut plugin accepts.
+fn rebind_shortcut(
+    bindings: &ShortcutBindings,
+    action: ShortcutAction,
+    accelerator: Option<&str>,
+) -> Result<ShortcutBindings, AppError> {
+    let rebound = bindings.rebind(action, accelerator)?;
+    if let Some(accelerator) = rebound.accelerator(action) {
+        accelerator.parse::<Shortcut>().map_err(|error| {
+            AppError::Message(format!("Invalid shortcut \"{}\": {}", accelerator, error))
+        })?;
+    }
+    Ok(rebound)
 }
 
-/// Set media offsets gathered by frontend camera/mic recorders
+fn persist_shortcut_bindings(app: &AppHandle, bindings: &ShortcutBindings) -> Result<(), AppError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| AppError::Message(format!("Failed to resolve app data dir: {}", error)))?;
+    save_shortcut_bindings(&shortcut_bindings_path(&app_data_dir), bindings)
+}
+
+/// Global shortcut bindings and any the OS refused to register
 #[tauri::command]
-fn set_recording_media_offsets(
-    state: tauri::State<'_, SharedRecorderState>,
-    project_id: String,
-    camera_offset_ms: Option<i64>,
-    microphone_offset_ms: Option<i64>,
-) -> Result<(), AppError> {
-    let project_id = normalize_project_id_input(project_id, "set media offsets")?;
-    do_set_media_offsets(&state, &project_id, camera_offset_ms, microphone_offset_ms)
+fn get_shortcut_bindings(
+    state: tauri::State<'_, SharedGlobalShortcuts>,
+) -> Result<Vec<ShortcutBindingStatus>, AppError> {
+    let state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    Ok(state_guard.statuses())
 }
 
+/// Check a shortcut for an action without applying it; returns its normalized form
 #[tauri::command]
-fn get_recording_state(
-    state: tauri::State<'_, SharedRecorderState>,
-    project_id: String,
-) -> Result<Option<RecorderRecordingState>, AppError> {
-    let project_id = normalize_project_id_input(project_id, "get recording state")?;
-    do_get_recording_state(&state, &project_id)
+fn validate_shortcut_binding(
+    state: tauri::State<'_, SharedGlobalShortcuts>,
+    action: ShortcutAction,
+    accelerator: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let rebound = rebind_shortcut(
+        &current_shortcut_bindings(&state)?,
+        action,
+        accelerator.as_deref(),
+    )?;
+    Ok(rebound.accelerator(action).map(str::to_string))
 }
 
+/// Rebind or, with no accelerator, unbind a global shortcut
 #[tauri::command]
-fn get_recording_snapshot(
-    state: tauri::State<'_, SharedRecorderState>,
-    project_id: String,
-) -> Result<Option<RecordingSessionSnapshot>, AppError> {
-    let project_id = normalize_project_id_input(project_id, "get recording snapshot")?;
-    do_get_recording_snapshot(&state, &project_id)
+fn set_shortcut_binding(
+    app: AppHandle,
+    state: tauri::State<'_, SharedGlobalShortcuts>,
+    action: ShortcutAction,
+    accelerator: Option<String>,
+) -> Result<Vec<ShortcutBindingStatus>, AppError> {
+    let current = current_shortcut_bindings(&state)?;
+    let rebound = rebind_shortcut(&current, action, accelerator.as_deref())?;
+    // Claim the new shortcut before anything changes, so a refused one leaves the old binding
+    // registered and saved.
+    let claimed = rebound
+        .accelerator(action)
+        .filter(|next| current.accelerator(action) != Some(*next));
+    if let Some(next) = claimed {
+        register_global_shortcut(&app, action, next).map_err(|error| {
+            AppError::Message(format!(
+                "Could not register shortcut \"{}\": {}",
+                next, error
+            ))
+        })?;
+    }
+    if let Err(error) = persist_shortcut_bindings(&app, &rebound) {
+        if let Some(shortcut) = claimed.and_then(|next| next.parse::<Shortcut>().ok()) {
+            log_if_err(
+                app.global_shortcut().unregister(shortcut),
+                "Failed to roll back a shortcut that could not be saved",
+            );
+        }
+        return Err(error);
+    }
+    apply_shortcut_bindings(&app, rebound)
 }
 
+/// Restore the default global shortcuts
 #[tauri::command]
-fn get_recording_source_status(
-    state: tauri::State<'_, SharedRecorderState>,
-    project_id: String,
-) -> Result<Option<RecordingSourceStatus>, AppError> {
-    let project_id = normalize_project_id_input(project_id, "get recording source status")?;
-    recording::get_recording_source_status(&state, &project_id)
+fn reset_shortcut_bindings(app: AppHandle) -> Result<Vec<ShortcutBindingStatus>, AppError> {
+    let defaults = ShortcutBindings::default();
+    persist_shortcut_bindings(&app, &defaults)?;
+    apply_shortcut_bindings(&app, defaults)
 }
 
 /// Pause screen recording
@@ -1486,6 +2668,7 @@ fn resume_recording(
         ));
     }
 
+    recording::ensure_resume_disk_space(&state, &project_id)?;
     let fallback_source = do_resume_recording(&state, &project_id)?;
 
     emit_with_log(
@@ -1512,6 +2695,78 @@ fn resume_recording(
     Ok(())
 }
 
+/// Save the replay buffer's retained footage as a new project; returns its id
+#[tauri::command]
+async fn save_replay(
+    app: AppHandle,
+    state: tauri::State<'_, SharedRecorderState>,
+    pending_finalizations: tauri::State<'_, SharedPendingFinalizations>,
+    project_id: String,
+) -> Result<String, AppError> {
+    let project_id = normalize_project_id_input(project_id, "save replay")?;
+    // Saving waits for the current replay segment to close, so keep it off the async runtime.
+    let recorder_state = state.inner().clone();
+    let buffer_project_id = project_id.clone();
+    let mut stop_result = tauri::async_runtime::spawn_blocking(move || {
+        do_save_replay(&recorder_state, &buffer_project_id)
+    })
+    .await
+    .map_err(|error| AppError::Message(format!("Replay save task failed: {}", error)))
+    .and_then(|result| result)?;
+
+    let replay_project_id = stop_result.project_id.clone();
+    store_pending_finalization(pending_finalizations.inner(), &stop_result)?;
+    let finalized = tokio::time::timeout(
+        std::time::Duration::from_secs(STOP_RECORDING_FINALIZATION_TIMEOUT_SECS),
+        finalize_stopped_recording(&app, &state, &replay_project_id, &mut stop_result),
+    )
+    .await
+    .unwrap_or_else(|_| {
+        Err(AppError::Message(format!(
+            "Replay finalization timed out after {} seconds.",
+            STOP_RECORDING_FINALIZATION_TIMEOUT_SECS
+        )))
+    });
+    if finalized.is_err() {
+        log_if_err(
+            store_pending_finalization(pending_finalizations.inner(), &stop_result),
+            "Failed to update pending finalization",
+        );
+    }
+    finalized?;
+    let _ = clear_pending_finalization(pending_finalizations.inner(), &replay_project_id);
+
+    emit_with_log(
+        &app,
+        "replay-saved",
+        serde_json::json!({
+            "projectId": &replay_project_id,
+            "bufferProjectId": &project_id
+        }),
+    );
+    Ok(replay_project_id)
+}
+
+/// Stop the replay buffer and discard its unsaved footage
+#[tauri::command]
+fn stop_replay_buffer(
+    app: AppHandle,
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<(), AppError> {
+    let project_id = normalize_project_id_input(project_id, "stop replay buffer")?;
+    do_stop_replay_buffer(&state, &project_id)?;
+    emit_with_log(
+        &app,
+        "recording-state-changed",
+        serde_json::json!({
+            "state": "idle",
+            "projectId": project_id
+        }),
+    );
+    Ok(())
+}
+
 /// Open the recording widget window
 #[tauri::command]
 fn open_recording_widget(
@@ -1544,7 +2799,7 @@ fn open_recording_widget(
         WebviewUrl::App("/recording-widget".into()),
     )
     .title("Recording")
-    .inner_size(220.0, 60.0)
+    .inner_size(256.0, 60.0)
     .resizable(false)
     .decorations(false)
     .always_on_top(true)
@@ -1574,6 +2829,24 @@ fn recordings_dir_from_managed_state(
     Ok(state_guard.recordings_dir.clone())
 }
 
+/// The take a marker should go to; replay buffers keep no markers.
+fn active_recording_project_id(state: &SharedRecorderState) -> Result<Option<String>, AppError> {
+    let state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    Ok(state_guard
+        .sessions
+        .values()
+        .find(|session| {
+            session.options.replay_buffer_seconds.is_none()
+                && matches!(
+                    session.state,
+                    RecorderRecordingState::Recording | RecorderRecordingState::Paused
+                )
+        })
+        .map(|session| session.project_id.clone()))
+}
+
 fn has_active_recording_session(state: &SharedRecorderState) -> Result<bool, AppError> {
     let state_guard = state
         .lock()
@@ -2062,6 +3335,28 @@ async fn delete_project(
     Ok(())
 }
 
+/// Suggest zoom effects from the clicks captured in the project's cursor log
+#[tauri::command]
+async fn suggest_auto_zooms(
+    state: tauri::State<'_, SharedRecorderState>,
+    project_id: String,
+) -> Result<Vec<ZoomEffect>, AppError> {
+    let project_id = normalize_project_id_input(project_id, "suggest auto zooms")?;
+    let recordings_dir = recordings_dir_from_managed_state(&state)?;
+    let project = project::load_project(&recordings_dir, &project_id).await?;
+    let cursor_log_path = project
+        .cursor_log_path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| recording::cursor_log_path(&recordings_dir.join(&project.id)));
+    if !cursor_log_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let events = recording::load_cursor_events(&cursor_log_path).await?;
+    Ok(project::suggest_zoom_effects(&project, &events))
+}
+
 /// Export a project
 #[tauri::command]
 async fn export_project(
@@ -2083,8 +3378,10 @@ async fn export_project(
     // Get output path
     let output_path = get_export_output_path(&project, &options, &downloads_dir);
 
-    // Build ffmpeg arguments
-    let args = build_ffmpeg_args(&project, &options, &output_path);
+    // Build ffmpeg arguments; the work dir lives until the export task finishes.
+    let work_dir = ExportWorkDir::create().await?;
+    let export_command = build_ffmpeg_args(&project, &options, &output_path, work_dir.path());
+    export_command.write_side_files().await?;
 
     // Run ffmpeg using the shell plugin
     let shell = app.shell();
@@ -2101,14 +3398,15 @@ async fn export_project(
         }
     };
     let (mut rx, child) = ffmpeg_command
-        .args(&args)
+        .args(&export_command.args)
         .spawn()
         .map_err(|e| AppError::Message(format!("Failed to spawn ffmpeg: {}", e)))?;
 
     // Clone output_path for use in async block
     let output_path_for_event = output_path.clone();
     let output_path_str = output_path.to_string_lossy().to_string();
-    let expected_duration = project.duration.max(1.0);
+    let expected_duration = export_output_duration(&project).max(1.0);
+    let chapter_list_project = options.chapter_list.then_some(project);
     let job_id = Uuid::new_v4().to_string();
     let job_pid = child.pid();
 
@@ -2129,6 +3427,7 @@ async fn export_project(
     let export_jobs_clone = export_jobs.inner().clone();
     let job_id_for_task = job_id.clone();
     tokio::spawn(async move {
+        let _work_dir = work_dir;
         let started = tokio::time::Instant::now();
         let mut last_progress_seconds = 0.0_f64;
         while let Some(event) = rx.recv().await {
@@ -2175,6 +3474,20 @@ async fn export_project(
                     }
 
                     if status.code == Some(0) {
+                        let chapter_list_path = match chapter_list_project.as_ref() {
+                            Some(project) => {
+                                match write_youtube_chapter_list(project, &output_path_for_event)
+                                    .await
+                                {
+                                    Ok(path) => path,
+                                    Err(error) => {
+                                        eprintln!("Failed to write export chapter list: {}", error);
+                                        None
+                                    }
+                                }
+                            }
+                            None => None,
+                        };
                         emit_with_log(
                             &app_clone,
                             "export-progress",
@@ -2188,7 +3501,9 @@ async fn export_project(
                             "export-complete",
                             serde_json::json!({
                                 "jobId": &job_id_for_task,
-                                "outputPath": output_path_for_event.to_string_lossy().to_string()
+                                "outputPath": output_path_for_event.to_string_lossy().to_string(),
+                                "chapterListPath": chapter_list_path
+                                    .map(|path| path.to_string_lossy().to_string())
                             }),
                         );
                     } else {
@@ -2299,6 +3614,149 @@ fn list_active_export_jobs(
     active_export_job_ids(export_jobs.inner())
 }
 
+fn control_request_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, AppError> {
+    serde_json::from_slice(body)
+        .map_err(|error| AppError::Message(format!("Invalid request body: {}", error)))
+}
+
+fn control_response<T: serde::Serialize>(value: T) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(value)
+        .map_err(|error| AppError::Message(format!("Failed to serialize response: {}", error)))
+}
+
+/// Run a control API request through the same commands the webview invokes.
+async fn run_control_route(
+    app: AppHandle,
+    route: ControlRoute,
+    body: Vec<u8>,
+) -> Result<serde_json::Value, AppError> {
+    match route {
+        ControlRoute::ListSources(source_type) => {
+            let source_type = serde_json::from_value(serde_json::Value::String(source_type))
+                .map_err(|error| AppError::Message(format!("Unknown source type: {}", error)))?;
+            control_response(list_capture_sources(source_type)?)
+        }
+        ControlRoute::StartRecording => control_response(
+            start_screen_recording(app.clone(), app.state(), control_request_body(&body)?).await?,
+        ),
+        ControlRoute::RecordingSnapshot(project_id) => {
+            control_response(get_recording_snapshot(app.state(), project_id)?)
+        }
+        ControlRoute::StopRecording(project_id) => {
+            stop_screen_recording(app.clone(), app.state(), app.state(), project_id).await?;
+            Ok(serde_json::Value::Null)
+        }
+        // Pausing and resuming wait on the capture processes, so keep them off the runtime.
+        ControlRoute::PauseRecording(project_id) => {
+            tauri::async_runtime::spawn_blocking(move || {
+                pause_recording(app.clone(), app.state(), project_id)
+            })
+            .await
+            .map_err(|error| AppError::Message(format!("Recording pause task failed: {}", error)))
+            .and_then(|result| result)?;
+            Ok(serde_json::Value::Null)
+        }
+        ControlRoute::ResumeRecording(project_id) => {
+            tauri::async_runtime::spawn_blocking(move || {
+                resume_recording(app.clone(), app.state(), project_id)
+            })
+            .await
+            .map_err(|error| AppError::Message(format!("Recording resume task failed: {}", error)))
+            .and_then(|result| result)?;
+            Ok(serde_json::Value::Null)
+        }
+        ControlRoute::ListProjects => control_response(list_projects(app.state()).await?),
+        ControlRoute::ExportProject(project_id) => control_response(
+            export_project(
+                app.clone(),
+                app.state(),
+                app.state(),
+                project_id,
+                control_request_body(&body)?,
+            )
+            .await?,
+        ),
+    }
+}
+
+/// Stop the control server and start it again if the settings enable it.
+///
+/// Must be called from within the async runtime.
+fn restart_control_server(
+    app: &AppHandle,
+    control_api: &mut ControlApiState,
+) -> Result<(), AppError> {
+    if let Some(server) = control_api.server.take() {
+        server.stop();
+    }
+    if !control_api.settings.enabled {
+        return Ok(());
+    }
+    let handler_app = app.clone();
+    control_api.server = Some(ControlServer::start(
+        control_api.settings.port,
+        control_api.settings.token.clone(),
+        control_api.events.clone(),
+        move |route, body| run_control_route(handler_app.clone(), route, body),
+    )?);
+    Ok(())
+}
+
+fn update_control_api_settings(
+    app: &AppHandle,
+    update: impl FnOnce(&mut ControlApiSettings),
+) -> Result<ControlApiStatus, AppError> {
+    let state = app.state::<SharedControlApi>();
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let mut settings = state_guard.settings.clone();
+    update(&mut settings);
+    if settings.port == 0 {
+        return Err(AppError::Message(
+            "The control API needs a fixed port".to_string(),
+        ));
+    }
+    save_control_api_settings(&state_guard.settings_path, &settings)?;
+    state_guard.settings = settings;
+    restart_control_server(app, &mut state_guard)?;
+    Ok(state_guard.status())
+}
+
+/// Local control API settings and whether it is listening
+#[tauri::command]
+fn get_control_api_status(
+    state: tauri::State<'_, SharedControlApi>,
+) -> Result<ControlApiStatus, AppError> {
+    let state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    Ok(state_guard.status())
+}
+
+/// Turn the local control API on or off, optionally moving it to another port
+#[tauri::command]
+async fn set_control_api_enabled(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<ControlApiStatus, AppError> {
+    update_control_api_settings(&app, |settings| {
+        settings.enabled = enabled;
+        if let Some(port) = port {
+            settings.port = port;
+        }
+    })
+}
+
+/// Replace the control API token; scripts using the old one are rejected
+#[tauri::command]
+async fn regenerate_control_api_token(app: AppHandle) -> Result<ControlApiStatus, AppError> {
+    update_control_api_settings(&app, |settings| {
+        settings.token = generate_control_api_token();
+    })
+}
+
 /// Parse ffmpeg progress from stderr line
 fn parse_ffmpeg_progress(line: &str) -> Option<f64> {
     fn parse_hhmmss(value: &str) -> Option<f64> {
@@ -2366,13 +3824,15 @@ fn parse_ffmpeg_progress(line: &str) -> Option<f64> {
 #[cfg(test)]
 mod tests {
     use super::{
-        active_export_job_ids, active_export_job_ids_without_process_check, build_editor_route,
-        clear_pending_finalization, get_pending_finalization, handle_ffmpeg_timeout,
-        has_active_recording_session, has_pending_finalization, is_missing_process_error,
-        is_process_running, normalize_opened_project_id, normalize_project_id_input,
-        parse_ffmpeg_progress, parse_ffprobe_dimensions_output, parse_ffprobe_duration_output,
-        project_id_from_opened_path, resolve_project_dir_from_payload, store_pending_finalization,
-        AppError, RecorderRecordingState, RecorderState, RecordingOptions,
+        active_export_job_ids, active_export_job_ids_without_process_check, block_on_io,
+        build_editor_route, clear_pending_finalization, commit_merged_tracks,
+        get_pending_finalization, handle_ffmpeg_timeout, has_active_recording_session,
+        has_pending_finalization, is_missing_process_error, is_process_running,
+        keep_segments_usable_on_every_track, normalize_opened_project_id,
+        normalize_project_id_input, parse_ffmpeg_progress, parse_ffprobe_dimensions_output,
+        parse_ffprobe_duration_output, project_id_from_opened_path,
+        resolve_project_dir_from_payload, store_pending_finalization, AppError, DiskSpacePolicy,
+        MergedTrack, RecorderRecordingState, RecorderState, RecordingOptions, SegmentOffsets,
         SharedPendingFinalizations, SharedRecorderState, SourceType, StopRecordingResult,
         OPENREC_RELEASES_URL, OPENREC_UNSIGNED_INSTALL_GUIDE_URL,
     };
@@ -2391,6 +3851,39 @@ mod tests {
         path
     }
 
+    #[test]
+    fn drops_a_segment_index_from_every_track_when_one_track_lost_it() {
+        let dir = PathBuf::from("/recordings/take");
+        let segment = |name: &str, duration: Option<f64>| (dir.join(name), duration);
+        let [screen, camera] = keep_segments_usable_on_every_track([
+            vec![
+                segment("screen.mp4", Some(10.0)),
+                segment("screen_part1.mp4", Some(5.0)),
+                segment("screen_part2.mp4", None),
+            ],
+            vec![
+                segment("camera.mp4", Some(10.0)),
+                segment("camera_part1.mp4", Some(5.0)),
+                segment("camera_part2.mp4", Some(3.0)),
+            ],
+        ]);
+
+        assert_eq!(
+            screen,
+            vec![
+                (dir.join("screen.mp4"), 10.0),
+                (dir.join("screen_part1.mp4"), 5.0)
+            ]
+        );
+        assert_eq!(
+            camera,
+            vec![
+                (dir.join("camera.mp4"), 10.0),
+                (dir.join("camera_part1.mp4"), 5.0)
+            ]
+        );
+    }
+
     #[test]
     fn parses_out_time_us_progress() {
         let parsed = parse_ffmpeg_progress("out_time_us=3500000");
@@ -2422,6 +3915,18 @@ mod tests {
         assert_eq!(message, "ffmpeg command timed out after 9 seconds");
     }
 
+    #[test]
+    fn recording_markers_are_numbered_and_kept_inside_the_take() {
+        let markers = recording_markers(&[1_500, 12_400], 12.0);
+        assert_eq!(
+            markers
+                .iter()
+                .map(|marker| (marker.time, marker.label.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![(1.5, Some("Marker 1")), (12.0, Some("Marker 2"))]
+        );
+    }
+
     #[test]
     fn parses_out_time_ms_progress() {
         let parsed = parse_ffmpeg_progress("out_time_ms=4200000");
@@ -2609,14 +4114,22 @@ mod tests {
                         source_id: "display-1".to_string(),
                         source_type: SourceType::Display,
                         preferred_display_ordinal: Some(1),
+                        capture_screen: true,
                         capture_camera: false,
+                        camera_device_id: None,
                         capture_microphone: false,
+                        microphone_device_id: None,
                         capture_system_audio: true,
+                        region: None,
+                        replay_buffer_seconds: None,
+                        disk_space: DiskSpacePolicy::default(),
+                        auto_source_fallback: false,
                         quality_preset: RecordingQualityPreset::P1080P30,
                         codec: RecordingCodec::H264,
                     },
                     state: RecorderRecordingState::Paused,
-                    screen_video_path: PathBuf::from("/tmp/screen.mp4"),
+                    project_dir: PathBuf::from("/tmp"),
+                    screen_video_path: Some(PathBuf::from("/tmp/screen.mp4")),
                     camera_video_path: None,
                     microphone_audio_path: None,
                     start_time: chrono::Utc::now(),
@@ -2627,17 +4140,41 @@ mod tests {
                     capture_fps: 30,
                     recording_codec: RecordingCodec::H264,
                     screen_segments: vec![PathBuf::from("/tmp/screen.mp4")],
+                    camera_segments: vec![],
+                    microphone_segments: vec![],
+                    system_audio_path: None,
+                    system_audio_segments: vec![],
+                    system_audio_offset_ms: None,
                     current_segment_path: PathBuf::from("/tmp/screen.mp4"),
                     active_duration_ms: 0,
                     last_resume_instant: Some(Instant::now()),
                     camera_offset_ms: None,
                     microphone_offset_ms: None,
+                    segment_offsets: SegmentOffsets::default(),
+                    marker_times_ms: Vec::new(),
+                    disk_watchdog: None,
                     #[cfg(target_os = "macos")]
                     stream: None,
                     #[cfg(target_os = "macos")]
                     recording_output: None,
                     #[cfg(target_os = "linux")]
                     ffmpeg_child: None,
+                    #[cfg(target_os = "linux")]
+                    health_monitor: None,
+                    #[cfg(target_os = "linux")]
+                    pipewire_feed: None,
+                    #[cfg(target_os = "linux")]
+                    portal_screen_cast: None,
+                    #[cfg(target_os = "linux")]
+                    cursor_telemetry: None,
+                    #[cfg(target_os = "linux")]
+                    camera_ffmpeg_child: None,
+                    #[cfg(target_os = "linux")]
+                    microphone_ffmpeg_child: None,
+                    #[cfg(target_os = "linux")]
+                    system_audio_ffmpeg_child: None,
+                    #[cfg(target_os = "linux")]
+                    replay_pruner: None,
                 },
             );
         }
@@ -2668,15 +4205,24 @@ mod tests {
             Arc::new(Mutex::new(HashMap::new()));
         let stop_result = StopRecordingResult {
             project_id: "retry-project".to_string(),
-            screen_video_path: screen_path.clone(),
+            screen_video_path: Some(screen_path.clone()),
             screen_segment_paths: vec![screen_path, segment_path],
             camera_video_path: Some(camera_path),
+            camera_segment_paths: vec![],
             microphone_audio_path: Some(microphone_path),
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
             duration_seconds: 12.3,
             source_width: 1920,
             source_height: 1080,
             camera_offset_ms: Some(10),
             microphone_offset_ms: Some(20),
+            system_audio_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            cursor_log_path: None,
+            fragmented_capture: false,
+            marker_times_ms: Vec::new(),
         };
 
         store_pending_finalization(&pending_finalizations, &stop_result)
@@ -2707,6 +4253,78 @@ mod tests {
         let _ = std::fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn committed_tracks_leave_a_retryable_pending_finalization() {
+        let root = create_test_dir("committed-merged-tracks");
+        let screen_path = root.join("screen.mp4");
+        let segment_path = root.join("screen_part1.mp4");
+        let merged_path = root.join("screen_merged.mp4");
+        let camera_path = root.join("camera.webm");
+        let camera_segment_path = root.join("camera_part1.webm");
+        for path in [
+            &screen_path,
+            &segment_path,
+            &merged_path,
+            &camera_path,
+            &camera_segment_path,
+        ] {
+            std::fs::write(path, b"data").expect("failed to write track fixture");
+        }
+
+        let pending_finalizations: SharedPendingFinalizations =
+            Arc::new(Mutex::new(HashMap::new()));
+        let mut stop_result = StopRecordingResult {
+            project_id: "partly-merged-project".to_string(),
+            screen_video_path: Some(screen_path.clone()),
+            screen_segment_paths: vec![screen_path.clone(), segment_path.clone()],
+            camera_video_path: Some(camera_path.clone()),
+            camera_segment_paths: vec![camera_path.clone(), camera_segment_path.clone()],
+            microphone_audio_path: None,
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
+            duration_seconds: 8.0,
+            source_width: 1920,
+            source_height: 1080,
+            camera_offset_ms: None,
+            microphone_offset_ms: None,
+            system_audio_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            cursor_log_path: None,
+            fragmented_capture: false,
+            marker_times_ms: Vec::new(),
+        };
+
+        // The screen track merged, then the camera join failed before it could commit.
+        let replaced = block_on_io(commit_merged_tracks(
+            &mut stop_result,
+            vec![MergedTrack {
+                merged_path: merged_path.clone(),
+                output_path: screen_path.clone(),
+                track: "screen",
+            }],
+        ))
+        .expect("runtime should start")
+        .expect("merged screen track should commit");
+        for path in &replaced {
+            std::fs::remove_file(path).expect("replaced segment should be removable");
+        }
+        store_pending_finalization(&pending_finalizations, &stop_result)
+            .expect("pending finalization should store");
+        let resolved = get_pending_finalization(&pending_finalizations, &stop_result.project_id)
+            .expect("pending finalization query should succeed");
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(replaced, vec![segment_path]);
+        assert!(!merged_path.exists());
+        let resolved = resolved.expect("partly merged take should stay retryable");
+        assert_eq!(resolved.screen_segment_paths, vec![screen_path]);
+        assert_eq!(
+            resolved.camera_segment_paths,
+            vec![camera_path, camera_segment_path]
+        );
+    }
+
     #[test]
     fn pending_finalization_context_is_cleared_when_artifacts_are_missing() {
         let root = create_test_dir("stale-pending-finalization");
@@ -2718,15 +4336,24 @@ mod tests {
             Arc::new(Mutex::new(HashMap::new()));
         let stop_result = StopRecordingResult {
             project_id: "stale-retry-project".to_string(),
-            screen_video_path: missing_screen_path.clone(),
+            screen_video_path: Some(missing_screen_path.clone()),
             screen_segment_paths: vec![missing_screen_path],
             camera_video_path: None,
+            camera_segment_paths: vec![],
             microphone_audio_path: None,
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
             duration_seconds: 1.0,
             source_width: 1280,
             source_height: 720,
             camera_offset_ms: None,
             microphone_offset_ms: None,
+            system_audio_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            cursor_log_path: None,
+            fragmented_capture: false,
+            marker_times_ms: Vec::new(),
         };
 
         store_pending_finalization(&pending_finalizations, &stop_result)
@@ -2758,15 +4385,24 @@ mod tests {
             Arc::new(Mutex::new(HashMap::new()));
         let stop_result = StopRecordingResult {
             project_id: "stale-segment-project".to_string(),
-            screen_video_path: screen_path,
+            screen_video_path: Some(screen_path),
             screen_segment_paths: vec![missing_segment_path],
             camera_video_path: None,
+            camera_segment_paths: vec![],
             microphone_audio_path: None,
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
             duration_seconds: 1.0,
             source_width: 1280,
             source_height: 720,
             camera_offset_ms: None,
             microphone_offset_ms: None,
+            system_audio_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            cursor_log_path: None,
+            fragmented_capture: false,
+            marker_times_ms: Vec::new(),
         };
 
         store_pending_finalization(&pending_finalizations, &stop_result)
@@ -3409,37 +5045,65 @@ pub fn run() {
                 .map_err(|error| -> Box<dyn std::error::Error> { Box::new(error) })?;
             let recorder_state = Arc::new(Mutex::new(RecorderState::new(app_data_dir.clone())));
             app.manage(recorder_state);
+            #[cfg(target_os = "linux")]
+            recording::prune_orphaned_replay_buffers(&app_data_dir.join("recordings"));
+            // This process records nothing yet, so every project dir without a project file that
+            // another process, like `open-rec record`, has not locked is an orphan.
+            let recoverable_recordings: SharedRecoverableRecordings = Arc::new(Mutex::new(
+                recording::find_recoverable_recordings(&app_data_dir.join("recordings")),
+            ));
+            app.manage(recoverable_recordings);
             let export_jobs: SharedExportJobs = Arc::new(Mutex::new(HashMap::new()));
             app.manage(export_jobs);
-            let pending_finalizations: SharedPendingFinalizations =
-                Arc::new(Mutex::new(HashMap::new()));
+            let pending_finalizations: SharedPendingFinalizations = Arc::new(Mutex::new(
+                recording::load_pending_finalization_journals(&app_data_dir.join("recordings"))
+                    .into_iter()
+                    .map(|stop_result| (stop_result.project_id.clone(), stop_result))
+                    .collect(),
+            ));
             app.manage(pending_finalizations);
 
-            let start_stop_shortcut: Shortcut = START_STOP_SHORTCUT
-                .parse()
-                .map_err(|error| -> Box<dyn std::error::Error> { Box::new(error) })?;
-            let pause_resume_shortcut: Shortcut = PAUSE_RESUME_SHORTCUT
-                .parse()
-                .map_err(|error| -> Box<dyn std::error::Error> { Box::new(error) })?;
-            let start_stop_handler = start_stop_shortcut;
-            let pause_resume_handler = pause_resume_shortcut;
-
-            app.global_shortcut()
-                .on_shortcuts(
-                    [start_stop_shortcut, pause_resume_shortcut],
-                    move |app_handle, shortcut, event| {
-                        if event.state != ShortcutState::Pressed {
-                            return;
-                        }
-
-                        if shortcut == &start_stop_handler {
-                            emit_with_log(app_handle, "global-shortcut-start-stop", ());
-                        } else if shortcut == &pause_resume_handler {
-                            emit_with_log(app_handle, "global-shortcut-toggle-pause", ());
-                        }
-                    },
-                )
-                .map_err(|error| -> Box<dyn std::error::Error> { Box::new(error) })?;
+            // A shortcut the OS refuses is reported through `get_shortcut_bindings` rather
+            // than keeping the app from starting.
+            let shortcut_bindings = load_shortcut_bindings(&shortcut_bindings_path(&app_data_dir));
+            let registration_errors = register_global_shortcuts(app.handle(), &shortcut_bindings);
+            let global_shortcuts: SharedGlobalShortcuts =
+                Arc::new(Mutex::new(GlobalShortcutState {
+                    bindings: shortcut_bindings,
+                    registration_errors,
+                }));
+            app.manage(global_shortcuts);
+
+            let control_events = control_event_channel();
+            for event_name in CONTROL_API_EVENTS {
+                let control_events = control_events.clone();
+                app.listen_any(event_name, move |event| {
+                    // Sending only fails while no script is subscribed.
+                    let _ = control_events.send(ControlEvent {
+                        name: event_name.to_string(),
+                        payload: event.payload().to_string(),
+                    });
+                });
+            }
+            let control_api_path = control_api_settings_path(&app_data_dir);
+            let control_api: SharedControlApi = Arc::new(Mutex::new(ControlApiState {
+                settings: load_control_api_settings(&control_api_path),
+                settings_path: control_api_path,
+                server: None,
+                events: control_events,
+            }));
+            app.manage(control_api);
+            let control_api_app = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = control_api_app.state::<SharedControlApi>();
+                let result = match state.lock() {
+                    Ok(mut state_guard) => {
+                        restart_control_server(&control_api_app, &mut state_guard)
+                    }
+                    Err(error) => Err(AppError::Lock(format!("Lock error: {}", error))),
+                };
+                log_if_err(result, "Failed to start the control API");
+            });
 
             let recordings_dir = app_data_dir.join("recordings");
             let tray_menu = build_tray_menu(app, &recordings_dir)
@@ -3473,12 +5137,7 @@ pub fn run() {
                         emit_with_log(app_handle, "tray-open-projects", ());
                     }
                     TRAY_MENU_QUICK_RECORD => {
-                        show_main_window(app_handle);
-                        let app_handle = app_handle.clone();
-                        tauri::async_runtime::spawn(async move {
-                            tokio::time::sleep(std::time::Duration::from_millis(120)).await;
-                            emit_with_log(&app_handle, "tray-quick-record", ());
-                        });
+                        quick_record_last_settings(app_handle);
                     }
                     TRAY_MENU_START_STOP => {
                         show_main_window(app_handle);
@@ -3492,6 +5151,9 @@ pub fn run() {
                     TRAY_MENU_PAUSE_RESUME => {
                         emit_with_log(app_handle, "global-shortcut-toggle-pause", ());
                     }
+                    TRAY_MENU_ADD_MARKER => {
+                        add_marker_to_active_recording(app_handle);
+                    }
                     TRAY_MENU_QUIT => {
                         app_handle.exit(0);
                     }
@@ -3554,21 +5216,36 @@ pub fn run() {
             stop_screen_recording,
             retry_recording_finalization,
             has_pending_recording_finalization,
+            list_pending_finalizations,
+            list_recoverable_recordings,
+            recover_recording,
+            discard_recoverable_recording,
             set_recording_media_offsets,
             get_recording_state,
             get_recording_snapshot,
             get_recording_source_status,
+            add_recording_marker,
             pause_recording,
             resume_recording,
+            save_replay,
+            stop_replay_buffer,
             open_recording_widget,
             open_project_window,
             load_project,
             save_project,
             list_projects,
             delete_project,
+            suggest_auto_zooms,
             export_project,
             cancel_export,
             list_active_export_jobs,
+            get_shortcut_bindings,
+            validate_shortcut_binding,
+            set_shortcut_binding,
+            reset_shortcut_bindings,
+            get_control_api_status,
+            set_control_api_enabled,
+            regenerate_control_api_token,
         ])
         .build(tauri::generate_context!());
 
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if open_rec_lib::is_cli_invocation(&args) {
+        std::process::exit(open_rec_lib::run_cli(&args));
+    }
     open_rec_lib::run()
 }
@@ -0,0 +1,193 @@
+use super::{Project, ZoomEasing, ZoomEffect};
+use crate::recording::{CursorEvent, CursorEventKind};
+
+/// Clicks closer together than this in time can share one zoom.
+const CLICK_CLUSTER_GAP_SECONDS: f64 = 2.0;
+/// Clicks further apart than this (in capture-relative units) start a new zoom.
+const CLICK_CLUSTER_RADIUS: f64 = 0.2;
+const SUGGESTED_ZOOM_LEAD_SECONDS: f64 = 0.8;
+const SUGGESTED_ZOOM_HOLD_SECONDS: f64 = 1.6;
+const SUGGESTED_ZOOM_MIN_SECONDS: f64 = 1.0;
+const SUGGESTED_ZOOM_SCALE: f64 = 1.5;
+const SUGGESTED_ZOOM_EASE_SECONDS: f64 = 0.4;
+
+struct ClickCluster {
+    start: f64,
+    end: f64,
+    sum_x: f64,
+    sum_y: f64,
+    clicks: usize,
+}
+
+impl ClickCluster {
+    fn centre(&self) -> (f64, f64) {
+        (
+            self.sum_x / self.clicks as f64,
+            self.sum_y / self.clicks as f64,
+        )
+    }
+
+    fn accepts(&self, time: f64, x: f64, y: f64) -> bool {
+        let (centre_x, centre_y) = self.centre();
+        time - self.end <= CLICK_CLUSTER_GAP_SECONDS
+            && (x - centre_x).hypot(y - centre_y) <= CLICK_CLUSTER_RADIUS
+    }
+}
+
+fn cluster_clicks(events: &[CursorEvent]) -> Vec<ClickCluster> {
+    let mut clicks = events
+        .iter()
+        .filter(|event| event.kind == CursorEventKind::Down && event.is_inside_capture())
+        .collect::<Vec<_>>();
+    clicks.sort_by_key(|event| event.time_ms);
+
+    let mut clusters: Vec<ClickCluster> = Vec::new();
+    for click in clicks {
+        let time = click.time_ms as f64 / 1000.0;
+        match clusters.last_mut() {
+            Some(cluster) if cluster.accepts(time, click.x, click.y) => {
+                cluster.end = time;
+                cluster.sum_x += click.x;
+                cluster.sum_y += click.y;
+                cluster.clicks += 1;
+            }
+            _ => clusters.push(ClickCluster {
+                start: time,
+                end: time,
+                sum_x: click.x,
+                sum_y: click.y,
+                clicks: 1,
+            }),
+        }
+    }
+    clusters
+}
+
+/// Offset from the frame centre, in project pixels, that keeps `centre` in view at `scale`.
+fn zoom_offset(centre: f64, dimension: u32, scale: f64) -> f64 {
+    let max_offset = dimension as f64 / 2.0 * (1.0 - 1.0 / scale);
+    ((centre - 0.5) * dimension as f64).clamp(-max_offset, max_offset)
+}
+
+/// Suggest zoom effects centred on clusters of clicks from the recording's cursor log.
+///
+/// Suggestions never overlap each other or the project's existing zooms, so they can be
+/// appended to the edit decision list as-is.
+pub fn suggest_zoom_effects(project: &Project, events: &[CursorEvent]) -> Vec<ZoomEffect> {
+    let mut taken = project
+        .edits
+        .zoom
+        .iter()
+        .map(|zoom| (zoom.start_time, zoom.end_time))
+        .collect::<Vec<_>>();
+    let mut suggestions = Vec::new();
+
+    for cluster in cluster_clicks(events) {
+        let mut start_time = (cluster.start - SUGGESTED_ZOOM_LEAD_SECONDS).max(0.0);
+        let mut end_time = (cluster.end + SUGGESTED_ZOOM_HOLD_SECONDS).min(project.duration);
+        for (taken_start, taken_end) in &taken {
+            if *taken_start <= cluster.start && cluster.start < *taken_end {
+                start_time = end_time;
+                break;
+            }
+            if *taken_end <= cluster.start {
+                start_time = start_time.max(*taken_end);
+            } else {
+                end_time = end_time.min(*taken_start);
+            }
+        }
+        if end_time - start_time < SUGGESTED_ZOOM_MIN_SECONDS {
+            continue;
+        }
+
+        let (centre_x, centre_y) = cluster.centre();
+        suggestions.push(ZoomEffect {
+            id: uuid::Uuid::new_v4().to_string(),
+            start_time,
+            end_time,
+            scale: SUGGESTED_ZOOM_SCALE,
+            x: zoom_offset(centre_x, project.resolution.width, SUGGESTED_ZOOM_SCALE),
+            y: zoom_offset(centre_y, project.resolution.height, SUGGESTED_ZOOM_SCALE),
+            ease_in: SUGGESTED_ZOOM_EASE_SECONDS,
+            ease_out: SUGGESTED_ZOOM_EASE_SECONDS,
+            easing: ZoomEasing::EaseInOut,
+        });
+        taken.push((start_time, end_time));
+    }
+
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn click(time_ms: u64, x: f64, y: f64) -> CursorEvent {
+        CursorEvent {
+            time_ms,
+            kind: CursorEventKind::Down,
+            x,
+            y,
+            button: Some(1),
+        }
+    }
+
+    fn build_test_project() -> Project {
+        Project::new(
+            "auto-zoom-project".to_string(),
+            Some(PathBuf::from("/tmp/auto-zoom/screen.mp4")),
+            None,
+            None,
+            30.0,
+            1920,
+            1080,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn suggests_one_zoom_per_click_cluster() {
+        let project = build_test_project();
+        let events = vec![
+            click(5_000, 0.75, 0.4),
+            click(6_000, 0.77, 0.42),
+            click(15_000, 0.2, 0.8),
+            click(15_500, -0.1, 0.5),
+        ];
+
+        let suggestions = suggest_zoom_effects(&project, &events);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].start_time, 4.2);
+        assert_eq!(suggestions[0].end_time, 7.6);
+        assert!((suggestions[0].x - 320.0).abs() < 0.001);
+        assert!((suggestions[0].y - -97.2).abs() < 0.001);
+        assert_eq!(suggestions[1].start_time, 14.2);
+        assert_eq!(suggestions[0].easing, ZoomEasing::EaseInOut);
+    }
+
+    #[test]
+    fn suggestions_avoid_existing_zooms() {
+        let mut project = build_test_project();
+        project.edits.zoom.push(ZoomEffect {
+            id: "manual".to_string(),
+            start_time: 3.0,
+            end_time: 5.5,
+            scale: 2.0,
+            x: 0.0,
+            y: 0.0,
+            ease_in: 0.0,
+            ease_out: 0.0,
+            easing: ZoomEasing::Linear,
+        });
+        let events = vec![click(4_000, 0.5, 0.5), click(6_000, 0.1, 0.1)];
+
+        let suggestions = suggest_zoom_effects(&project, &events);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].start_time, 5.5);
+        assert_eq!(suggestions[0].end_time, 7.6);
+    }
+}
@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use crate::error::AppError;
+
+mod auto_zoom;
+
+pub use auto_zoom::suggest_zoom_effects;
+
 const PROJECT_ASSOCIATION_EXTENSION: &str = "openrec";
 
 /// Project metadata and edit decision list
@@ -12,16 +17,40 @@ pub struct Project {
     pub id: String,
     pub name: String,
     pub created_at: DateTime<Utc>,
-    pub screen_video_path: String,
+    /// Missing for camera-only and audio-only takes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screen_video_path: Option<String>,
     pub camera_video_path: Option<String>,
     pub microphone_audio_path: Option<String>,
     pub camera_offset_ms: Option<i64>,
     pub microphone_offset_ms: Option<i64>,
+    /// Lossless system audio track; older projects carry system audio inside the screen file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_audio_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_audio_offset_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor_log_path: Option<String>,
+    /// Screen segments re-encoded onto a common canvas before they were joined.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub normalized_screen_segments: Vec<NormalizedSegment>,
     pub duration: f64,
     pub resolution: Resolution,
     pub edits: EditDecisionList,
 }
 
+/// Original stream parameters of a segment that did not match the project canvas.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedSegment {
+    /// Position of the segment in recording order.
+    pub index: usize,
+    pub width: u32,
+    pub height: u32,
+    pub frame_rate: String,
+    pub codec: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Resolution {
@@ -43,6 +72,14 @@ pub struct EditDecisionList {
     pub audio_mix: AudioMixSettings,
     #[serde(default)]
     pub color_correction: ColorCorrectionSettings,
+    #[serde(default)]
+    pub cursor_effects: CursorEffectsSettings,
+    /// Bookmarks dropped while recording.
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    /// Named chapters written into exports.
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +92,9 @@ pub struct CameraOverlaySettings {
     pub custom_x: f64,
     #[serde(default = "default_camera_overlay_custom_y")]
     pub custom_y: f64,
+    /// Punch into the camera while a screen zoom is active; off keeps it unzoomed.
+    #[serde(default = "default_camera_overlay_follow_zoom")]
+    pub follow_zoom: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +119,34 @@ pub struct ColorCorrectionSettings {
     pub saturation: f64,
 }
 
+/// Cursor overlays drawn at export from the recording's cursor log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorEffectsSettings {
+    #[serde(default)]
+    pub highlight: CursorHighlightStyle,
+    #[serde(default)]
+    pub click_ripples: bool,
+    /// Halo diameter or enlarged cursor height, in source pixels.
+    #[serde(default = "default_cursor_effects_size")]
+    pub size: u32,
+    /// Fill colour of the halo and click ripples.
+    #[serde(default = "default_cursor_effects_color")]
+    pub color: String,
+    /// Halo opacity; ripples and the enlarged cursor are always drawn opaque.
+    #[serde(default = "default_cursor_effects_opacity")]
+    pub opacity: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CursorHighlightStyle {
+    #[default]
+    None,
+    Halo,
+    Enlarged,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum CameraOverlayPosition {
@@ -97,6 +165,22 @@ fn default_camera_overlay_custom_y() -> f64 {
     1.0
 }
 
+fn default_camera_overlay_follow_zoom() -> bool {
+    false
+}
+
+fn default_cursor_effects_size() -> u32 {
+    48
+}
+
+fn default_cursor_effects_color() -> String {
+    "#ffd60a".to_string()
+}
+
+fn default_cursor_effects_opacity() -> f64 {
+    0.35
+}
+
 fn default_system_volume() -> f64 {
     1.0
 }
@@ -129,6 +213,7 @@ impl Default for CameraOverlaySettings {
             scale: 0.25,
             custom_x: default_camera_overlay_custom_x(),
             custom_y: default_camera_overlay_custom_y(),
+            follow_zoom: default_camera_overlay_follow_zoom(),
         }
     }
 }
@@ -143,6 +228,34 @@ impl Default for AudioMixSettings {
     }
 }
 
+impl Default for CursorEffectsSettings {
+    fn default() -> Self {
+        Self {
+            highlight: CursorHighlightStyle::default(),
+            click_ripples: false,
+            size: default_cursor_effects_size(),
+            color: default_cursor_effects_color(),
+            opacity: default_cursor_effects_opacity(),
+        }
+    }
+}
+
+impl CursorEffectsSettings {
+    /// The colour goes into the export filter graph, so only `#RRGGBB` is accepted.
+    pub fn validate(&self) -> Result<(), AppError> {
+        let is_hex_color = self.color.len() == 7
+            && self.color.starts_with('#')
+            && self.color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !is_hex_color {
+            return Err(AppError::Message(format!(
+                "Cursor effect colour must look like #RRGGBB, got \"{}\"",
+                self.color
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl Default for ColorCorrectionSettings {
     fn default() -> Self {
         Self {
@@ -171,6 +284,24 @@ pub struct ZoomEffect {
     pub scale: f64,
     pub x: f64,
     pub y: f64,
+    /// Seconds spent zooming in from the start of the effect; zero snaps in.
+    #[serde(default)]
+    pub ease_in: f64,
+    /// Seconds spent zooming back out before the end of the effect; zero snaps out.
+    #[serde(default)]
+    pub ease_out: f64,
+    #[serde(default)]
+    pub easing: ZoomEasing,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZoomEasing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -201,6 +332,25 @@ pub struct Annotation {
     pub mode: AnnotationMode,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Marker {
+    pub id: String,
+    /// Seconds into the recording.
+    pub time: f64,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Chapter {
+    pub id: String,
+    pub title: String,
+    /// Seconds into the recording; exports move it onto the edited timeline.
+    pub start_time: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum AnnotationMode {
@@ -220,7 +370,7 @@ impl Project {
     /// Create a new project from recording paths
     pub fn new(
         id: String,
-        screen_video_path: PathBuf,
+        screen_video_path: Option<PathBuf>,
         camera_video_path: Option<PathBuf>,
         microphone_audio_path: Option<PathBuf>,
         duration: f64,
@@ -234,11 +384,15 @@ impl Project {
             id: id.clone(),
             name: format!("Recording {}", &id[..8]),
             created_at: Utc::now(),
-            screen_video_path: screen_video_path.to_string_lossy().to_string(),
+            screen_video_path: screen_video_path.map(|p| p.to_string_lossy().to_string()),
             camera_video_path: camera_video_path.map(|p| p.to_string_lossy().to_string()),
             microphone_audio_path: microphone_audio_path.map(|p| p.to_string_lossy().to_string()),
             camera_offset_ms,
             microphone_offset_ms,
+            system_audio_path: None,
+            system_audio_offset_ms: None,
+            cursor_log_path: None,
+            normalized_screen_segments: vec![],
             duration,
             resolution: Resolution { width, height },
             edits: EditDecisionList {
@@ -254,6 +408,9 @@ impl Project {
                 camera_overlay: CameraOverlaySettings::default(),
                 audio_mix: AudioMixSettings::default(),
                 color_correction: ColorCorrectionSettings::default(),
+                cursor_effects: CursorEffectsSettings::default(),
+                markers: vec![],
+                chapters: vec![],
             },
         }
     }
@@ -290,6 +447,7 @@ pub async fn load_project(recordings_dir: &PathBuf, project_id: &str) -> Result<
 
 /// Save project
 pub async fn save_project(recordings_dir: &PathBuf, project: &Project) -> Result<(), AppError> {
+    project.edits.cursor_effects.validate()?;
     let project_dir = recordings_dir.join(&project.id);
     tokio::fs::create_dir_all(&project_dir)
         .await
@@ -367,8 +525,25 @@ pub async fn list_projects(recordings_dir: &PathBuf) -> Result<Vec<Project>, App
     Ok(projects)
 }
 
+/// Reject ids that do not name a single entry directly inside the recordings directory.
+pub fn validate_project_id(project_id: &str) -> Result<(), AppError> {
+    let mut components = Path::new(project_id).components();
+    let is_single_name = matches!(
+        components.next(),
+        Some(Component::Normal(name)) if name == project_id
+    ) && components.next().is_none();
+    if !is_single_name || project_id.contains(['/', '\\']) {
+        return Err(AppError::Message(format!(
+            "Invalid project id \"{}\"",
+            project_id
+        )));
+    }
+    Ok(())
+}
+
 /// Delete project by ID (directory + files)
 pub async fn delete_project(recordings_dir: &PathBuf, project_id: &str) -> Result<(), AppError> {
+    validate_project_id(project_id)?;
     let project_dir = recordings_dir.join(project_id);
     if tokio::fs::metadata(&project_dir).await.is_ok() {
         tokio::fs::remove_dir_all(&project_dir)
@@ -393,7 +568,10 @@ pub async fn delete_project(recordings_dir: &PathBuf, project_id: &str) -> Resul
 
 #[cfg(test)]
 mod tests {
-    use super::{delete_project, save_project, Project, PROJECT_ASSOCIATION_EXTENSION};
+    use super::{
+        delete_project, save_project, validate_project_id, CursorEffectsSettings, Project,
+        ZoomEasing, ZoomEffect, PROJECT_ASSOCIATION_EXTENSION,
+    };
     use serde_json::Value;
     use std::path::PathBuf;
     use uuid::Uuid;
@@ -408,7 +586,7 @@ mod tests {
         let screen_video_path = recordings_dir.join(project_id).join("screen.mp4");
         Project::new(
             project_id.to_string(),
-            screen_video_path,
+            Some(screen_video_path),
             None,
             None,
             12.5,
@@ -550,4 +728,56 @@ mod tests {
 
         let _ = tokio::fs::remove_dir_all(&recordings_dir).await;
     }
+
+    #[tokio::test]
+    async fn delete_project_refuses_ids_outside_the_recordings_dir() {
+        let recordings_dir = create_test_recordings_dir("delete-guard");
+
+        for project_id in ["", ".", "..", "../other", "a/b", "a\\b", "/tmp", "project/"] {
+            assert!(
+                validate_project_id(project_id).is_err(),
+                "{project_id:?} should be rejected"
+            );
+            assert!(delete_project(&recordings_dir, project_id).await.is_err());
+        }
+        assert!(validate_project_id("project-1.take").is_ok());
+        let recordings_dir_kept = recordings_dir.exists();
+        let _ = tokio::fs::remove_dir_all(&recordings_dir).await;
+
+        assert!(recordings_dir_kept);
+    }
+
+    #[test]
+    fn zoom_effects_without_easing_fields_snap_in_and_out() {
+        let zoom: ZoomEffect = serde_json::from_str(
+            r#"{"id":"zoom","startTime":1.0,"endTime":3.0,"scale":1.5,"x":0.0,"y":0.0}"#,
+        )
+        .expect("legacy zoom effect should deserialize");
+
+        assert_eq!(zoom.ease_in, 0.0);
+        assert_eq!(zoom.ease_out, 0.0);
+        assert_eq!(zoom.easing, ZoomEasing::EaseInOut);
+
+        let eased: ZoomEffect = serde_json::from_str(
+            r#"{"id":"zoom","startTime":1.0,"endTime":3.0,"scale":1.5,"x":0.0,"y":0.0,"easeIn":0.4,"easeOut":0.6,"easing":"ease-out"}"#,
+        )
+        .expect("eased zoom effect should deserialize");
+        assert_eq!(eased.ease_in, 0.4);
+        assert_eq!(eased.ease_out, 0.6);
+        assert_eq!(eased.easing, ZoomEasing::EaseOut);
+    }
+
+    #[test]
+    fn cursor_effect_colours_must_be_hex_triplets() {
+        let settings = |color: &str| CursorEffectsSettings {
+            color: color.to_string(),
+            ..CursorEffectsSettings::default()
+        };
+
+        assert!(settings("#FFD60A").validate().is_ok());
+        assert!(settings("#ffd60a").validate().is_ok());
+        for color in ["red", "#ffd60", "#ffd60a:s=1x1", "#ffd6,a", "#ffd6;a"] {
+            assert!(settings(color).validate().is_err(), "{color}");
+        }
+    }
 }
@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+#[cfg(target_os = "linux")]
+use {
+    std::io::{BufWriter, Write},
+    std::sync::atomic::{AtomicBool, Ordering},
+    std::sync::Arc,
+    std::thread,
+    std::time::{Duration, Instant},
+    x11rb::connection::Connection,
+    x11rb::protocol::xproto::{ConnectionExt, KeyButMask},
+};
+
+pub const CURSOR_LOG_FILE_NAME: &str = "cursor.jsonl";
+
+#[cfg(target_os = "linux")]
+const CURSOR_POLL_INTERVAL: Duration = Duration::from_millis(16);
+#[cfg(target_os = "linux")]
+const CURSOR_MOVE_SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+#[cfg(target_os = "linux")]
+const CURSOR_LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorEventKind {
+    Move,
+    Down,
+    Up,
+}
+
+/// A cursor sample timed on the recording's active-duration clock.
+///
+/// Positions are relative to the captured area, so `0..=1` on both axes is on screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorEvent {
+    pub time_ms: u64,
+    pub kind: CursorEventKind,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub button: Option<u8>,
+}
+
+impl CursorEvent {
+    pub fn is_inside_capture(&self) -> bool {
+        (0.0..=1.0).contains(&self.x) && (0.0..=1.0).contains(&self.y)
+    }
+}
+
+pub fn cursor_log_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(CURSOR_LOG_FILE_NAME)
+}
+
+fn parse_cursor_log(raw: &str) -> Vec<CursorEvent> {
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<CursorEvent>(line).ok())
+        .collect()
+}
+
+/// Load a cursor log, skipping lines that were cut short when the recorder stopped.
+pub async fn load_cursor_events(path: &Path) -> Result<Vec<CursorEvent>, AppError> {
+    let raw = tokio::fs::read_to_string(path).await.map_err(|error| {
+        AppError::Io(format!(
+            "Failed to read cursor log {}: {}",
+            path.display(),
+            error
+        ))
+    })?;
+    Ok(parse_cursor_log(&raw))
+}
+
+/// Blocking variant of [`load_cursor_events`] for building export arguments.
+pub fn read_cursor_events(path: &Path) -> Result<Vec<CursorEvent>, AppError> {
+    let raw = std::fs::read_to_string(path).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to read cursor log {}: {}",
+            path.display(),
+            error
+        ))
+    })?;
+    Ok(parse_cursor_log(&raw))
+}
+
+/// Screen area the cursor is reported against.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CursorCaptureArea {
+    pub window_id: Option<u32>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Background X11 poller that appends cursor moves and clicks to the project's cursor log.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct CursorTelemetryRecorder {
+    stop_requested: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+#[cfg(target_os = "linux")]
+impl CursorTelemetryRecorder {
+    /// Start polling the pointer; `base_ms` is the active duration already recorded.
+    pub(super) fn start(
+        log_path: &Path,
+        area: CursorCaptureArea,
+        base_ms: u64,
+    ) -> Result<Self, AppError> {
+        let (connection, screen_index) = x11rb::connect(None).map_err(|error| {
+            AppError::Message(format!(
+                "Failed to connect to X11 for cursor telemetry: {}",
+                error
+            ))
+        })?;
+        let root = connection.setup().roots[screen_index].root;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)
+            .map_err(|error| {
+                AppError::Io(format!(
+                    "Failed to open cursor log {}: {}",
+                    log_path.display(),
+                    error
+                ))
+            })?;
+
+        let stop_requested = Arc::new(AtomicBool::new(false));
+        let thread_stop_requested = stop_requested.clone();
+        let handle = thread::Builder::new()
+            .name("cursor-telemetry".to_string())
+            .spawn(move || {
+                let mut writer = BufWriter::new(file);
+                let started = Instant::now();
+                let mut last_flush = Instant::now();
+                let mut last_move: Option<(Instant, i32, i32)> = None;
+                let mut pressed_buttons = [false; 3];
+                let query_window = area.window_id.unwrap_or(root);
+
+                while !thread_stop_requested.load(Ordering::Relaxed) {
+                    let reply = match connection
+                        .query_pointer(query_window)
+                        .map_err(|error| error.to_string())
+                        .and_then(|cookie| cookie.reply().map_err(|error| error.to_string()))
+                    {
+                        Ok(reply) => reply,
+                        Err(error) => {
+                            eprintln!("Cursor telemetry stopped: {}", error);
+                            break;
+                        }
+                    };
+                    let (pointer_x, pointer_y) = if area.window_id.is_some() {
+                        (i32::from(reply.win_x), i32::from(reply.win_y))
+                    } else {
+                        (
+                            i32::from(reply.root_x) - area.x,
+                            i32::from(reply.root_y) - area.y,
+                        )
+                    };
+                    let time_ms = base_ms + started.elapsed().as_millis() as u64;
+                    let event = |kind: CursorEventKind, button: Option<u8>| CursorEvent {
+                        time_ms,
+                        kind,
+                        x: pointer_x as f64 / area.width.max(1) as f64,
+                        y: pointer_y as f64 / area.height.max(1) as f64,
+                        button,
+                    };
+
+                    let mut events = Vec::new();
+                    let moved = last_move
+                        .map(|(_, x, y)| x != pointer_x || y != pointer_y)
+                        .unwrap_or(true);
+                    let move_due = last_move
+                        .map(|(at, _, _)| at.elapsed() >= CURSOR_MOVE_SAMPLE_INTERVAL)
+                        .unwrap_or(true);
+                    if moved && move_due {
+                        events.push(event(CursorEventKind::Move, None));
+                        last_move = Some((Instant::now(), pointer_x, pointer_y));
+                    }
+                    for (index, mask) in [
+                        KeyButMask::BUTTON1,
+                        KeyButMask::BUTTON2,
+                        KeyButMask::BUTTON3,
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    {
+                        let pressed = reply.mask.contains(mask);
+                        if pressed != pressed_buttons[index] {
+                            pressed_buttons[index] = pressed;
+                            let kind = if pressed {
+                                CursorEventKind::Down
+                            } else {
+                                CursorEventKind::Up
+                            };
+                            events.push(event(kind, Some(index as u8 + 1)));
+                        }
+                    }
+
+                    for event in events {
+                        if let Ok(line) = serde_json::to_string(&event) {
+                            let _ = writeln!(writer, "{}", line);
+                        }
+                    }
+                    if last_flush.elapsed() >= CURSOR_LOG_FLUSH_INTERVAL {
+                        let _ = writer.flush();
+                        last_flush = Instant::now();
+                    }
+                    thread::sleep(CURSOR_POLL_INTERVAL);
+                }
+
+                let _ = writer.flush();
+            })
+            .map_err(|error| {
+                AppError::Io(format!(
+                    "Failed to spawn cursor telemetry thread: {}",
+                    error
+                ))
+            })?;
+
+        Ok(Self {
+            stop_requested,
+            handle: Some(handle),
+        })
+    }
+
+    pub(super) fn stop(mut self) {
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                eprintln!("Cursor telemetry thread panicked while stopping");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cursor_log_and_skips_truncated_lines() {
+        let raw = concat!(
+            "{\"timeMs\":0,\"kind\":\"move\",\"x\":0.5,\"y\":0.25}\n",
+            "{\"timeMs\":120,\"kind\":\"down\",\"x\":0.5,\"y\":0.25,\"button\":1}\n",
+            "\n",
+            "{\"timeMs\":180,\"kind\":\"up\",\"x\":0.5,\"y\""
+        );
+
+        let events = parse_cursor_log(raw);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].kind, CursorEventKind::Down);
+        assert_eq!(events[1].button, Some(1));
+        assert!(events[1].is_inside_capture());
+    }
+}
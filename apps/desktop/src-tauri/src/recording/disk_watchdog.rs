@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+pub const DEFAULT_MIN_RECORDING_FREE_SPACE_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+pub const DEFAULT_RECORDING_FREE_SPACE_FLOOR_BYTES: u64 = 1024 * 1024 * 1024;
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+const GROWTH_WINDOW: Duration = Duration::from_secs(30);
+/// Warn once the take would reach the floor within this long at its current growth rate.
+const LOW_SPACE_LEAD_TIME: Duration = Duration::from_secs(5 * 60);
+
+const fn default_min_free_bytes() -> u64 {
+    DEFAULT_MIN_RECORDING_FREE_SPACE_BYTES
+}
+
+const fn default_floor_bytes() -> u64 {
+    DEFAULT_RECORDING_FREE_SPACE_FLOOR_BYTES
+}
+
+/// What to do with a take when free space reaches the floor.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LowDiskSpaceAction {
+    #[default]
+    Pause,
+    Stop,
+}
+
+/// Free-space limits for a recording.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpacePolicy {
+    /// Free space required to start; the watchdog warns while below it.
+    #[serde(default = "default_min_free_bytes")]
+    pub min_free_bytes: u64,
+    /// Free space at which the watchdog applies `action`.
+    #[serde(default = "default_floor_bytes")]
+    pub floor_bytes: u64,
+    #[serde(default)]
+    pub action: LowDiskSpaceAction,
+}
+
+impl Default for DiskSpacePolicy {
+    fn default() -> Self {
+        Self {
+            min_free_bytes: DEFAULT_MIN_RECORDING_FREE_SPACE_BYTES,
+            floor_bytes: DEFAULT_RECORDING_FREE_SPACE_FLOOR_BYTES,
+            action: LowDiskSpaceAction::default(),
+        }
+    }
+}
+
+impl DiskSpacePolicy {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.floor_bytes >= self.min_free_bytes {
+            return Err(AppError::Message(
+                "The disk space floor must be lower than the free space required to start recording"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiskSpaceLevel {
+    Ok,
+    Low,
+    Critical,
+}
+
+/// Emitted by the watchdog whenever a take's disk space level changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceReport {
+    pub project_id: String,
+    pub level: DiskSpaceLevel,
+    pub free_bytes: u64,
+    pub floor_bytes: u64,
+    pub bytes_per_second: f64,
+    /// Seconds until the floor at the current growth rate, if the take is growing.
+    pub seconds_remaining: Option<f64>,
+    /// Set when the level is critical and the take must be paused or stopped.
+    pub action: Option<LowDiskSpaceAction>,
+}
+
+fn classify_disk_space(
+    free_bytes: u64,
+    bytes_per_second: f64,
+    policy: &DiskSpacePolicy,
+) -> (DiskSpaceLevel, Option<f64>) {
+    if free_bytes <= policy.floor_bytes {
+        return (DiskSpaceLevel::Critical, Some(0.0));
+    }
+    let seconds_remaining = (bytes_per_second > 0.0)
+        .then(|| (free_bytes - policy.floor_bytes) as f64 / bytes_per_second);
+    let low = free_bytes < policy.min_free_bytes
+        || seconds_remaining.is_some_and(|seconds| seconds < LOW_SPACE_LEAD_TIME.as_secs_f64());
+    let level = if low {
+        DiskSpaceLevel::Low
+    } else {
+        DiskSpaceLevel::Ok
+    };
+    (level, seconds_remaining)
+}
+
+/// Refuse to go on recording while free space is still at the floor.
+fn ensure_free_space_above_floor(
+    free_bytes: u64,
+    policy: &DiskSpacePolicy,
+) -> Result<(), AppError> {
+    if classify_disk_space(free_bytes, 0.0, policy).0 == DiskSpaceLevel::Critical {
+        return Err(AppError::Message(format!(
+            "Not enough disk space to resume recording. Free up space above {:.2} GB first (currently {:.2} GB).",
+            policy.floor_bytes as f64 / (1024.0 * 1024.0 * 1024.0),
+            free_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+        )));
+    }
+    Ok(())
+}
+
+/// Check `dir` has room above the policy's floor, e.g. before resuming a take paused for space.
+pub fn ensure_disk_space_above_floor(dir: &Path, policy: &DiskSpacePolicy) -> Result<(), AppError> {
+    let free_bytes = fs2::available_space(dir).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to check free disk space for {}: {}",
+            dir.display(),
+            error
+        ))
+    })?;
+    ensure_free_space_above_floor(free_bytes, policy)
+}
+
+/// Output growth over a sliding window of directory size samples.
+#[derive(Debug, Default)]
+struct GrowthRate {
+    samples: VecDeque<(Duration, u64)>,
+}
+
+impl GrowthRate {
+    /// Record the output size at `at` since the watchdog started; returns bytes per second.
+    fn record(&mut self, at: Duration, size_bytes: u64) -> f64 {
+        self.samples.push_back((at, size_bytes));
+        while self.samples.len() > 2
+            && self
+                .samples
+                .front()
+                .is_some_and(|(sampled_at, _)| at.saturating_sub(*sampled_at) > GROWTH_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+        let Some(&(first_at, first_size)) = self.samples.front() else {
+            return 0.0;
+        };
+        let elapsed = at.saturating_sub(first_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        size_bytes.saturating_sub(first_size) as f64 / elapsed
+    }
+}
+
+fn directory_size_bytes(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Background thread that samples free space and output growth for one recording.
+#[derive(Debug)]
+pub struct DiskSpaceWatchdog {
+    stop_requested: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DiskSpaceWatchdog {
+    /// Start watching `project_dir`; `on_report` runs on the watchdog thread when the level changes.
+    pub fn start(
+        project_id: String,
+        project_dir: PathBuf,
+        policy: DiskSpacePolicy,
+        on_report: impl Fn(DiskSpaceReport) + Send + 'static,
+    ) -> Self {
+        let stop_requested = Arc::new(AtomicBool::new(false));
+        let thread_stop_requested = Arc::clone(&stop_requested);
+        let handle = thread::spawn(move || {
+            let started = Instant::now();
+            let mut growth = GrowthRate::default();
+            let mut last_level = DiskSpaceLevel::Ok;
+            let mut space_check_failed = false;
+            let mut next_sample = started;
+            while !thread_stop_requested.load(Ordering::Relaxed) {
+                if Instant::now() >= next_sample {
+                    next_sample += SAMPLE_INTERVAL;
+                    let bytes_per_second =
+                        growth.record(started.elapsed(), directory_size_bytes(&project_dir));
+                    match fs2::available_space(&project_dir) {
+                        Ok(free_bytes) => {
+                            space_check_failed = false;
+                            let (level, seconds_remaining) =
+                                classify_disk_space(free_bytes, bytes_per_second, &policy);
+                            if level != last_level {
+                                last_level = level;
+                                on_report(DiskSpaceReport {
+                                    project_id: project_id.clone(),
+                                    level,
+                                    free_bytes,
+                                    floor_bytes: policy.floor_bytes,
+                                    bytes_per_second,
+                                    seconds_remaining,
+                                    action: (level == DiskSpaceLevel::Critical)
+                                        .then_some(policy.action),
+                                });
+                            }
+                        }
+                        Err(error) if !space_check_failed => {
+                            space_check_failed = true;
+                            eprintln!(
+                                "Failed to check free disk space for recording {}: {}",
+                                project_id, error
+                            );
+                        }
+                        Err(_) => {}
+                    }
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        Self {
+            stop_requested,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn stop(mut self) {
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                eprintln!("Disk space watchdog thread panicked while stopping");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn classifies_free_space_against_the_floor_and_growth_rate() {
+        let policy = DiskSpacePolicy::default();
+
+        assert_eq!(
+            classify_disk_space(20 * GIB, 0.0, &policy),
+            (DiskSpaceLevel::Ok, None)
+        );
+        assert_eq!(
+            classify_disk_space(4 * GIB, 0.0, &policy).0,
+            DiskSpaceLevel::Low
+        );
+        // 19 GiB above the floor at 100 MiB/s leaves a little over three minutes.
+        let (level, seconds_remaining) =
+            classify_disk_space(20 * GIB, 100.0 * 1024.0 * 1024.0, &policy);
+        assert_eq!(level, DiskSpaceLevel::Low);
+        assert_eq!(seconds_remaining.map(f64::round), Some(195.0));
+        assert_eq!(
+            classify_disk_space(GIB, 0.0, &policy),
+            (DiskSpaceLevel::Critical, Some(0.0))
+        );
+    }
+
+    #[test]
+    fn refuses_to_resume_a_take_paused_while_space_is_still_critical() {
+        let policy = DiskSpacePolicy::default();
+        let (level, _) = classify_disk_space(GIB / 2, 0.0, &policy);
+        assert_eq!(level, DiskSpaceLevel::Critical);
+        assert_eq!(policy.action, LowDiskSpaceAction::Pause);
+
+        // Nothing was freed while paused, so resuming would land straight back on the floor.
+        assert!(ensure_free_space_above_floor(GIB / 2, &policy).is_err());
+        assert!(ensure_free_space_above_floor(GIB, &policy).is_err());
+        assert!(ensure_free_space_above_floor(2 * GIB, &policy).is_ok());
+    }
+
+    #[test]
+    fn measures_output_growth_over_a_sliding_window() {
+        let mut growth = GrowthRate::default();
+
+        assert_eq!(growth.record(Duration::from_secs(0), 0), 0.0);
+        assert_eq!(growth.record(Duration::from_secs(10), 1_000), 100.0);
+        assert_eq!(growth.record(Duration::from_secs(40), 31_000), 1_000.0);
+        // A pruned replay buffer shrinks instead of growing.
+        assert_eq!(growth.record(Duration::from_secs(42), 500), 0.0);
+    }
+
+    #[test]
+    fn rejects_a_floor_above_the_start_requirement() {
+        assert!(DiskSpacePolicy::default().validate().is_ok());
+        let policy: DiskSpacePolicy =
+            serde_json::from_str(r#"{"minFreeBytes": 1000, "floorBytes": 1000}"#).unwrap();
+        assert_eq!(policy.action, LowDiskSpaceAction::Pause);
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use {
+    std::io::{BufRead, BufReader, Read},
+    std::sync::{Arc, Mutex},
+    std::thread,
+};
+
+/// Encoding speeds below this fraction of real time mean the capture is falling behind.
+#[cfg(any(target_os = "linux", test))]
+const MIN_HEALTHY_SPEED: f64 = 0.95;
+
+/// Encoder statistics for the capture segment currently being written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingHealth {
+    pub frames: u64,
+    pub fps: f64,
+    pub dropped_frames: u64,
+    pub duplicated_frames: u64,
+    /// Encoding speed relative to real time.
+    pub speed: Option<f64>,
+    pub bitrate_kbps: Option<f64>,
+    /// The encoder is slower than real time or dropped frames since the last report.
+    pub falling_behind: bool,
+}
+
+/// Accumulates ffmpeg `-progress` key/value lines into one report per block.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Default)]
+struct ProgressParser {
+    current: RecordingHealth,
+    last_dropped_frames: u64,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl ProgressParser {
+    /// Feed one line; returns the finished report at each `progress=` line.
+    fn push_line(&mut self, line: &str) -> Option<RecordingHealth> {
+        let (key, value) = line.trim().split_once('=')?;
+        let value = value.trim();
+        match key {
+            "frame" => self.current.frames = value.parse().unwrap_or(self.current.frames),
+            "fps" => self.current.fps = value.parse().unwrap_or(self.current.fps),
+            "drop_frames" => {
+                self.current.dropped_frames = value.parse().unwrap_or(self.current.dropped_frames)
+            }
+            "dup_frames" => {
+                self.current.duplicated_frames =
+                    value.parse().unwrap_or(self.current.duplicated_frames)
+            }
+            "speed" => self.current.speed = value.trim_end_matches('x').trim().parse().ok(),
+            "bitrate" => {
+                self.current.bitrate_kbps = value.trim_end_matches("kbits/s").trim().parse().ok()
+            }
+            "progress" => {
+                let mut health = self.current.clone();
+                health.falling_behind = health.speed.is_some_and(|speed| speed < MIN_HEALTHY_SPEED)
+                    || health.dropped_frames > self.last_dropped_frames;
+                self.last_dropped_frames = health.dropped_frames;
+                return Some(health);
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Follows an ffmpeg `-progress` stream and keeps its latest report.
+///
+/// The reader thread ends on its own when ffmpeg exits and closes the pipe.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug)]
+pub struct RecordingHealthMonitor {
+    latest: Arc<Mutex<Option<RecordingHealth>>>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl RecordingHealthMonitor {
+    pub(super) fn start(progress: impl Read + Send + 'static) -> Self {
+        let latest = Arc::new(Mutex::new(None));
+        let thread_latest = Arc::clone(&latest);
+        thread::spawn(move || {
+            let mut parser = ProgressParser::default();
+            for line in BufReader::new(progress).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Some(health) = parser.push_line(&line) {
+                    if let Ok(mut guard) = thread_latest.lock() {
+                        *guard = Some(health);
+                    }
+                }
+            }
+        });
+        Self { latest }
+    }
+
+    pub(super) fn latest(&self) -> Option<RecordingHealth> {
+        self.latest.lock().ok().and_then(|guard| guard.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    const PROGRESS_BLOCKS: &str = "frame=60
+fps=30.00
+stream_0_0_q=23.0
+bitrate=2411.3kbits/s
+total_size=602112
+out_time_us=2000000
+dup_frames=0
+drop_frames=0
+speed=1.01x
+progress=continue
+frame=88
+fps=29.10
+bitrate=N/A
+dup_frames=3
+drop_frames=2
+speed=0.82x
+progress=continue
+";
+
+    #[test]
+    fn parses_progress_blocks_into_health_reports() {
+        let mut parser = ProgressParser::default();
+        let reports = PROGRESS_BLOCKS
+            .lines()
+            .filter_map(|line| parser.push_line(line))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            reports,
+            vec![
+                RecordingHealth {
+                    frames: 60,
+                    fps: 30.0,
+                    dropped_frames: 0,
+                    duplicated_frames: 0,
+                    speed: Some(1.01),
+                    bitrate_kbps: Some(2411.3),
+                    falling_behind: false,
+                },
+                RecordingHealth {
+                    frames: 88,
+                    fps: 29.1,
+                    dropped_frames: 2,
+                    duplicated_frames: 3,
+                    speed: Some(0.82),
+                    bitrate_kbps: None,
+                    falling_behind: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn monitor_keeps_the_latest_report_from_the_stream() {
+        let monitor = RecordingHealthMonitor::start(PROGRESS_BLOCKS.as_bytes());
+        let started = Instant::now();
+        while monitor.latest().map(|health| health.frames) != Some(88) {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
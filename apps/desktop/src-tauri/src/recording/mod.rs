@@ -1,5 +1,21 @@
+pub mod cursor;
+pub mod disk_watchdog;
+pub mod health;
+pub mod normalize;
+#[cfg(target_os = "linux")]
+mod portal;
 pub mod recorder;
+pub mod recovery;
+#[cfg(target_os = "linux")]
+mod replay;
 pub mod sources;
 
+pub use cursor::*;
+pub use disk_watchdog::*;
+pub use health::*;
+pub use normalize::*;
 pub use recorder::*;
+pub use recovery::*;
+#[cfg(target_os = "linux")]
+pub use replay::prune_orphaned_replay_buffers;
 pub use sources::*;
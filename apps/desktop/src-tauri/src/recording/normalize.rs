@@ -0,0 +1,402 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::project::NormalizedSegment;
+
+/// Codec and pixel format every normalized segment is re-encoded to.
+const NORMALIZED_CODEC: &str = "h264";
+const NORMALIZED_PIXEL_FORMAT: &str = "yuv420p";
+
+/// Stream parameters that must match for the concat demuxer to copy segments as-is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentVideoParams {
+    pub width: u32,
+    pub height: u32,
+    /// ffprobe `r_frame_rate`, e.g. `30/1`.
+    pub frame_rate: String,
+    pub codec: String,
+    pub pixel_format: String,
+    /// macOS screen segments carry the system audio.
+    pub has_audio: bool,
+}
+
+/// Common canvas for a track whose segments differ, and the segments that do not fit it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentNormalizationPlan {
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+    pub frame_rate: String,
+    pub normalized_segments: Vec<NormalizedSegment>,
+    /// Whether each segment has an audio stream; empty when none has, so only video is joined.
+    pub audio_segments: Vec<bool>,
+}
+
+fn parse_segment_video_params(raw: &str) -> Result<SegmentVideoParams, AppError> {
+    let mut width = None;
+    let mut height = None;
+    let mut frame_rate = None;
+    let mut codec = None;
+    let mut pixel_format = None;
+    for (key, value) in raw.lines().filter_map(|line| line.trim().split_once('=')) {
+        let value = value.trim();
+        match key {
+            "width" => width = value.parse::<u32>().ok().filter(|width| *width > 0),
+            "height" => height = value.parse::<u32>().ok().filter(|height| *height > 0),
+            "r_frame_rate" if value != "0/0" => frame_rate = Some(value.to_string()),
+            "codec_name" => codec = Some(value.to_string()),
+            "pix_fmt" => pixel_format = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    let missing =
+        |field: &str| AppError::Message(format!("ffprobe did not return a video {field}"));
+    Ok(SegmentVideoParams {
+        width: width.ok_or_else(|| missing("width"))?,
+        height: height.ok_or_else(|| missing("height"))?,
+        frame_rate: frame_rate.ok_or_else(|| missing("frame rate"))?,
+        codec: codec.ok_or_else(|| missing("codec"))?,
+        pixel_format: pixel_format.ok_or_else(|| missing("pixel format"))?,
+        has_audio: false,
+    })
+}
+
+pub fn probe_segment_video_params(path: &Path) -> Result<SegmentVideoParams, AppError> {
+    let output = std::process::Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-show_entries")
+        .arg("stream=codec_name,width,height,pix_fmt,r_frame_rate")
+        .arg("-of")
+        .arg("default=noprint_wrappers=1")
+        .arg(path)
+        .output()
+        .map_err(|error| AppError::Io(format!("Failed to run ffprobe for segment: {}", error)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Message(format!(
+            "ffprobe failed to read segment {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    let mut params = parse_segment_video_params(&String::from_utf8_lossy(&output.stdout))?;
+    params.has_audio = probe_segment_has_audio(path)?;
+    Ok(params)
+}
+
+fn probe_segment_has_audio(path: &Path) -> Result<bool, AppError> {
+    let output = std::process::Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("a:0")
+        .arg("-show_entries")
+        .arg("stream=codec_type")
+        .arg("-of")
+        .arg("csv=p=0")
+        .arg(path)
+        .output()
+        .map_err(|error| AppError::Io(format!("Failed to run ffprobe for segment: {}", error)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Message(format!(
+            "ffprobe failed to read segment {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+/// Returns `None` when all segments share their stream parameters and can be stream-copied.
+///
+/// The canvas is the largest segment size, rounded up to even for yuv420p, at the first
+/// segment's frame rate.
+pub fn plan_segment_normalization(
+    segments: &[SegmentVideoParams],
+) -> Option<SegmentNormalizationPlan> {
+    let first = segments.first()?;
+    if segments.iter().all(|segment| segment == first) {
+        return None;
+    }
+    let round_even = |value: u32| value + value % 2;
+    let canvas_width = round_even(segments.iter().map(|segment| segment.width).max()?);
+    let canvas_height = round_even(segments.iter().map(|segment| segment.height).max()?);
+    let normalized_segments = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| {
+            segment.width != canvas_width
+                || segment.height != canvas_height
+                || segment.frame_rate != first.frame_rate
+                || segment.codec != NORMALIZED_CODEC
+                || segment.pixel_format != NORMALIZED_PIXEL_FORMAT
+        })
+        .map(|(index, segment)| NormalizedSegment {
+            index,
+            width: segment.width,
+            height: segment.height,
+            frame_rate: segment.frame_rate.clone(),
+            codec: segment.codec.clone(),
+        })
+        .collect();
+    let audio_segments = if segments.iter().any(|segment| segment.has_audio) {
+        segments.iter().map(|segment| segment.has_audio).collect()
+    } else {
+        Vec::new()
+    };
+    Some(SegmentNormalizationPlan {
+        canvas_width,
+        canvas_height,
+        frame_rate: first.frame_rate.clone(),
+        normalized_segments,
+        audio_segments,
+    })
+}
+
+/// ffmpeg arguments that fit each segment onto the plan's canvas and join them with the
+/// concat filter, re-encoding the video.
+///
+/// When any segment has audio the audio is joined too. Segments without audio get a short
+/// silent clip, which the concat filter pads with silence to the segment's video length.
+pub fn normalized_concat_args(
+    segment_paths: &[PathBuf],
+    plan: &SegmentNormalizationPlan,
+    output_path: &Path,
+) -> Vec<String> {
+    let mut args = Vec::new();
+    for path in segment_paths {
+        args.extend(["-i".to_string(), path.to_string_lossy().to_string()]);
+    }
+    let (width, height) = (plan.canvas_width, plan.canvas_height);
+    let mut filter = String::new();
+    for index in 0..segment_paths.len() {
+        filter.push_str(&format!(
+            "[{index}:v:0]scale={width}:{height}:force_original_aspect_ratio=decrease,\
+             pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={},format={}[v{index}];",
+            plan.frame_rate, NORMALIZED_PIXEL_FORMAT
+        ));
+    }
+    let with_audio = !plan.audio_segments.is_empty();
+    if with_audio {
+        for (index, has_audio) in plan.audio_segments.iter().enumerate() {
+            let source = if *has_audio {
+                format!("[{index}:a:0]aresample=48000,")
+            } else {
+                "anullsrc=r=48000:cl=stereo,atrim=duration=0.01,".to_string()
+            };
+            filter.push_str(&format!(
+                "{source}aformat=sample_fmts=fltp:channel_layouts=stereo[a{index}];"
+            ));
+        }
+    }
+    for index in 0..segment_paths.len() {
+        filter.push_str(&format!("[v{index}]"));
+        if with_audio {
+            filter.push_str(&format!("[a{index}]"));
+        }
+    }
+    if with_audio {
+        filter.push_str(&format!(
+            "concat=n={}:v=1:a=1[out][aout]",
+            segment_paths.len()
+        ));
+    } else {
+        filter.push_str(&format!("concat=n={}:v=1:a=0[out]", segment_paths.len()));
+    }
+
+    args.extend(["-filter_complex".to_string(), filter]);
+    args.extend(["-map".to_string(), "[out]".to_string()]);
+    if with_audio {
+        args.extend([
+            "-map".to_string(),
+            "[aout]".to_string(),
+            "-c:a".to_string(),
+            "aac".to_string(),
+            "-b:a".to_string(),
+            "192k".to_string(),
+        ]);
+    }
+    args.push("-c:v".to_string());
+    if cfg!(target_os = "macos") {
+        args.extend([
+            "h264_videotoolbox".to_string(),
+            "-q:v".to_string(),
+            "65".to_string(),
+            "-allow_sw".to_string(),
+            "1".to_string(),
+        ]);
+    } else {
+        args.extend([
+            "libx264".to_string(),
+            "-preset".to_string(),
+            "veryfast".to_string(),
+            "-crf".to_string(),
+            "18".to_string(),
+        ]);
+    }
+    args.extend([
+        "-pix_fmt".to_string(),
+        NORMALIZED_PIXEL_FORMAT.to_string(),
+        "-movflags".to_string(),
+        "+faststart".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ]);
+    args
+}
+
+/// Where to cut each companion segment so that, once joined, segment `i` starts where screen
+/// segment `i` does.
+///
+/// `offsets_ms` are the companion segments' starts relative to their screen segments. A segment
+/// without a screen duration or a following offset, like the last one, is kept whole.
+pub fn companion_segment_outpoints(
+    screen_durations: &[f64],
+    offsets_ms: &[i64],
+) -> Vec<Option<f64>> {
+    (0..offsets_ms.len())
+        .map(|index| {
+            let screen_duration = screen_durations.get(index)?;
+            let next_offset_ms = offsets_ms.get(index + 1)?;
+            let drift_ms = next_offset_ms - offsets_ms[index];
+            Some((screen_duration + drift_ms as f64 / 1000.0).max(0.0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(width: u32, height: u32, frame_rate: &str) -> SegmentVideoParams {
+        SegmentVideoParams {
+            width,
+            height,
+            frame_rate: frame_rate.to_string(),
+            codec: "h264".to_string(),
+            pixel_format: "yuv420p".to_string(),
+            has_audio: false,
+        }
+    }
+
+    #[test]
+    fn cuts_companion_segments_to_keep_them_on_the_screen_timeline() {
+        // The camera started 250ms after the screen, then 400ms after it on resume, so its
+        // first segment must run 150ms longer than the screen's.
+        assert_eq!(
+            companion_segment_outpoints(&[10.0, 5.0], &[250, 400]),
+            vec![Some(10.15), None]
+        );
+        assert_eq!(
+            companion_segment_outpoints(&[10.0, 5.0, 3.0], &[300, 100, 100]),
+            vec![Some(9.8), Some(5.0), None]
+        );
+        assert_eq!(
+            companion_segment_outpoints(&[], &[250, 400]),
+            vec![None, None]
+        );
+        assert!(companion_segment_outpoints(&[10.0], &[]).is_empty());
+    }
+
+    #[test]
+    fn parses_ffprobe_stream_entries() {
+        let raw = "codec_name=h264\nwidth=2560\nheight=1440\npix_fmt=yuv420p\nr_frame_rate=60/1\n";
+        assert_eq!(
+            parse_segment_video_params(raw).unwrap(),
+            params(2560, 1440, "60/1")
+        );
+        assert!(parse_segment_video_params("codec_name=h264\nwidth=0\nheight=1440").is_err());
+    }
+
+    #[test]
+    fn plans_a_common_canvas_only_for_mismatched_segments() {
+        let matching = [params(1920, 1080, "30/1"), params(1920, 1080, "30/1")];
+        assert_eq!(plan_segment_normalization(&matching), None);
+
+        let plan = plan_segment_normalization(&[
+            params(1920, 1080, "30/1"),
+            params(1279, 720, "30/1"),
+            params(1920, 1200, "60/1"),
+        ])
+        .unwrap();
+
+        assert_eq!((plan.canvas_width, plan.canvas_height), (1920, 1200));
+        assert_eq!(plan.frame_rate, "30/1");
+        assert_eq!(
+            plan.normalized_segments
+                .iter()
+                .map(|segment| segment.index)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(plan.normalized_segments[1].width, 1279);
+        assert_eq!(plan.normalized_segments[2].frame_rate, "60/1");
+    }
+
+    #[test]
+    fn builds_a_concat_filter_over_every_segment() {
+        let plan =
+            plan_segment_normalization(&[params(1920, 1080, "30/1"), params(1280, 720, "30/1")])
+                .unwrap();
+        let args = normalized_concat_args(
+            &[
+                PathBuf::from("screen.mp4"),
+                PathBuf::from("screen_part1.mp4"),
+            ],
+            &plan,
+            Path::new("screen_merged.mp4"),
+        );
+        let filter = &args[args
+            .iter()
+            .position(|arg| arg == "-filter_complex")
+            .unwrap()
+            + 1];
+
+        assert_eq!(plan.normalized_segments.len(), 1);
+        assert!(filter.starts_with(
+            "[0:v:0]scale=1920:1080:force_original_aspect_ratio=decrease,pad=1920:1080:"
+        ));
+        assert!(filter.ends_with("[v0][v1]concat=n=2:v=1:a=0[out]"));
+        assert!(!args.contains(&"[aout]".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("screen_merged.mp4"));
+    }
+
+    #[test]
+    fn joins_the_audio_of_segments_that_carry_it_and_pads_silent_ones() {
+        let with_audio = SegmentVideoParams {
+            has_audio: true,
+            ..params(1920, 1080, "30/1")
+        };
+        let plan = plan_segment_normalization(&[with_audio, params(1280, 720, "30/1")]).unwrap();
+        let args = normalized_concat_args(
+            &[
+                PathBuf::from("screen.mp4"),
+                PathBuf::from("screen_part1.mp4"),
+            ],
+            &plan,
+            Path::new("screen_merged.mp4"),
+        );
+        let filter = &args[args
+            .iter()
+            .position(|arg| arg == "-filter_complex")
+            .unwrap()
+            + 1];
+        let maps = args
+            .windows(2)
+            .filter(|pair| pair[0] == "-map")
+            .map(|pair| pair[1].as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(plan.audio_segments, vec![true, false]);
+        assert!(filter.contains(
+            "[0:a:0]aresample=48000,aformat=sample_fmts=fltp:channel_layouts=stereo[a0];"
+        ));
+        assert!(filter.contains("anullsrc=r=48000:cl=stereo,atrim=duration=0.01,"));
+        assert!(filter.ends_with("[v0][a0][v1][a1]concat=n=2:v=1:a=1[out][aout]"));
+        assert_eq!(maps, vec!["[out]", "[aout]"]);
+        assert!(args.windows(2).any(|pair| pair == ["-c:a", "aac"]));
+    }
+}
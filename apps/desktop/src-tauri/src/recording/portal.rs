@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::os::fd::OwnedFd;
+
+use uuid::Uuid;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{self, ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+use super::SourceType;
+use crate::error::AppError;
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SCREEN_CAST_INTERFACE: &str = "org.freedesktop.portal.ScreenCast";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+const SESSION_INTERFACE: &str = "org.freedesktop.portal.Session";
+
+const PORTAL_SOURCE_MONITOR: u32 = 1;
+const PORTAL_SOURCE_WINDOW: u32 = 2;
+const PORTAL_CURSOR_HIDDEN: u32 = 1;
+const PORTAL_CURSOR_EMBEDDED: u32 = 2;
+const PORTAL_RESPONSE_SUCCESS: u32 = 0;
+const PORTAL_RESPONSE_CANCELLED: u32 = 1;
+
+/// Source id reported for portal-backed sources; the portal dialog picks the real one.
+pub const WAYLAND_PORTAL_SOURCE_ID: &str = "portal";
+
+type PortalResults = HashMap<String, OwnedValue>;
+
+fn portal_error(context: &str, error: impl std::fmt::Display) -> AppError {
+    AppError::Message(format!("{context}: {error}"))
+}
+
+/// Whether this session should capture through the ScreenCast portal instead of X11.
+fn is_wayland_session(session_type: Option<&str>) -> bool {
+    session_type.is_some_and(|value| value.trim().eq_ignore_ascii_case("wayland"))
+}
+
+pub(super) fn wayland_session_active() -> bool {
+    is_wayland_session(std::env::var("XDG_SESSION_TYPE").ok().as_deref())
+}
+
+/// Cursor mode to request given the portal's `AvailableCursorModes` bitmask.
+///
+/// Recordings show the cursor like the other backends, so embedded is preferred; portals
+/// that predate cursor modes (no bitmask) must not be sent one at all.
+fn select_cursor_mode(available_modes: Option<u32>) -> Option<u32> {
+    let available_modes = available_modes?;
+    [PORTAL_CURSOR_EMBEDDED, PORTAL_CURSOR_HIDDEN]
+        .into_iter()
+        .find(|mode| available_modes & mode != 0)
+}
+
+fn request_handle_token() -> String {
+    format!("openrec_{}", Uuid::new_v4().simple())
+}
+
+/// Object path the portal will use for a request made with `token`.
+fn request_object_path(unique_name: &str, token: &str) -> String {
+    format!(
+        "{}/request/{}/{}",
+        PORTAL_PATH,
+        unique_name.trim_start_matches(':').replace('.', "_"),
+        token
+    )
+}
+
+/// A PipeWire stream granted by the portal, sized in compositor pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct PortalStream {
+    pub node_id: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+}
+
+fn parse_portal_streams(
+    streams: Vec<(u32, HashMap<String, OwnedValue>)>,
+) -> Result<PortalStream, AppError> {
+    let (node_id, properties) = streams.into_iter().next().ok_or_else(|| {
+        AppError::Message("The screen cast portal did not share any streams".to_string())
+    })?;
+    let read_pair = |key: &str| {
+        properties
+            .get(key)
+            .and_then(|value| <(i32, i32)>::try_from(Value::from(value.clone())).ok())
+    };
+    let (width, height) = read_pair("size").unwrap_or((0, 0));
+    let (x, y) = read_pair("position").unwrap_or((0, 0));
+    Ok(PortalStream {
+        node_id,
+        width: width.max(0) as u32,
+        height: height.max(0) as u32,
+        x,
+        y,
+    })
+}
+
+/// An xdg-desktop-portal ScreenCast session; closing it revokes the PipeWire stream.
+pub struct PortalScreenCast {
+    connection: Connection,
+    session_handle: OwnedObjectPath,
+    stream: PortalStream,
+}
+
+impl std::fmt::Debug for PortalScreenCast {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("PortalScreenCast")
+            .field("session_handle", &self.session_handle.as_str())
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+impl PortalScreenCast {
+    /// Ask the portal for a monitor or window stream. Blocks while the user picks a source.
+    pub(super) fn start(source_type: SourceType) -> Result<Self, AppError> {
+        let source_types = match source_type {
+            SourceType::Display => PORTAL_SOURCE_MONITOR,
+            SourceType::Window => PORTAL_SOURCE_WINDOW,
+            SourceType::Region => {
+                return Err(AppError::Message(
+                    "Region capture is not available through the screen cast portal; record a whole screen or window on Wayland".to_string(),
+                ))
+            }
+            SourceType::Camera | SourceType::Microphone => {
+                return Err(AppError::Message(
+                    "The screen cast portal only shares screens and windows".to_string(),
+                ))
+            }
+        };
+        let connection = Connection::session()
+            .map_err(|error| portal_error("Failed to connect to the session bus", error))?;
+        let proxy = Proxy::new(
+            &connection,
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            SCREEN_CAST_INTERFACE,
+        )
+        .map_err(|error| portal_error("Failed to reach the screen cast portal", error))?;
+
+        let cursor_mode =
+            select_cursor_mode(proxy.get_property::<u32>("AvailableCursorModes").ok());
+
+        let session_token = request_handle_token();
+        let mut results = portal_request(&connection, &proxy, "CreateSession", |token| {
+            let options: HashMap<&str, Value> = HashMap::from([
+                ("handle_token", Value::from(token)),
+                ("session_handle_token", Value::from(session_token.as_str())),
+            ]);
+            (options,)
+        })?;
+        let session_handle = results
+            .remove("session_handle")
+            .and_then(|value| String::try_from(value).ok())
+            .and_then(|value| OwnedObjectPath::try_from(value).ok())
+            .ok_or_else(|| {
+                AppError::Message("The screen cast portal did not return a session".to_string())
+            })?;
+
+        portal_request(&connection, &proxy, "SelectSources", |token| {
+            let mut options: HashMap<&str, Value> = HashMap::from([
+                ("handle_token", Value::from(token)),
+                ("types", Value::from(source_types)),
+                ("multiple", Value::from(false)),
+            ]);
+            if let Some(cursor_mode) = cursor_mode {
+                options.insert("cursor_mode", Value::from(cursor_mode));
+            }
+            (session_handle.as_ref(), options)
+        })?;
+
+        let mut results = portal_request(&connection, &proxy, "Start", |token| {
+            let options: HashMap<&str, Value> =
+                HashMap::from([("handle_token", Value::from(token))]);
+            (session_handle.as_ref(), "", options)
+        })?;
+        let streams = results
+            .remove("streams")
+            .and_then(|value| Vec::<(u32, HashMap<String, OwnedValue>)>::try_from(value).ok())
+            .unwrap_or_default();
+        let stream = parse_portal_streams(streams)?;
+
+        Ok(Self {
+            connection,
+            session_handle,
+            stream,
+        })
+    }
+
+    pub(super) fn stream(&self) -> &PortalStream {
+        &self.stream
+    }
+
+    /// Open a PipeWire remote restricted to this session's stream.
+    pub(super) fn open_pipewire_remote(&self) -> Result<OwnedFd, AppError> {
+        let proxy = Proxy::new(
+            &self.connection,
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            SCREEN_CAST_INTERFACE,
+        )
+        .map_err(|error| portal_error("Failed to reach the screen cast portal", error))?;
+        let options: HashMap<&str, Value> = HashMap::new();
+        let fd: zvariant::OwnedFd = proxy
+            .call(
+                "OpenPipeWireRemote",
+                &(self.session_handle.as_ref(), options),
+            )
+            .map_err(|error| portal_error("Failed to open the PipeWire remote", error))?;
+        Ok(fd.into())
+    }
+}
+
+impl Drop for PortalScreenCast {
+    fn drop(&mut self) {
+        let closed = Proxy::new(
+            &self.connection,
+            PORTAL_DESTINATION,
+            self.session_handle.as_ref(),
+            SESSION_INTERFACE,
+        )
+        .and_then(|proxy| proxy.call_method("Close", &()));
+        if let Err(error) = closed {
+            eprintln!("Failed to close screen cast portal session: {}", error);
+        }
+    }
+}
+
+/// Call a portal method that answers through a `Request` object and wait for its response.
+fn portal_request<B>(
+    connection: &Connection,
+    proxy: &Proxy<'_>,
+    method: &str,
+    build_body: impl FnOnce(String) -> B,
+) -> Result<PortalResults, AppError>
+where
+    B: serde::Serialize + zvariant::DynamicType,
+{
+    let unique_name = connection
+        .unique_name()
+        .ok_or_else(|| AppError::Message("Session bus connection has no unique name".to_string()))?
+        .to_string();
+    let token = request_handle_token();
+    let request_path = ObjectPath::try_from(request_object_path(&unique_name, &token))
+        .map_err(|error| portal_error("Invalid portal request path", error))?;
+    // Subscribe before calling so a fast response cannot be missed.
+    let request_proxy = Proxy::new(
+        connection,
+        PORTAL_DESTINATION,
+        request_path,
+        REQUEST_INTERFACE,
+    )
+    .map_err(|error| portal_error("Failed to watch portal request", error))?;
+    let mut responses = request_proxy
+        .receive_signal("Response")
+        .map_err(|error| portal_error("Failed to watch portal request", error))?;
+
+    proxy
+        .call_method(method, &build_body(token))
+        .map_err(|error| portal_error(&format!("Screen cast portal {method} failed"), error))?;
+
+    let message = responses.next().ok_or_else(|| {
+        AppError::Message(format!(
+            "Screen cast portal {method} ended without a response"
+        ))
+    })?;
+    let (response, results): (u32, PortalResults) = message
+        .body()
+        .deserialize()
+        .map_err(|error| portal_error("Failed to read portal response", error))?;
+    match response {
+        PORTAL_RESPONSE_SUCCESS => Ok(results),
+        PORTAL_RESPONSE_CANCELLED => Err(AppError::Message(
+            "Screen sharing was cancelled in the system dialog".to_string(),
+        )),
+        _ => Err(AppError::Message(format!(
+            "Screen cast portal {method} was rejected"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_portal_capture_only_for_wayland_sessions() {
+        assert!(is_wayland_session(Some("wayland")));
+        assert!(is_wayland_session(Some(" Wayland\n")));
+        assert!(!is_wayland_session(Some("x11")));
+        assert!(!is_wayland_session(None));
+    }
+
+    #[test]
+    fn requests_only_cursor_modes_the_portal_offers() {
+        assert_eq!(
+            select_cursor_mode(Some(1 | 2 | 4)),
+            Some(PORTAL_CURSOR_EMBEDDED)
+        );
+        assert_eq!(select_cursor_mode(Some(1 | 4)), Some(PORTAL_CURSOR_HIDDEN));
+        assert_eq!(select_cursor_mode(Some(4)), None);
+        assert_eq!(select_cursor_mode(None), None);
+    }
+
+    #[test]
+    fn builds_request_path_from_unique_bus_name() {
+        assert_eq!(
+            request_object_path(":1.42", "openrec_abc"),
+            "/org/freedesktop/portal/desktop/request/1_42/openrec_abc"
+        );
+    }
+
+    #[test]
+    fn parses_first_portal_stream_size_and_position() {
+        let properties = HashMap::from([
+            (
+                "size".to_string(),
+                OwnedValue::try_from(Value::from((2560_i32, 1440_i32))).unwrap(),
+            ),
+            (
+                "position".to_string(),
+                OwnedValue::try_from(Value::from((1920_i32, 0_i32))).unwrap(),
+            ),
+        ]);
+
+        let stream = parse_portal_streams(vec![(57, properties)]).unwrap();
+
+        assert_eq!(
+            stream,
+            PortalStream {
+                node_id: 57,
+                width: 2560,
+                height: 1440,
+                x: 1920,
+                y: 0,
+            }
+        );
+        assert!(parse_portal_streams(Vec::new()).is_err());
+    }
+}
@@ -1,23 +1,22 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::future::Future;
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-#[cfg(target_os = "macos")]
-use std::time::Duration;
-use std::time::Instant;
-#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::time::{Duration, Instant};
+#[cfg(target_os = "linux")]
 use uuid::Uuid;
 #[cfg(target_os = "linux")]
 use {
-    std::io::{Read, Write},
-    std::process::{Child, Command, Stdio},
+    std::io::{BufRead, BufReader, Read, Write},
+    std::process::{Child, ChildStdout, Command, Stdio},
     std::thread,
-    std::time::Duration,
 };
 
+use super::disk_watchdog::{ensure_disk_space_above_floor, DiskSpacePolicy, DiskSpaceWatchdog};
+use super::health::RecordingHealth;
 use crate::error::AppError;
 
 #[cfg(target_os = "macos")]
@@ -30,6 +29,18 @@ use screencapturekit::{
     shareable_content::SCShareableContent,
 };
 
+#[cfg(target_os = "linux")]
+use super::cursor::{cursor_log_path, CursorCaptureArea, CursorTelemetryRecorder};
+#[cfg(target_os = "linux")]
+use super::health::RecordingHealthMonitor;
+#[cfg(target_os = "linux")]
+use super::portal::{wayland_session_active, PortalScreenCast, WAYLAND_PORTAL_SOURCE_ID};
+#[cfg(target_os = "linux")]
+use super::replay::{
+    copy_replay_segments, replay_output_args, replay_segment_pattern, replay_segments_to_keep,
+    validate_replay_buffer_seconds, wait_for_closed_replay_segment, ReplayBufferPruner,
+    REPLAY_SEGMENT_SECONDS,
+};
 #[cfg(target_os = "linux")]
 use super::sources::{linux_list_display_sources, linux_list_window_sources};
 use super::SourceType;
@@ -38,19 +49,50 @@ use super::SourceType;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordingOptions {
+    /// Ignored when `capture_screen` is off.
     pub source_id: String,
     pub source_type: SourceType,
+    /// Record the screen; off makes a camera-only or audio-only take.
+    #[serde(default = "default_capture_screen")]
+    pub capture_screen: bool,
     #[serde(default)]
     pub preferred_display_ordinal: Option<u32>,
     pub capture_camera: bool,
+    /// V4L2 device (e.g. `/dev/video0`) to record the camera from on Linux instead of the webview.
+    #[serde(default)]
+    pub camera_device_id: Option<String>,
     pub capture_microphone: bool,
+    /// Pulse source (or `default`) to record the microphone from on Linux instead of the webview.
+    #[serde(default)]
+    pub microphone_device_id: Option<String>,
     pub capture_system_audio: bool,
+    /// Rectangle on the display `source_id` to record when `source_type` is `region`.
+    #[serde(default)]
+    pub region: Option<CaptureRegion>,
+    /// Keep only this many trailing seconds of screen capture until a replay is saved (Linux).
+    #[serde(default)]
+    pub replay_buffer_seconds: Option<u32>,
+    #[serde(default)]
+    pub disk_space: DiskSpacePolicy,
+    /// Move the take onto a fallback source when the recorded one disappears mid-recording.
+    #[serde(default)]
+    pub auto_source_fallback: bool,
     #[serde(default = "default_quality_preset")]
     pub quality_preset: RecordingQualityPreset,
     #[serde(default = "default_recording_codec")]
     pub codec: RecordingCodec,
 }
 
+/// A capture rectangle in pixels, relative to the top-left corner of its display.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum RecordingQualityPreset {
@@ -73,6 +115,10 @@ pub enum RecordingCodec {
     Hevc,
 }
 
+const fn default_capture_screen() -> bool {
+    true
+}
+
 const fn default_quality_preset() -> RecordingQualityPreset {
     RecordingQualityPreset::P1080P30
 }
@@ -90,6 +136,16 @@ pub enum RecordingState {
     Stopped,
 }
 
+/// Where each backend camera and audio segment starts relative to its screen segment.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentOffsets {
+    pub camera_ms: Vec<i64>,
+    pub microphone_ms: Vec<i64>,
+    #[serde(default)]
+    pub system_audio_ms: Vec<i64>,
+}
+
 /// Information about an active recording session
 #[derive(Debug)]
 #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
@@ -97,7 +153,9 @@ pub struct RecordingSession {
     pub project_id: String,
     pub options: RecordingOptions,
     pub state: RecordingState,
-    pub screen_video_path: PathBuf,
+    pub project_dir: PathBuf,
+    /// `None` for camera-only and audio-only takes.
+    pub screen_video_path: Option<PathBuf>,
     pub camera_video_path: Option<PathBuf>,
     pub microphone_audio_path: Option<PathBuf>,
     pub start_time: chrono::DateTime<chrono::Utc>,
@@ -108,17 +166,50 @@ pub struct RecordingSession {
     pub capture_fps: u32,
     pub recording_codec: RecordingCodec,
     pub screen_segments: Vec<PathBuf>,
+    /// Camera files recorded by the backend, one per screen segment.
+    pub camera_segments: Vec<PathBuf>,
+    /// Microphone files recorded by the backend, one per screen segment.
+    pub microphone_segments: Vec<PathBuf>,
+    pub system_audio_path: Option<PathBuf>,
+    pub system_audio_segments: Vec<PathBuf>,
+    pub system_audio_offset_ms: Option<i64>,
     pub current_segment_path: PathBuf,
     pub active_duration_ms: u64,
     pub last_resume_instant: Option<Instant>,
     pub camera_offset_ms: Option<i64>,
     pub microphone_offset_ms: Option<i64>,
+    pub segment_offsets: SegmentOffsets,
+    /// Active-duration times of the markers dropped so far.
+    pub marker_times_ms: Vec<u64>,
+    /// Attached by the app once the session has started.
+    pub disk_watchdog: Option<DiskSpaceWatchdog>,
     #[cfg(target_os = "macos")]
     pub stream: Option<SCStream>,
     #[cfg(target_os = "macos")]
     pub recording_output: Option<SCRecordingOutput>,
     #[cfg(target_os = "linux")]
     pub ffmpeg_child: Option<Child>,
+    /// Reads the screen ffmpeg's `-progress` output for the current segment.
+    #[cfg(target_os = "linux")]
+    pub health_monitor: Option<RecordingHealthMonitor>,
+    /// GStreamer process feeding portal frames to ffmpeg on Wayland.
+    #[cfg(target_os = "linux")]
+    pub pipewire_feed: Option<Child>,
+    /// Kept open across pauses so resuming does not ask the user to pick a source again.
+    #[cfg(target_os = "linux")]
+    pub portal_screen_cast: Option<PortalScreenCast>,
+    #[cfg(target_os = "linux")]
+    pub cursor_telemetry: Option<CursorTelemetryRecorder>,
+    /// ffmpeg process recording the V4L2 camera next to the screen capture.
+    #[cfg(target_os = "linux")]
+    pub camera_ffmpeg_child: Option<Child>,
+    #[cfg(target_os = "linux")]
+    pub microphone_ffmpeg_child: Option<Child>,
+    #[cfg(target_os = "linux")]
+    pub system_audio_ffmpeg_child: Option<Child>,
+    /// Deletes segments that fell out of the replay window in replay buffer mode.
+    #[cfg(target_os = "linux")]
+    pub replay_pruner: Option<ReplayBufferPruner>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +217,8 @@ pub struct RecordingSession {
 pub struct RecordingSessionSnapshot {
     pub state: RecordingState,
     pub elapsed_seconds: f64,
+    /// Encoder statistics while capturing; only reported on Linux.
+    pub health: Option<RecordingHealth>,
 }
 
 /// Global recorder state
@@ -186,7 +279,7 @@ fn block_on_io<T>(future: impl Future<Output = T>) -> Result<T, AppError> {
 #[serde(rename_all = "camelCase")]
 pub struct StartRecordingResult {
     pub project_id: String,
-    pub screen_video_path: String,
+    pub screen_video_path: Option<String>,
     pub camera_video_path: Option<String>,
     pub recording_start_time_ms: i64,
     pub resolved_source_id: String,
@@ -206,6 +299,55 @@ pub struct RecordingSourceFallbackUpdate {
     pub fallback_source: RecordingSourceFallback,
 }
 
+/// Why a running capture has to move onto a new segment.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptureInterruption {
+    /// The recorded display or window is gone and a fallback is available.
+    SourceLost,
+    /// The capture process exited on its own.
+    CaptureExited,
+}
+
+/// A lost source only counts as an interruption once there is somewhere to fall back to.
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+fn source_lost_interruption(status: Option<RecordingSourceStatus>) -> Option<CaptureInterruption> {
+    status
+        .filter(|status| !status.available && status.fallback_source.is_some())
+        .map(|_| CaptureInterruption::SourceLost)
+}
+
+/// Restarts of a capture that keeps exiting before the supervisor stops the take instead.
+const MAX_CAPTURE_RESTARTS: u32 = 5;
+/// A capture that keeps running this long after a restart counts as healthy again.
+const CAPTURE_RESTART_RESET_AFTER: Duration = Duration::from_secs(60);
+
+/// Exponential backoff for restarting a capture process that exits on its own.
+#[derive(Debug, Default)]
+pub struct CaptureRestartBackoff {
+    restarts: u32,
+    last_restart: Option<Instant>,
+}
+
+impl CaptureRestartBackoff {
+    /// Delay before restarting a capture that exited at `now`, or `None` once it has exited
+    /// too often in a row to keep trying.
+    pub fn next_delay(&mut self, now: Instant) -> Option<Duration> {
+        if self.last_restart.is_some_and(|last_restart| {
+            now.saturating_duration_since(last_restart) >= CAPTURE_RESTART_RESET_AFTER
+        }) {
+            self.restarts = 0;
+        }
+        if self.restarts >= MAX_CAPTURE_RESTARTS {
+            return None;
+        }
+        let delay = Duration::from_secs(2 << self.restarts);
+        self.restarts += 1;
+        self.last_restart = Some(now + delay);
+        Some(delay)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordingSourceStatus {
@@ -216,18 +358,81 @@ pub struct RecordingSourceStatus {
 }
 
 /// Result of stopping a recording
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StopRecordingResult {
     pub project_id: String,
-    pub screen_video_path: PathBuf,
+    pub screen_video_path: Option<PathBuf>,
     pub screen_segment_paths: Vec<PathBuf>,
     pub camera_video_path: Option<PathBuf>,
+    pub camera_segment_paths: Vec<PathBuf>,
     pub microphone_audio_path: Option<PathBuf>,
+    pub microphone_segment_paths: Vec<PathBuf>,
+    pub system_audio_path: Option<PathBuf>,
+    pub system_audio_segment_paths: Vec<PathBuf>,
     pub duration_seconds: f64,
     pub source_width: u32,
     pub source_height: u32,
     pub camera_offset_ms: Option<i64>,
     pub microphone_offset_ms: Option<i64>,
+    pub system_audio_offset_ms: Option<i64>,
+    /// Lets finalization line up the backend tracks' segments with the screen's.
+    #[serde(default)]
+    pub segment_offsets: SegmentOffsets,
+    pub cursor_log_path: Option<PathBuf>,
+    /// The screen and native camera files are fragmented MP4 and still need a faststart remux.
+    pub fragmented_capture: bool,
+    #[serde(default)]
+    pub marker_times_ms: Vec<u64>,
+}
+
+impl StopRecordingResult {
+    /// Directory holding the take, found through whichever track was recorded.
+    pub fn project_dir(&self) -> Option<&Path> {
+        self.screen_video_path
+            .as_ref()
+            .or(self.camera_video_path.as_ref())
+            .or(self.microphone_audio_path.as_ref())
+            .or(self.system_audio_path.as_ref())
+            .and_then(|path| path.parent())
+    }
+}
+
+/// A take without the screen still needs a track to record, and cannot be a replay buffer.
+fn validate_capture_tracks(options: &RecordingOptions) -> Result<(), AppError> {
+    if options.capture_screen {
+        return Ok(());
+    }
+    if options.replay_buffer_seconds.is_some() {
+        return Err(AppError::Message(
+            "Replay buffer recording needs screen capture".to_string(),
+        ));
+    }
+    if !options.capture_camera && !options.capture_microphone && !options.capture_system_audio {
+        return Err(AppError::Message(
+            "Turn on the camera, microphone or system audio to record without the screen"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn device_source_error() -> AppError {
+    AppError::Message(
+        "Cameras and microphones cannot be the screen capture source; pass them as the camera or microphone device instead"
+            .to_string(),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn replay_unsupported_error() -> AppError {
+    AppError::Message("Replay buffer recording is only supported on Linux".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn region_source_error() -> AppError {
+    AppError::Message("Region capture is only supported on Linux X11 sessions".to_string())
 }
 
 #[cfg(target_os = "macos")]
@@ -343,6 +548,30 @@ fn resolve_display_fallback_index(
     0
 }
 
+/// Check that `region` is a usable rectangle lying inside a display of the given size.
+#[cfg(any(target_os = "linux", test))]
+fn validate_capture_region(
+    region: &CaptureRegion,
+    display_width: u32,
+    display_height: u32,
+) -> Result<(), AppError> {
+    if region.width < 2 || region.height < 2 {
+        return Err(AppError::Message(format!(
+            "Capture region {}x{} is too small",
+            region.width, region.height
+        )));
+    }
+    let right = region.x as u64 + region.width as u64;
+    let bottom = region.y as u64 + region.height as u64;
+    if right > display_width as u64 || bottom > display_height as u64 {
+        return Err(AppError::Message(format!(
+            "Capture region {}x{}+{}+{} does not fit on the {}x{} display",
+            region.width, region.height, region.x, region.y, display_width, display_height
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(target_os = "macos")]
 fn find_display_or_fallback_with_ordinal(
     content: &SCShareableContent,
@@ -423,7 +652,10 @@ fn create_recording_output(
     })
 }
 
-#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+#[cfg_attr(
+    not(any(target_os = "macos", target_os = "linux", test)),
+    allow(dead_code)
+)]
 fn parse_ffprobe_duration_seconds(raw: &str) -> Option<f64> {
     let trimmed = raw.trim();
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("n/a") {
@@ -529,13 +761,26 @@ async fn wait_for_file_ready_async(path: &Path, timeout: Duration) -> Result<(),
     )))
 }
 
-/// Start screen recording
+/// Start screen recording into the take directory named `project_id`, a fresh id from the caller.
 #[cfg(target_os = "macos")]
 pub fn start_recording(
     state: &SharedRecorderState,
     options: RecordingOptions,
+    project_id: String,
 ) -> Result<StartRecordingResult, AppError> {
-    let project_id = Uuid::new_v4().to_string();
+    if options.capture_screen && !options.source_type.is_screen() {
+        return Err(device_source_error());
+    }
+    if options.replay_buffer_seconds.is_some() {
+        return Err(replay_unsupported_error());
+    }
+    validate_capture_tracks(&options)?;
+    if !options.capture_screen && options.capture_system_audio {
+        return Err(AppError::Message(
+            "System audio is captured with the screen on macOS; turn screen capture on to record it"
+                .to_string(),
+        ));
+    }
 
     let mut state_guard = state
         .lock()
@@ -559,6 +804,52 @@ pub fn start_recording(
         None
     };
 
+    // The webview records the camera and microphone; the session only keeps time.
+    if !options.capture_screen {
+        let recording_start_time_ms = chrono::Utc::now().timestamp_millis();
+        let session = RecordingSession {
+            project_id: project_id.clone(),
+            options: options.clone(),
+            state: RecordingState::Recording,
+            project_dir: project_dir.clone(),
+            screen_video_path: None,
+            camera_video_path: camera_video_path.clone(),
+            microphone_audio_path: microphone_audio_path.clone(),
+            start_time: chrono::Utc::now(),
+            recording_start_time_ms,
+            segment_index: 0,
+            capture_width: 0,
+            capture_height: 0,
+            capture_fps: options.quality_preset.fps(),
+            recording_codec: options.codec,
+            screen_segments: Vec::new(),
+            camera_segments: Vec::new(),
+            microphone_segments: Vec::new(),
+            system_audio_path: None,
+            system_audio_segments: Vec::new(),
+            system_audio_offset_ms: None,
+            current_segment_path: screen_video_path,
+            active_duration_ms: 0,
+            last_resume_instant: Some(Instant::now()),
+            camera_offset_ms: None,
+            microphone_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            marker_times_ms: Vec::new(),
+            disk_watchdog: None,
+            stream: None,
+            recording_output: None,
+        };
+        state_guard.sessions.insert(project_id.clone(), session);
+        return Ok(StartRecordingResult {
+            project_id,
+            screen_video_path: None,
+            camera_video_path: camera_video_path.map(|p| p.to_string_lossy().to_string()),
+            recording_start_time_ms,
+            resolved_source_id: String::new(),
+            fallback_source: None,
+        });
+    }
+
     // Get shareable content
     let content = SCShareableContent::get()
         .map_err(|e| AppError::Message(format!("Failed to get shareable content: {:?}", e)))?;
@@ -606,6 +897,8 @@ pub fn start_recording(
                 },
             )
         }
+        SourceType::Camera | SourceType::Microphone => return Err(device_source_error()),
+        SourceType::Region => return Err(region_source_error()),
         SourceType::Window => {
             let window_id = parse_window_id(&options.source_id)?;
             let (window, used_fallback) = find_window_or_fallback(&content, window_id)?;
@@ -678,7 +971,8 @@ pub fn start_recording(
         project_id: project_id.clone(),
         options: session_options,
         state: RecordingState::Recording,
-        screen_video_path: screen_video_path.clone(),
+        project_dir: project_dir.clone(),
+        screen_video_path: Some(screen_video_path.clone()),
         camera_video_path: camera_video_path.clone(),
         microphone_audio_path: microphone_audio_path.clone(),
         start_time: chrono::Utc::now(),
@@ -689,11 +983,19 @@ pub fn start_recording(
         capture_fps,
         recording_codec: options.codec,
         screen_segments: vec![screen_video_path.clone()],
+        camera_segments: Vec::new(),
+        microphone_segments: Vec::new(),
+        system_audio_path: None,
+        system_audio_segments: Vec::new(),
+        system_audio_offset_ms: None,
         current_segment_path: screen_video_path.clone(),
         active_duration_ms: 0,
         last_resume_instant: Some(Instant::now()),
         camera_offset_ms: None,
         microphone_offset_ms: None,
+        segment_offsets: SegmentOffsets::default(),
+        marker_times_ms: Vec::new(),
+        disk_watchdog: None,
         stream: Some(stream),
         recording_output: Some(recording_output),
     };
@@ -702,7 +1004,7 @@ pub fn start_recording(
 
     Ok(StartRecordingResult {
         project_id,
-        screen_video_path: screen_video_path.to_string_lossy().to_string(),
+        screen_video_path: Some(screen_video_path.to_string_lossy().to_string()),
         camera_video_path: camera_video_path.map(|p| p.to_string_lossy().to_string()),
         recording_start_time_ms,
         resolved_source_id,
@@ -724,6 +1026,7 @@ pub fn stop_recording(
         let session = state_guard.sessions.get_mut(project_id).ok_or_else(|| {
             AppError::Message(format!("Recording session not found: {}", project_id))
         })?;
+        stop_disk_space_watchdog(session);
         let current_segment_path = session.current_segment_path.clone();
 
         if let Some(ref stream) = session.stream {
@@ -766,12 +1069,21 @@ pub fn stop_recording(
         screen_video_path: session.screen_video_path.clone(),
         screen_segment_paths: session.screen_segments.clone(),
         camera_video_path: session.camera_video_path.clone(),
+        camera_segment_paths: Vec::new(),
         microphone_audio_path: session.microphone_audio_path.clone(),
+        microphone_segment_paths: Vec::new(),
+        system_audio_path: None,
+        system_audio_segment_paths: Vec::new(),
         duration_seconds: session.active_duration_ms as f64 / 1000.0,
         source_width: session.capture_width,
         source_height: session.capture_height,
         camera_offset_ms: session.camera_offset_ms,
         microphone_offset_ms: session.microphone_offset_ms,
+        system_audio_offset_ms: None,
+        segment_offsets: SegmentOffsets::default(),
+        cursor_log_path: None,
+        fragmented_capture: false,
+        marker_times_ms: session.marker_times_ms.clone(),
     })
 }
 
@@ -813,7 +1125,9 @@ pub fn pause_recording(state: &SharedRecorderState, project_id: &str) -> Result<
         session.active_duration_ms = session.active_duration_ms.saturating_add(elapsed);
     }
 
-    wait_for_file_ready(&session.current_segment_path, Duration::from_secs(20))?;
+    if session.screen_video_path.is_some() {
+        wait_for_file_ready(&session.current_segment_path, Duration::from_secs(20))?;
+    }
 
     session.state = RecordingState::Paused;
     session.stream = None;
@@ -836,28 +1150,34 @@ pub fn resume_recording(
         .sessions
         .get_mut(project_id)
         .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    resume_session(session)
+}
 
+/// Start the next segment of a paused session; the caller holds the recorder lock.
+#[cfg(target_os = "macos")]
+fn resume_session(
+    session: &mut RecordingSession,
+) -> Result<Option<RecordingSourceFallbackUpdate>, AppError> {
     if session.state != RecordingState::Paused {
         return Err(AppError::Message(format!(
             "Recording is not paused for project {}",
-            project_id
+            session.project_id
         )));
     }
 
+    if session.screen_video_path.is_none() {
+        session.last_resume_instant = Some(Instant::now());
+        session.state = RecordingState::Recording;
+        return Ok(None);
+    }
+
     // Increment segment index
     session.segment_index += 1;
 
-    // Get the project directory
-    let project_dir = session.screen_video_path.parent().ok_or_else(|| {
-        AppError::Message(format!(
-            "Invalid video path for project {}: {}",
-            project_id,
-            session.screen_video_path.display()
-        ))
-    })?;
-
     // Create new segment file path
-    let segment_path = project_dir.join(format!("screen_part{}.mp4", session.segment_index));
+    let segment_path = session
+        .project_dir
+        .join(format!("screen_part{}.mp4", session.segment_index));
 
     // Get shareable content again
     let content = SCShareableContent::get()
@@ -880,7 +1200,7 @@ pub fn resume_recording(
                 eprintln!(
                     "Requested display {} for project {} is unavailable. Resuming on display {}.",
                     display_id,
-                    project_id,
+                    session.project_id,
                     display.display_id()
                 );
                 session.options.source_id = display.display_id().to_string();
@@ -897,6 +1217,8 @@ pub fn resume_recording(
                 .with_excluding_windows(&[])
                 .build()
         }
+        SourceType::Camera | SourceType::Microphone => return Err(device_source_error()),
+        SourceType::Region => return Err(region_source_error()),
         SourceType::Window => {
             let window_id = parse_window_id(&session.options.source_id)?;
             let (window, used_fallback) = find_window_or_fallback(&content, window_id)?;
@@ -904,7 +1226,7 @@ pub fn resume_recording(
                 eprintln!(
                     "Requested window {} for project {} is unavailable. Resuming on window {}.",
                     window_id,
-                    project_id,
+                    session.project_id,
                     window.window_id()
                 );
                 session.options.source_id = window.window_id().to_string();
@@ -992,7 +1314,12 @@ pub fn get_recording_source_status(
     let state_guard = state
         .lock()
         .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
-    let session = if let Some(session) = state_guard.sessions.get(project_id) {
+    // Camera-only and audio-only takes have no source to lose.
+    let session = if let Some(session) = state_guard
+        .sessions
+        .get(project_id)
+        .filter(|session| session.screen_video_path.is_some())
+    {
         session
     } else {
         return Ok(None);
@@ -1034,6 +1361,8 @@ pub fn get_recording_source_status(
                 fallback_source: None,
             }))
         }
+        SourceType::Camera | SourceType::Microphone => Err(device_source_error()),
+        SourceType::Region => Err(region_source_error()),
         SourceType::Window => {
             let window_id = parse_window_id(&source_id)?;
             let available_window_ids = content
@@ -1073,6 +1402,55 @@ pub fn get_recording_source_status(
     }
 }
 
+/// Hand a started session its disk space watchdog.
+pub fn attach_disk_space_watchdog(
+    state: &SharedRecorderState,
+    project_id: &str,
+    watchdog: DiskSpaceWatchdog,
+) -> Result<(), AppError> {
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let Some(session) = state_guard.sessions.get_mut(project_id) else {
+        drop(state_guard);
+        watchdog.stop();
+        return Err(AppError::Message(format!(
+            "Recording session not found: {}",
+            project_id
+        )));
+    };
+    if let Some(previous) = session.disk_watchdog.replace(watchdog) {
+        previous.stop();
+    }
+    Ok(())
+}
+
+/// Refuse to resume a take while its disk is still at the floor of its disk space policy.
+///
+/// The watchdog only reports level changes, so a take paused at the floor is not paused again.
+pub fn ensure_resume_disk_space(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<(), AppError> {
+    let (project_dir, policy) = {
+        let state_guard = state
+            .lock()
+            .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+        let session = state_guard.sessions.get(project_id).ok_or_else(|| {
+            AppError::Message(format!("Recording session not found: {}", project_id))
+        })?;
+        (session.project_dir.clone(), session.options.disk_space)
+    };
+    ensure_disk_space_above_floor(&project_dir, &policy)
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn stop_disk_space_watchdog(session: &mut RecordingSession) {
+    if let Some(disk_watchdog) = session.disk_watchdog.take() {
+        disk_watchdog.stop();
+    }
+}
+
 #[cfg(any(target_os = "macos", target_os = "linux", test))]
 fn calculate_elapsed_duration_ms(session: &RecordingSession) -> u64 {
     let mut elapsed_ms = session.active_duration_ms;
@@ -1084,6 +1462,29 @@ fn calculate_elapsed_duration_ms(session: &RecordingSession) -> u64 {
     elapsed_ms
 }
 
+/// Drop a marker at the session's active-duration clock; returns its time in seconds.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub fn add_recording_marker(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<f64, AppError> {
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let session = state_guard
+        .sessions
+        .get_mut(project_id)
+        .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    if session.options.replay_buffer_seconds.is_some() {
+        return Err(AppError::Message(
+            "Markers are not available in replay buffer mode".to_string(),
+        ));
+    }
+    let time_ms = calculate_elapsed_duration_ms(session);
+    session.marker_times_ms.push(time_ms);
+    Ok(time_ms as f64 / 1000.0)
+}
+
 #[cfg(target_os = "macos")]
 pub fn get_recording_state(
     state: &SharedRecorderState,
@@ -1111,10 +1512,69 @@ pub fn get_recording_snapshot(
         RecordingSessionSnapshot {
             state: session.state,
             elapsed_seconds,
+            health: None,
         }
     }))
 }
 
+#[cfg(target_os = "macos")]
+pub fn detect_capture_interruption(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<Option<CaptureInterruption>, AppError> {
+    if get_recording_state(state, project_id)? != Some(RecordingState::Recording) {
+        return Ok(None);
+    }
+    Ok(source_lost_interruption(get_recording_source_status(
+        state, project_id,
+    )?))
+}
+
+/// Close the current segment and continue on a new one, re-resolving the source.
+///
+/// Unlike a pause, failures to close the old stream are logged rather than returned, since
+/// the stream is usually already gone.
+#[cfg(target_os = "macos")]
+pub fn switch_capture_segment(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<Option<RecordingSourceFallbackUpdate>, AppError> {
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let session = state_guard
+        .sessions
+        .get_mut(project_id)
+        .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    if session.state != RecordingState::Recording {
+        return Err(AppError::Message(format!(
+            "Recording is not active for project {}",
+            project_id
+        )));
+    }
+    if let Some(stream) = session.stream.take() {
+        if let Some(recording_output) = session.recording_output.take() {
+            if let Err(error) = stream.remove_recording_output(&recording_output) {
+                eprintln!("Failed to remove recording output while switching source: {error:?}");
+            }
+        }
+        if let Err(error) = stream.stop_capture() {
+            eprintln!("Failed to stop capture while switching source: {error:?}");
+        }
+    }
+    if let Some(last_resume) = session.last_resume_instant.take() {
+        let elapsed = last_resume.elapsed().as_millis() as u64;
+        session.active_duration_ms = session.active_duration_ms.saturating_add(elapsed);
+    }
+    if let Err(error) = wait_for_file_ready(&session.current_segment_path, Duration::from_secs(20))
+    {
+        eprintln!("Interrupted segment may be incomplete: {}", error);
+    }
+    session.state = RecordingState::Paused;
+    // Resume under the same lock so no other command sees the take paused in between.
+    resume_session(session)
+}
+
 /// Cleanup all active recording streams (used on app termination)
 #[cfg(target_os = "macos")]
 pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppError> {
@@ -1123,6 +1583,7 @@ pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppE
         .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
 
     for session in state_guard.sessions.values_mut() {
+        stop_disk_space_watchdog(session);
         if let Some(stream) = session.stream.as_ref() {
             if let Err(error) = stream.stop_capture() {
                 eprintln!(
@@ -1144,6 +1605,9 @@ pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppE
         session.recording_output = None;
         session.state = RecordingState::Stopped;
 
+        if session.screen_video_path.is_none() {
+            continue;
+        }
         if let Err(error) =
             wait_for_file_ready(&session.current_segment_path, Duration::from_secs(5))
         {
@@ -1320,19 +1784,74 @@ fn resolve_linux_window_selection(source_id: &str) -> Result<LinuxCaptureSelecti
 }
 
 #[cfg(target_os = "linux")]
-fn resolve_linux_capture_selection(
+fn resolve_linux_region_selection(
     source_id: &str,
-    source_type: SourceType,
     preferred_display_ordinal: Option<u32>,
+    region: Option<&CaptureRegion>,
 ) -> Result<LinuxCaptureSelection, AppError> {
-    match source_type {
+    let region = region.ok_or_else(|| {
+        AppError::Message("Region capture requires a region rectangle".to_string())
+    })?;
+    let display = resolve_linux_display_selection(source_id, preferred_display_ordinal)?;
+    validate_capture_region(region, display.source_width, display.source_height)?;
+    Ok(LinuxCaptureSelection {
+        source_type: SourceType::Region,
+        source_width: region.width,
+        source_height: region.height,
+        x: display.x + region.x as i32,
+        y: display.y + region.y as i32,
+        ..display
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn resolve_linux_capture_selection(
+    options: &RecordingOptions,
+) -> Result<LinuxCaptureSelection, AppError> {
+    match options.source_type {
         SourceType::Display => {
-            resolve_linux_display_selection(source_id, preferred_display_ordinal)
+            resolve_linux_display_selection(&options.source_id, options.preferred_display_ordinal)
         }
-        SourceType::Window => resolve_linux_window_selection(source_id),
+        SourceType::Window => resolve_linux_window_selection(&options.source_id),
+        SourceType::Region => resolve_linux_region_selection(
+            &options.source_id,
+            options.preferred_display_ordinal,
+            options.region.as_ref(),
+        ),
+        SourceType::Camera | SourceType::Microphone => Err(device_source_error()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn linux_portal_selection(
+    source_type: SourceType,
+    portal_screen_cast: &PortalScreenCast,
+) -> LinuxCaptureSelection {
+    let stream = portal_screen_cast.stream();
+    LinuxCaptureSelection {
+        source_type,
+        source_id: WAYLAND_PORTAL_SOURCE_ID.to_string(),
+        source_width: stream.width,
+        source_height: stream.height,
+        x: stream.x,
+        y: stream.y,
+        window_id: None,
+        preferred_display_ordinal: None,
+        fallback_source: None,
     }
 }
 
+/// Muxer flags that keep an MP4 playable up to its last flushed fragment if ffmpeg dies.
+#[cfg(target_os = "linux")]
+fn linux_fragmented_mp4_args() -> [String; 4] {
+    [
+        "-movflags".to_string(),
+        "+frag_keyframe+empty_moov+default_base_moof".to_string(),
+        "-frag_duration".to_string(),
+        "1000000".to_string(),
+    ]
+}
+
 #[cfg(target_os = "linux")]
 fn resolve_linux_display_env() -> Result<String, AppError> {
     std::env::var("DISPLAY").map_err(|_| {
@@ -1381,13 +1900,21 @@ fn resolve_default_pulse_monitor_input() -> Option<String> {
     })
 }
 
+/// Where the Linux ffmpeg capture reads its video frames from.
+#[cfg(target_os = "linux")]
+enum LinuxVideoFeed {
+    X11Grab,
+    /// Y4M frames from a portal PipeWire stream, already scaled to the capture size.
+    PipeWire(ChildStdout),
+}
+
 #[cfg(target_os = "linux")]
 fn spawn_linux_ffmpeg_capture(
     options: &RecordingOptions,
     selection: &LinuxCaptureSelection,
     output_path: &PathBuf,
+    video_feed: LinuxVideoFeed,
 ) -> Result<Child, AppError> {
-    let display = resolve_linux_display_env()?;
     let fps = linux_fps(options.quality_preset);
     let (capture_width, capture_height) = resolve_output_dimensions_linux(
         selection.source_width,
@@ -1399,49 +1926,61 @@ fn spawn_linux_ffmpeg_capture(
         "-hide_banner".to_string(),
         "-loglevel".to_string(),
         "error".to_string(),
+        "-nostats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
         "-thread_queue_size".to_string(),
         "1024".to_string(),
-        "-f".to_string(),
-        "x11grab".to_string(),
-        "-framerate".to_string(),
-        fps.to_string(),
     ];
 
-    match selection.source_type {
-        SourceType::Display => {
-            args.push("-video_size".to_string());
-            args.push(format!(
-                "{}x{}",
-                selection.source_width, selection.source_height
-            ));
-            args.push("-i".to_string());
-            args.push(format!("{}+{},{}", display, selection.x, selection.y));
+    let (stdin, scaled_by_feed) = match video_feed {
+        LinuxVideoFeed::X11Grab => {
+            let display = resolve_linux_display_env()?;
+            args.extend([
+                "-f".to_string(),
+                "x11grab".to_string(),
+                "-framerate".to_string(),
+                fps.to_string(),
+            ]);
+            match selection.source_type {
+                SourceType::Display | SourceType::Region => {
+                    args.push("-video_size".to_string());
+                    args.push(format!(
+                        "{}x{}",
+                        selection.source_width, selection.source_height
+                    ));
+                    args.push("-i".to_string());
+                    args.push(format!("{}+{},{}", display, selection.x, selection.y));
+                }
+                SourceType::Camera | SourceType::Microphone => return Err(device_source_error()),
+                SourceType::Window => {
+                    let window_id = selection.window_id.ok_or_else(|| {
+                        AppError::Message(
+                            "Window capture source is missing a window id".to_string(),
+                        )
+                    })?;
+                    args.push("-window_id".to_string());
+                    args.push(format!("0x{:x}", window_id));
+                    args.push("-i".to_string());
+                    args.push(display);
+                }
+            }
+            (Stdio::piped(), false)
         }
-        SourceType::Window => {
-            let window_id = selection.window_id.ok_or_else(|| {
-                AppError::Message("Window capture source is missing a window id".to_string())
-            })?;
-            args.push("-window_id".to_string());
-            args.push(format!("0x{:x}", window_id));
-            args.push("-i".to_string());
-            args.push(display);
+        LinuxVideoFeed::PipeWire(frames) => {
+            args.extend([
+                "-f".to_string(),
+                "yuv4mpegpipe".to_string(),
+                "-i".to_string(),
+                "pipe:0".to_string(),
+            ]);
+            (Stdio::from(frames), true)
         }
-    }
-
-    if options.capture_system_audio {
-        let pulse_input =
-            resolve_default_pulse_monitor_input().unwrap_or_else(|| "default".to_string());
-        args.extend([
-            "-thread_queue_size".to_string(),
-            "1024".to_string(),
-            "-f".to_string(),
-            "pulse".to_string(),
-            "-i".to_string(),
-            pulse_input,
-        ]);
-    }
+    };
 
-    if capture_width != selection.source_width || capture_height != selection.source_height {
+    if !scaled_by_feed
+        && (capture_width != selection.source_width || capture_height != selection.source_height)
+    {
         args.push("-vf".to_string());
         args.push(format!(
             "scale={}x{}:flags=lanczos",
@@ -1474,32 +2013,18 @@ fn spawn_linux_ffmpeg_capture(
         }
     }
 
-    if options.capture_system_audio {
-        args.extend([
-            "-c:a".to_string(),
-            "aac".to_string(),
-            "-b:a".to_string(),
-            "192k".to_string(),
-            "-ac".to_string(),
-            "2".to_string(),
-            "-ar".to_string(),
-            "48000".to_string(),
-        ]);
+    args.push("-an".to_string());
+    if options.replay_buffer_seconds.is_some() {
+        args.extend(replay_output_args());
     } else {
-        args.push("-an".to_string());
+        args.extend(linux_fragmented_mp4_args());
     }
-
-    args.extend([
-        "-movflags".to_string(),
-        "+faststart".to_string(),
-        "-y".to_string(),
-        output_path.to_string_lossy().to_string(),
-    ]);
+    args.extend(["-y".to_string(), output_path.to_string_lossy().to_string()]);
 
     let mut child = Command::new("ffmpeg")
         .args(args)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::null())
+        .stdin(stdin)
+        .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|error| {
@@ -1513,26 +2038,551 @@ fn spawn_linux_ffmpeg_capture(
     Ok(child)
 }
 
+/// Start GStreamer reading the portal's PipeWire stream and writing Y4M frames to stdout.
 #[cfg(target_os = "linux")]
-fn read_child_stderr(child: &mut Child) -> String {
-    if let Some(mut stderr) = child.stderr.take() {
-        let mut buffer = String::new();
-        if stderr.read_to_string(&mut buffer).is_ok() {
-            return buffer.trim().to_string();
-        }
-    }
-    String::new()
-}
-
-#[cfg(target_os = "linux")]
-fn ensure_linux_ffmpeg_started(child: &mut Child, context: &str) -> Result<(), AppError> {
-    let timeout = Duration::from_millis(200);
-    let poll_interval = Duration::from_millis(20);
-    let started = Instant::now();
+fn spawn_linux_pipewire_feed(
+    portal_screen_cast: &PortalScreenCast,
+    width: u32,
+    height: u32,
+    fps: u32,
+) -> Result<Child, AppError> {
+    let pipewire_remote = portal_screen_cast.open_pipewire_remote()?;
+    let args = [
+        "-q".to_string(),
+        "pipewiresrc".to_string(),
+        "fd=0".to_string(),
+        format!("path={}", portal_screen_cast.stream().node_id),
+        "do-timestamp=true".to_string(),
+        "keepalive-time=1000".to_string(),
+        "!".to_string(),
+        "videoconvert".to_string(),
+        "!".to_string(),
+        "videoscale".to_string(),
+        "!".to_string(),
+        "videorate".to_string(),
+        "!".to_string(),
+        format!("video/x-raw,format=I420,width={width},height={height},framerate={fps}/1"),
+        "!".to_string(),
+        "y4menc".to_string(),
+        "!".to_string(),
+        "fdsink".to_string(),
+        "fd=1".to_string(),
+    ];
+    let mut child = Command::new("gst-launch-1.0")
+        .args(args)
+        .stdin(Stdio::from(pipewire_remote))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            AppError::Io(format!(
+                "Failed to spawn GStreamer for Wayland capture. Ensure gst-launch-1.0 and the PipeWire GStreamer plugin are installed: {}",
+                error
+            ))
+        })?;
+    ensure_linux_child_started(&mut child, "GStreamer", "starting the PipeWire feed")?;
+    // Nothing reads the pipe after startup, and a full pipe would freeze the feed mid-take.
+    log_child_stderr(&mut child, "GStreamer");
+    Ok(child)
+}
+
+/// Spawn the capture for a selection, through the portal feed when one is open.
+///
+/// Returns the ffmpeg process and, on Wayland, the GStreamer process feeding it.
+#[cfg(target_os = "linux")]
+fn spawn_linux_capture(
+    options: &RecordingOptions,
+    selection: &LinuxCaptureSelection,
+    output_path: &PathBuf,
+    portal_screen_cast: Option<&PortalScreenCast>,
+) -> Result<(Child, Option<Child>), AppError> {
+    let Some(portal_screen_cast) = portal_screen_cast else {
+        let child =
+            spawn_linux_ffmpeg_capture(options, selection, output_path, LinuxVideoFeed::X11Grab)?;
+        return Ok((child, None));
+    };
+
+    let (capture_width, capture_height) = resolve_output_dimensions_linux(
+        selection.source_width,
+        selection.source_height,
+        options.quality_preset,
+    );
+    let mut feed = spawn_linux_pipewire_feed(
+        portal_screen_cast,
+        capture_width,
+        capture_height,
+        linux_fps(options.quality_preset),
+    )?;
+    let Some(frames) = feed.stdout.take() else {
+        let _ = feed.kill();
+        let _ = feed.wait();
+        return Err(AppError::Message(
+            "PipeWire feed did not expose an output pipe".to_string(),
+        ));
+    };
+    match spawn_linux_ffmpeg_capture(
+        options,
+        selection,
+        output_path,
+        LinuxVideoFeed::PipeWire(frames),
+    ) {
+        Ok(child) => Ok((child, Some(feed))),
+        Err(error) => {
+            let _ = feed.kill();
+            let _ = feed.wait();
+            Err(error)
+        }
+    }
+}
+
+/// Stop the frame feed first so ffmpeg sees the end of its input, then finalize ffmpeg.
+#[cfg(target_os = "linux")]
+fn stop_linux_capture(
+    child: &mut Child,
+    pipewire_feed: Option<Child>,
+    context: &str,
+) -> Result<(), AppError> {
+    if let Some(mut feed) = pipewire_feed {
+        let _ = feed.kill();
+        let _ = feed.wait();
+    }
+    stop_linux_ffmpeg_capture(child, context)
+}
+
+/// The V4L2 device to record from when the backend owns the camera.
+#[cfg(target_os = "linux")]
+fn linux_camera_device(options: &RecordingOptions) -> Option<&str> {
+    options
+        .camera_device_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|device| options.capture_camera && !device.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+fn camera_segment_path(project_dir: &Path, segment_index: u32) -> PathBuf {
+    if segment_index == 0 {
+        project_dir.join("camera.mp4")
+    } else {
+        project_dir.join(format!("camera_part{}.mp4", segment_index))
+    }
+}
+
+/// Frame sizes in `ffmpeg -f v4l2 -list_formats all` output; stepwise ranges are skipped.
+#[cfg(target_os = "linux")]
+fn parse_v4l2_frame_sizes(listing: &str) -> Vec<(u32, u32)> {
+    let mut sizes = Vec::new();
+    for line in listing.lines() {
+        let Some((_, size_list)) = line.rsplit_once(" : ") else {
+            continue;
+        };
+        for size in size_list.split_whitespace() {
+            let parsed = size
+                .split_once('x')
+                .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)));
+            if let Some(size) = parsed.filter(|size| !sizes.contains(size)) {
+                sizes.push(size);
+            }
+        }
+    }
+    sizes
+}
+
+/// 720p when the camera offers it, else its largest size below that.
+#[cfg(target_os = "linux")]
+fn choose_camera_video_size(sizes: &[(u32, u32)]) -> Option<(u32, u32)> {
+    if sizes.contains(&(1280, 720)) {
+        return Some((1280, 720));
+    }
+    sizes
+        .iter()
+        .copied()
+        .filter(|&(width, height)| width <= 1280 && height <= 720)
+        .max_by_key(|&(width, height)| width * height)
+}
+
+#[cfg(target_os = "linux")]
+fn probe_camera_video_size(device: &str) -> Option<(u32, u32)> {
+    let output = Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-f",
+            "v4l2",
+            "-list_formats",
+            "all",
+            "-i",
+            device,
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    choose_camera_video_size(&parse_v4l2_frame_sizes(&String::from_utf8_lossy(
+        &output.stderr,
+    )))
+}
+
+/// Record a V4L2 camera at `video_size`, or at the device default; the caller checks it started.
+#[cfg(target_os = "linux")]
+fn spawn_linux_camera_capture(
+    device: &str,
+    output_path: &Path,
+    video_size: Option<(u32, u32)>,
+) -> Result<Child, AppError> {
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-thread_queue_size".to_string(),
+        "512".to_string(),
+        "-f".to_string(),
+        "v4l2".to_string(),
+    ];
+    if let Some((width, height)) = video_size {
+        args.extend([
+            "-framerate".to_string(),
+            "30".to_string(),
+            "-video_size".to_string(),
+            format!("{}x{}", width, height),
+        ]);
+    }
+    args.extend([
+        "-i".to_string(),
+        device.to_string(),
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "veryfast".to_string(),
+        "-crf".to_string(),
+        "23".to_string(),
+        "-pix_fmt".to_string(),
+        "yuv420p".to_string(),
+        "-an".to_string(),
+    ]);
+    args.extend(linux_fragmented_mp4_args());
+    args.extend(["-y".to_string(), output_path.to_string_lossy().to_string()]);
+    Command::new("ffmpeg")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            AppError::Io(format!(
+                "Failed to spawn ffmpeg for camera capture. Ensure ffmpeg is installed and available on PATH: {}",
+                error
+            ))
+        })
+}
+
+/// The Pulse source to record from when the backend owns the microphone.
+#[cfg(target_os = "linux")]
+fn linux_microphone_device(options: &RecordingOptions) -> Option<&str> {
+    options
+        .microphone_device_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|device| options.capture_microphone && !device.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+fn microphone_segment_path(project_dir: &Path, segment_index: u32) -> PathBuf {
+    if segment_index == 0 {
+        project_dir.join("microphone.flac")
+    } else {
+        project_dir.join(format!("microphone_part{}.flac", segment_index))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn system_audio_segment_path(project_dir: &Path, segment_index: u32) -> PathBuf {
+    if segment_index == 0 {
+        project_dir.join("system_audio.flac")
+    } else {
+        project_dir.join(format!("system_audio_part{}.flac", segment_index))
+    }
+}
+
+/// Record a Pulse source (a microphone or a sink monitor) losslessly to FLAC.
+///
+/// The caller checks that the process started.
+#[cfg(target_os = "linux")]
+fn spawn_linux_pulse_capture(
+    device: &str,
+    output_path: &Path,
+    track: &str,
+) -> Result<Child, AppError> {
+    let args = [
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-thread_queue_size".to_string(),
+        "1024".to_string(),
+        "-f".to_string(),
+        "pulse".to_string(),
+        "-i".to_string(),
+        device.to_string(),
+        "-ar".to_string(),
+        "48000".to_string(),
+        "-c:a".to_string(),
+        "flac".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ];
+    Command::new("ffmpeg")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            AppError::Io(format!(
+                "Failed to spawn ffmpeg for {} capture. Ensure ffmpeg is installed and available on PATH: {}",
+                track, error
+            ))
+        })
+}
+
+/// Camera and audio processes the backend records next to one screen segment.
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct LinuxSegmentTracks {
+    camera: Option<Child>,
+    camera_started_ms: Option<i64>,
+    microphone: Option<Child>,
+    microphone_started_ms: Option<i64>,
+    system_audio: Option<Child>,
+    system_audio_started_ms: Option<i64>,
+}
+
+/// Start the backend camera, microphone and system audio for a segment.
+///
+/// If any fails to open, the whole segment is torn down, screen capture included.
+#[cfg(target_os = "linux")]
+fn spawn_linux_segment_tracks(
+    options: &RecordingOptions,
+    project_dir: &Path,
+    segment_index: u32,
+    screen_child: Option<&mut Child>,
+    pipewire_feed: &mut Option<Child>,
+) -> Result<LinuxSegmentTracks, AppError> {
+    let mut tracks = LinuxSegmentTracks::default();
+    let spawned = (|| -> Result<(), AppError> {
+        // Probe first so the tracks start as close together as possible.
+        let camera = linux_camera_device(options)
+            .map(|device| {
+                if !Path::new(device).exists() {
+                    return Err(AppError::Message(format!(
+                        "Camera device {} is not available",
+                        device
+                    )));
+                }
+                Ok((device, probe_camera_video_size(device)))
+            })
+            .transpose()?;
+        let microphone = linux_microphone_device(options);
+        let system_audio_monitor = options.capture_system_audio.then(|| {
+            resolve_default_pulse_monitor_input().unwrap_or_else(|| "default".to_string())
+        });
+
+        let spawned_at = Instant::now();
+        if let Some((device, video_size)) = camera {
+            let output_path = camera_segment_path(project_dir, segment_index);
+            tracks.camera = Some(spawn_linux_camera_capture(
+                device,
+                &output_path,
+                video_size,
+            )?);
+            tracks.camera_started_ms = Some(chrono::Utc::now().timestamp_millis());
+        }
+        if let Some(device) = microphone {
+            let output_path = microphone_segment_path(project_dir, segment_index);
+            tracks.microphone = Some(spawn_linux_pulse_capture(
+                device,
+                &output_path,
+                "microphone",
+            )?);
+            tracks.microphone_started_ms = Some(chrono::Utc::now().timestamp_millis());
+        }
+        if let Some(monitor) = system_audio_monitor.as_deref() {
+            let output_path = system_audio_segment_path(project_dir, segment_index);
+            tracks.system_audio = Some(spawn_linux_pulse_capture(
+                monitor,
+                &output_path,
+                "system audio",
+            )?);
+            tracks.system_audio_started_ms = Some(chrono::Utc::now().timestamp_millis());
+        }
+
+        // One startup window covers every track.
+        if let Some(child) = tracks.microphone.as_mut() {
+            ensure_linux_child_started_since(
+                child,
+                "ffmpeg",
+                "opening the microphone",
+                spawned_at,
+            )?;
+        }
+        if let Some(child) = tracks.system_audio.as_mut() {
+            ensure_linux_child_started_since(child, "ffmpeg", "opening system audio", spawned_at)?;
+        }
+        if let (Some(child), Some((device, video_size))) = (tracks.camera.as_mut(), camera) {
+            if let Err(error) =
+                ensure_linux_child_started_since(child, "ffmpeg", "opening the camera", spawned_at)
+            {
+                let Some((width, height)) = video_size else {
+                    return Err(error);
+                };
+                eprintln!(
+                    "Camera {} failed at {}x{}, retrying at its default size: {}",
+                    device, width, height, error
+                );
+                let output_path = camera_segment_path(project_dir, segment_index);
+                let mut child = spawn_linux_camera_capture(device, &output_path, None)?;
+                tracks.camera_started_ms = Some(chrono::Utc::now().timestamp_millis());
+                ensure_linux_ffmpeg_started(&mut child, "opening the camera")?;
+                tracks.camera = Some(child);
+            }
+        }
+        Ok(())
+    })();
+    if let Err(error) = spawned {
+        for mut child in [
+            tracks.camera,
+            tracks.microphone,
+            tracks.system_audio,
+            pipewire_feed.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        if let Some(screen_child) = screen_child {
+            let _ = screen_child.kill();
+            let _ = screen_child.wait();
+        }
+        return Err(error);
+    }
+    Ok(tracks)
+}
+
+/// Stop the screen capture and every backend track of the current segment.
+///
+/// Every process is told to stop before any is waited on, so the segment's tracks end together.
+#[cfg(target_os = "linux")]
+fn stop_linux_segment(
+    session: &mut RecordingSession,
+    action: &str,
+    file_timeout: Duration,
+) -> Result<(), AppError> {
+    // Ending the frame feed lets ffmpeg see the end of its input.
+    if let Some(mut feed) = session.pipewire_feed.take() {
+        let _ = feed.kill();
+        let _ = feed.wait();
+    }
+    let mut stopping = Vec::new();
+    if let Some(child) = session.ffmpeg_child.take() {
+        stopping.push((
+            child,
+            Some(session.current_segment_path.clone()),
+            format!("{action} recording"),
+        ));
+    }
+    for (child, segment_path, track) in [
+        (
+            session.camera_ffmpeg_child.take(),
+            session.camera_segments.last(),
+            "the camera",
+        ),
+        (
+            session.microphone_ffmpeg_child.take(),
+            session.microphone_segments.last(),
+            "the microphone",
+        ),
+        (
+            session.system_audio_ffmpeg_child.take(),
+            session.system_audio_segments.last(),
+            "system audio",
+        ),
+    ] {
+        if let Some(child) = child {
+            stopping.push((child, segment_path.cloned(), format!("{action} {track}")));
+        }
+    }
+
+    for (child, _, _) in stopping.iter_mut() {
+        request_linux_ffmpeg_stop(child);
+    }
+    let mut result = Ok(());
+    let mut output_paths = Vec::new();
+    for (mut child, output_path, context) in stopping {
+        match wait_for_linux_ffmpeg_exit(&mut child, &context) {
+            Ok(()) => output_paths.extend(output_path),
+            Err(error) if result.is_ok() => result = Err(error),
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+    result?;
+    wait_for_files_ready_linux(&output_paths, file_timeout)
+}
+
+#[cfg(target_os = "linux")]
+fn read_child_stderr(child: &mut Child) -> String {
+    if let Some(mut stderr) = child.stderr.take() {
+        let mut buffer = String::new();
+        if stderr.read_to_string(&mut buffer).is_ok() {
+            return buffer.trim().to_string();
+        }
+    }
+    String::new()
+}
+
+/// Log a long-running child's stderr line by line on a thread that ends when the child exits.
+#[cfg(target_os = "linux")]
+fn log_child_stderr(child: &mut Child, process: &'static str) {
+    let Some(stderr) = child.stderr.take() else {
+        return;
+    };
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            eprintln!("{process}: {line}");
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn ensure_linux_ffmpeg_started(child: &mut Child, context: &str) -> Result<(), AppError> {
+    ensure_linux_child_started(child, "ffmpeg", context)
+}
+
+#[cfg(target_os = "linux")]
+fn ensure_linux_child_started(
+    child: &mut Child,
+    process: &str,
+    context: &str,
+) -> Result<(), AppError> {
+    ensure_linux_child_started_since(child, process, context, Instant::now())
+}
+
+/// Check a process is still running 200ms after `started`, so processes spawned together
+/// share one startup window.
+#[cfg(target_os = "linux")]
+fn ensure_linux_child_started_since(
+    child: &mut Child,
+    process: &str,
+    context: &str,
+    started: Instant,
+) -> Result<(), AppError> {
+    let timeout = Duration::from_millis(200);
+    let poll_interval = Duration::from_millis(20);
 
     loop {
         if let Some(status) = child.try_wait().map_err(|error| {
-            AppError::Io(format!("Failed to poll ffmpeg startup state: {error}"))
+            AppError::Io(format!("Failed to poll {process} startup state: {error}"))
         })? {
             let stderr = read_child_stderr(child);
             let detail = if stderr.is_empty() {
@@ -1544,7 +2594,7 @@ fn ensure_linux_ffmpeg_started(child: &mut Child, context: &str) -> Result<(), A
                 stderr
             };
             return Err(AppError::Message(format!(
-                "ffmpeg exited immediately while {context}: {detail}"
+                "{process} exited immediately while {context}: {detail}"
             )));
         }
 
@@ -1557,11 +2607,21 @@ fn ensure_linux_ffmpeg_started(child: &mut Child, context: &str) -> Result<(), A
 
 #[cfg(target_os = "linux")]
 fn stop_linux_ffmpeg_capture(child: &mut Child, context: &str) -> Result<(), AppError> {
+    request_linux_ffmpeg_stop(child);
+    wait_for_linux_ffmpeg_exit(child, context)
+}
+
+/// Ask ffmpeg to finish its output without waiting for it.
+#[cfg(target_os = "linux")]
+fn request_linux_ffmpeg_stop(child: &mut Child) {
     if let Some(stdin) = child.stdin.as_mut() {
         let _ = stdin.write_all(b"q\n");
         let _ = stdin.flush();
     }
+}
 
+#[cfg(target_os = "linux")]
+fn wait_for_linux_ffmpeg_exit(child: &mut Child, context: &str) -> Result<(), AppError> {
     let timeout = Duration::from_secs(20);
     let started = Instant::now();
     loop {
@@ -1586,44 +2646,84 @@ fn stop_linux_ffmpeg_capture(child: &mut Child, context: &str) -> Result<(), App
         }
 
         if started.elapsed() >= timeout {
+            // Fragmented MP4 and FLAC output stay readable up to the last flushed data.
             let _ = child.kill();
             let _ = child.wait();
-            return Err(AppError::Message(format!(
-                "Timed out waiting for ffmpeg while {context}"
-            )));
+            eprintln!("Timed out waiting for ffmpeg while {context}; killed it and kept the output written so far");
+            return Ok(());
         }
         thread::sleep(Duration::from_millis(120));
     }
 }
 
 #[cfg(target_os = "linux")]
-fn wait_for_file_ready_linux(path: &PathBuf, timeout: Duration) -> Result<(), AppError> {
+fn start_linux_cursor_telemetry(
+    project_dir: &Path,
+    selection: &LinuxCaptureSelection,
+    base_ms: u64,
+) -> Option<CursorTelemetryRecorder> {
+    let area = CursorCaptureArea {
+        window_id: selection.window_id,
+        x: selection.x,
+        y: selection.y,
+        width: selection.source_width,
+        height: selection.source_height,
+    };
+    match CursorTelemetryRecorder::start(&cursor_log_path(project_dir), area, base_ms) {
+        Ok(recorder) => Some(recorder),
+        Err(error) => {
+            eprintln!(
+                "Cursor telemetry is unavailable for this recording: {}",
+                error
+            );
+            None
+        }
+    }
+}
+
+/// Wait until every file has stopped growing, polling them together.
+#[cfg(target_os = "linux")]
+fn wait_for_files_ready_linux(paths: &[PathBuf], timeout: Duration) -> Result<(), AppError> {
     let started = Instant::now();
-    let mut last_size = 0_u64;
-    let mut stable_checks = 0_u8;
+    // Last size and number of consecutive checks it stayed the same, per file.
+    let mut progress = vec![(0_u64, 0_u8); paths.len()];
     while started.elapsed() < timeout {
-        match std::fs::metadata(path) {
-            Ok(metadata) => {
-                let size = metadata.len();
-                if size > 1024 && size == last_size {
-                    stable_checks = stable_checks.saturating_add(1);
-                    if stable_checks >= 3 {
-                        return Ok(());
+        for (path, (last_size, stable_checks)) in paths.iter().zip(progress.iter_mut()) {
+            if *stable_checks >= 3 {
+                continue;
+            }
+            match std::fs::metadata(path) {
+                Ok(metadata) => {
+                    let size = metadata.len();
+                    if size > 1024 && size == *last_size {
+                        *stable_checks += 1;
+                    } else {
+                        *stable_checks = 0;
+                        *last_size = size;
                     }
-                } else {
-                    stable_checks = 0;
-                    last_size = size;
+                }
+                Err(_) => {
+                    *stable_checks = 0;
                 }
             }
-            Err(_) => {
-                stable_checks = 0;
-            }
+        }
+        if progress
+            .iter()
+            .all(|(_, stable_checks)| *stable_checks >= 3)
+        {
+            return Ok(());
         }
         thread::sleep(Duration::from_millis(200));
     }
+    let pending = paths
+        .iter()
+        .zip(&progress)
+        .filter(|(_, (_, stable_checks))| *stable_checks < 3)
+        .map(|(path, _)| path.display().to_string())
+        .collect::<Vec<_>>();
     Err(AppError::Message(format!(
         "Timed out waiting for Linux capture output finalization: {}",
-        path.display()
+        pending.join(", ")
     )))
 }
 
@@ -1631,13 +2731,34 @@ fn wait_for_file_ready_linux(path: &PathBuf, timeout: Duration) -> Result<(), Ap
 pub fn start_recording(
     state: &SharedRecorderState,
     options: RecordingOptions,
+    project_id: String,
 ) -> Result<StartRecordingResult, AppError> {
-    let project_id = Uuid::new_v4().to_string();
-    let mut selection = resolve_linux_capture_selection(
-        &options.source_id,
-        options.source_type,
-        options.preferred_display_ordinal,
-    )?;
+    if options.capture_screen && !options.source_type.is_screen() {
+        return Err(device_source_error());
+    }
+    validate_capture_tracks(&options)?;
+    if let Some(buffer_seconds) = options.replay_buffer_seconds {
+        validate_replay_buffer_seconds(buffer_seconds)?;
+        if options.capture_camera || options.capture_microphone || options.capture_system_audio {
+            return Err(AppError::Message(
+                "Replay buffer recording captures the screen only; turn off the camera, microphone and system audio"
+                    .to_string(),
+            ));
+        }
+    }
+    let portal_screen_cast = if options.capture_screen && wayland_session_active() {
+        Some(PortalScreenCast::start(options.source_type)?)
+    } else {
+        None
+    };
+    let mut selection = match portal_screen_cast.as_ref() {
+        Some(portal_screen_cast) => Some(linux_portal_selection(
+            options.source_type,
+            portal_screen_cast,
+        )),
+        None if options.capture_screen => Some(resolve_linux_capture_selection(&options)?),
+        None => None,
+    };
 
     let mut state_guard = state
         .lock()
@@ -1647,36 +2768,90 @@ pub fn start_recording(
     block_on_io(tokio::fs::create_dir_all(&project_dir))?
         .map_err(|error| AppError::Io(format!("Failed to create project dir: {}", error)))?;
 
-    let screen_video_path = project_dir.join("screen.mp4");
-    let camera_video_path = if options.capture_camera {
-        Some(project_dir.join("camera.webm"))
-    } else {
-        None
+    // A replay buffer writes rolling segments into the project dir until a replay is saved.
+    let screen_video_path = match options.replay_buffer_seconds {
+        Some(_) => replay_segment_pattern(&project_dir),
+        None => project_dir.join("screen.mp4"),
     };
-    let microphone_audio_path = if options.capture_microphone {
-        Some(project_dir.join("microphone.webm"))
-    } else {
-        None
+    let camera_device = linux_camera_device(&options);
+    let camera_video_path = match camera_device {
+        Some(_) => Some(camera_segment_path(&project_dir, 0)),
+        None if options.capture_camera => Some(project_dir.join("camera.webm")),
+        None => None,
+    };
+    let microphone_device = linux_microphone_device(&options);
+    let microphone_audio_path = match microphone_device {
+        Some(_) => Some(microphone_segment_path(&project_dir, 0)),
+        None if options.capture_microphone => Some(project_dir.join("microphone.webm")),
+        None => None,
     };
 
-    let child = spawn_linux_ffmpeg_capture(&options, &selection, &screen_video_path)?;
+    let (mut child, mut pipewire_feed) = match selection.as_ref() {
+        Some(selection) => {
+            let (child, pipewire_feed) = spawn_linux_capture(
+                &options,
+                selection,
+                &screen_video_path,
+                portal_screen_cast.as_ref(),
+            )?;
+            (Some(child), pipewire_feed)
+        }
+        None => (None, None),
+    };
+    let health_monitor = child
+        .as_mut()
+        .and_then(|child| child.stdout.take())
+        .map(RecordingHealthMonitor::start);
     let recording_start_time_ms = chrono::Utc::now().timestamp_millis();
-    let (capture_width, capture_height) = resolve_output_dimensions_linux(
-        selection.source_width,
-        selection.source_height,
-        options.quality_preset,
-    );
+    let tracks = spawn_linux_segment_tracks(
+        &options,
+        &project_dir,
+        0,
+        child.as_mut(),
+        &mut pipewire_feed,
+    )?;
+    let camera_segments = camera_device
+        .map(|_| vec![camera_segment_path(&project_dir, 0)])
+        .unwrap_or_default();
+    let microphone_segments = microphone_device
+        .map(|_| vec![microphone_segment_path(&project_dir, 0)])
+        .unwrap_or_default();
+    let system_audio_path = options
+        .capture_system_audio
+        .then(|| system_audio_segment_path(&project_dir, 0));
+    // The portal embeds the cursor but exposes no pointer position to poll, and a replay
+    // buffer would have to trim the log along with its segments.
+    let cursor_telemetry = selection
+        .as_ref()
+        .filter(|_| portal_screen_cast.is_none() && options.replay_buffer_seconds.is_none())
+        .and_then(|selection| start_linux_cursor_telemetry(&project_dir, selection, 0));
+    let replay_pruner = options.replay_buffer_seconds.map(|buffer_seconds| {
+        ReplayBufferPruner::start(project_dir.clone(), replay_segments_to_keep(buffer_seconds))
+    });
+    let (capture_width, capture_height) = selection
+        .as_ref()
+        .map(|selection| {
+            resolve_output_dimensions_linux(
+                selection.source_width,
+                selection.source_height,
+                options.quality_preset,
+            )
+        })
+        .unwrap_or((0, 0));
     let capture_fps = linux_fps(options.quality_preset);
 
     let mut session_options = options.clone();
-    session_options.source_id = selection.source_id.clone();
-    session_options.preferred_display_ordinal = selection.preferred_display_ordinal;
+    if let Some(selection) = selection.as_ref() {
+        session_options.source_id = selection.source_id.clone();
+        session_options.preferred_display_ordinal = selection.preferred_display_ordinal;
+    }
 
     let session = RecordingSession {
         project_id: project_id.clone(),
         options: session_options,
         state: RecordingState::Recording,
-        screen_video_path: screen_video_path.clone(),
+        project_dir: project_dir.clone(),
+        screen_video_path: options.capture_screen.then(|| screen_video_path.clone()),
         camera_video_path: camera_video_path.clone(),
         microphone_audio_path: microphone_audio_path.clone(),
         start_time: chrono::Utc::now(),
@@ -1686,23 +2861,72 @@ pub fn start_recording(
         capture_height,
         capture_fps,
         recording_codec: options.codec,
-        screen_segments: vec![screen_video_path.clone()],
+        screen_segments: if options.capture_screen && replay_pruner.is_none() {
+            vec![screen_video_path.clone()]
+        } else {
+            Vec::new()
+        },
+        camera_segments,
+        microphone_segments,
+        system_audio_segments: system_audio_path.iter().cloned().collect(),
+        system_audio_path,
+        system_audio_offset_ms: tracks
+            .system_audio_started_ms
+            .map(|started_ms| started_ms - recording_start_time_ms),
         current_segment_path: screen_video_path.clone(),
         active_duration_ms: 0,
         last_resume_instant: Some(Instant::now()),
-        camera_offset_ms: None,
-        microphone_offset_ms: None,
-        ffmpeg_child: Some(child),
+        camera_offset_ms: tracks
+            .camera_started_ms
+            .map(|started_ms| started_ms - recording_start_time_ms),
+        microphone_offset_ms: tracks
+            .microphone_started_ms
+            .map(|started_ms| started_ms - recording_start_time_ms),
+        segment_offsets: SegmentOffsets {
+            camera_ms: tracks
+                .camera_started_ms
+                .map(|started_ms| started_ms - recording_start_time_ms)
+                .into_iter()
+                .collect(),
+            microphone_ms: tracks
+                .microphone_started_ms
+                .map(|started_ms| started_ms - recording_start_time_ms)
+                .into_iter()
+                .collect(),
+            system_audio_ms: tracks
+                .system_audio_started_ms
+                .map(|started_ms| started_ms - recording_start_time_ms)
+                .into_iter()
+                .collect(),
+        },
+        marker_times_ms: Vec::new(),
+        disk_watchdog: None,
+        ffmpeg_child: child,
+        health_monitor,
+        pipewire_feed,
+        portal_screen_cast,
+        cursor_telemetry,
+        camera_ffmpeg_child: tracks.camera,
+        microphone_ffmpeg_child: tracks.microphone,
+        system_audio_ffmpeg_child: tracks.system_audio,
+        replay_pruner,
     };
     state_guard.sessions.insert(project_id.clone(), session);
 
     Ok(StartRecordingResult {
         project_id,
-        screen_video_path: screen_video_path.to_string_lossy().to_string(),
+        screen_video_path: options
+            .capture_screen
+            .then(|| screen_video_path.to_string_lossy().to_string()),
         camera_video_path: camera_video_path.map(|path| path.to_string_lossy().to_string()),
         recording_start_time_ms,
-        resolved_source_id: selection.source_id,
-        fallback_source: selection.fallback_source.take(),
+        resolved_source_id: selection
+            .as_ref()
+            .map(|selection| selection.source_id.clone())
+            .unwrap_or_default(),
+        fallback_source: selection
+            .as_mut()
+            .and_then(|selection| selection.fallback_source.take()),
     })
 }
 
@@ -1718,16 +2942,19 @@ pub fn stop_recording(
         let session = state_guard.sessions.get_mut(project_id).ok_or_else(|| {
             AppError::Message(format!("Recording session not found: {}", project_id))
         })?;
+        ensure_not_replay_session(session)?;
+        stop_disk_space_watchdog(session);
 
         if let Some(last_resume) = session.last_resume_instant.take() {
             let elapsed = last_resume.elapsed().as_millis() as u64;
             session.active_duration_ms = session.active_duration_ms.saturating_add(elapsed);
         }
 
-        if let Some(mut child) = session.ffmpeg_child.take() {
-            stop_linux_ffmpeg_capture(&mut child, "stopping recording")?;
-            wait_for_file_ready_linux(&session.current_segment_path, Duration::from_secs(20))?;
+        if let Some(cursor_telemetry) = session.cursor_telemetry.take() {
+            cursor_telemetry.stop();
         }
+        stop_linux_segment(session, "stopping", Duration::from_secs(20))?;
+        session.portal_screen_cast = None;
         session.state = RecordingState::Stopped;
     }
 
@@ -1741,12 +2968,21 @@ pub fn stop_recording(
         screen_video_path: session.screen_video_path.clone(),
         screen_segment_paths: session.screen_segments.clone(),
         camera_video_path: session.camera_video_path.clone(),
+        camera_segment_paths: session.camera_segments.clone(),
         microphone_audio_path: session.microphone_audio_path.clone(),
+        microphone_segment_paths: session.microphone_segments.clone(),
+        system_audio_path: session.system_audio_path.clone(),
+        system_audio_segment_paths: session.system_audio_segments.clone(),
         duration_seconds: session.active_duration_ms as f64 / 1000.0,
         source_width: session.capture_width,
         source_height: session.capture_height,
         camera_offset_ms: session.camera_offset_ms,
         microphone_offset_ms: session.microphone_offset_ms,
+        system_audio_offset_ms: session.system_audio_offset_ms,
+        segment_offsets: session.segment_offsets.clone(),
+        cursor_log_path: Some(cursor_log_path(&session.project_dir)).filter(|path| path.exists()),
+        fragmented_capture: true,
+        marker_times_ms: session.marker_times_ms.clone(),
     })
 }
 
@@ -1760,6 +2996,7 @@ pub fn pause_recording(state: &SharedRecorderState, project_id: &str) -> Result<
         .get_mut(project_id)
         .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
 
+    ensure_not_replay_session(session)?;
     if session.state != RecordingState::Recording {
         return Err(AppError::Message(format!(
             "Recording is not active for project {}",
@@ -1767,14 +3004,17 @@ pub fn pause_recording(state: &SharedRecorderState, project_id: &str) -> Result<
         )));
     }
 
-    let mut child = session.ffmpeg_child.take().ok_or_else(|| {
-        AppError::Message(format!(
+    if session.screen_video_path.is_some() && session.ffmpeg_child.is_none() {
+        return Err(AppError::Message(format!(
             "Recording process is unavailable for project {}",
             project_id
-        ))
-    })?;
-    stop_linux_ffmpeg_capture(&mut child, "pausing recording")?;
-    wait_for_file_ready_linux(&session.current_segment_path, Duration::from_secs(20))?;
+        )));
+    }
+    if let Some(cursor_telemetry) = session.cursor_telemetry.take() {
+        cursor_telemetry.stop();
+    }
+    session.health_monitor = None;
+    stop_linux_segment(session, "pausing", Duration::from_secs(20))?;
 
     if let Some(last_resume) = session.last_resume_instant.take() {
         let elapsed = last_resume.elapsed().as_millis() as u64;
@@ -1796,52 +3036,125 @@ pub fn resume_recording(
         .sessions
         .get_mut(project_id)
         .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    resume_session(session)
+}
 
+/// Start the next segment of a paused session; the caller holds the recorder lock.
+#[cfg(target_os = "linux")]
+fn resume_session(
+    session: &mut RecordingSession,
+) -> Result<Option<RecordingSourceFallbackUpdate>, AppError> {
     if session.state != RecordingState::Paused {
         return Err(AppError::Message(format!(
             "Recording is not paused for project {}",
-            project_id
+            session.project_id
         )));
     }
 
     session.segment_index = session.segment_index.saturating_add(1);
-    let project_dir = session.screen_video_path.parent().ok_or_else(|| {
-        AppError::Message(format!(
-            "Invalid video path for project {}: {}",
-            project_id,
-            session.screen_video_path.display()
-        ))
-    })?;
+    let project_dir = session.project_dir.clone();
     let segment_path = project_dir.join(format!("screen_part{}.mp4", session.segment_index));
 
-    let selection = resolve_linux_capture_selection(
-        &session.options.source_id,
-        session.options.source_type,
-        session.options.preferred_display_ordinal,
+    let selection = match session.portal_screen_cast.as_ref() {
+        Some(portal_screen_cast) => Some(linux_portal_selection(
+            session.options.source_type,
+            portal_screen_cast,
+        )),
+        None if session.screen_video_path.is_some() => {
+            Some(resolve_linux_capture_selection(&session.options)?)
+        }
+        None => None,
+    };
+    let (mut child, mut pipewire_feed) = match selection.as_ref() {
+        Some(selection) => {
+            let (child, pipewire_feed) = spawn_linux_capture(
+                &session.options,
+                selection,
+                &segment_path,
+                session.portal_screen_cast.as_ref(),
+            )?;
+            (Some(child), pipewire_feed)
+        }
+        None => (None, None),
+    };
+    let health_monitor = child
+        .as_mut()
+        .and_then(|child| child.stdout.take())
+        .map(RecordingHealthMonitor::start);
+    let segment_started_ms = chrono::Utc::now().timestamp_millis();
+    let tracks = spawn_linux_segment_tracks(
+        &session.options,
+        &project_dir,
+        session.segment_index,
+        child.as_mut(),
+        &mut pipewire_feed,
     )?;
-    let child = spawn_linux_ffmpeg_capture(&session.options, &selection, &segment_path)?;
+    let cursor_telemetry = selection
+        .as_ref()
+        .filter(|_| session.portal_screen_cast.is_none())
+        .and_then(|selection| {
+            start_linux_cursor_telemetry(&project_dir, selection, session.active_duration_ms)
+        });
 
     let mut fallback_update = None;
-    session.options.source_id = selection.source_id.clone();
-    session.options.preferred_display_ordinal = selection.preferred_display_ordinal;
-    if let Some(fallback_source) = selection.fallback_source {
-        fallback_update = Some(RecordingSourceFallbackUpdate {
-            source_type: selection.source_type,
-            fallback_source,
-        });
-    }
+    if let Some(selection) = selection {
+        session.options.source_id = selection.source_id.clone();
+        session.options.preferred_display_ordinal = selection.preferred_display_ordinal;
+        if let Some(fallback_source) = selection.fallback_source {
+            fallback_update = Some(RecordingSourceFallbackUpdate {
+                source_type: selection.source_type,
+                fallback_source,
+            });
+        }
 
-    let (capture_width, capture_height) = resolve_output_dimensions_linux(
-        selection.source_width,
-        selection.source_height,
-        session.options.quality_preset,
-    );
-    session.capture_width = capture_width;
-    session.capture_height = capture_height;
+        let (capture_width, capture_height) = resolve_output_dimensions_linux(
+            selection.source_width,
+            selection.source_height,
+            session.options.quality_preset,
+        );
+        session.capture_width = capture_width;
+        session.capture_height = capture_height;
+        session.current_segment_path = segment_path.clone();
+        session.screen_segments.push(segment_path);
+    }
     session.capture_fps = linux_fps(session.options.quality_preset);
-    session.ffmpeg_child = Some(child);
-    session.current_segment_path = segment_path.clone();
-    session.screen_segments.push(segment_path);
+    session.ffmpeg_child = child;
+    session.health_monitor = health_monitor;
+    session.pipewire_feed = pipewire_feed;
+    session.cursor_telemetry = cursor_telemetry;
+    if let Some(started_ms) = tracks.camera_started_ms {
+        session
+            .camera_segments
+            .push(camera_segment_path(&project_dir, session.segment_index));
+        session
+            .segment_offsets
+            .camera_ms
+            .push(started_ms - segment_started_ms);
+    }
+    if let Some(started_ms) = tracks.microphone_started_ms {
+        session
+            .microphone_segments
+            .push(microphone_segment_path(&project_dir, session.segment_index));
+        session
+            .segment_offsets
+            .microphone_ms
+            .push(started_ms - segment_started_ms);
+    }
+    if let Some(started_ms) = tracks.system_audio_started_ms {
+        session
+            .system_audio_segments
+            .push(system_audio_segment_path(
+                &project_dir,
+                session.segment_index,
+            ));
+        session
+            .segment_offsets
+            .system_audio_ms
+            .push(started_ms - segment_started_ms);
+    }
+    session.camera_ffmpeg_child = tracks.camera;
+    session.microphone_ffmpeg_child = tracks.microphone;
+    session.system_audio_ffmpeg_child = tracks.system_audio;
     session.last_resume_instant = Some(Instant::now());
     session.state = RecordingState::Recording;
 
@@ -1879,14 +3192,28 @@ pub fn get_recording_source_status(
     let state_guard = state
         .lock()
         .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
-    let session = if let Some(session) = state_guard.sessions.get(project_id) {
+    // Camera-only and audio-only takes have no source to lose.
+    let session = if let Some(session) = state_guard
+        .sessions
+        .get(project_id)
+        .filter(|session| session.screen_video_path.is_some())
+    {
         session
     } else {
         return Ok(None);
     };
 
+    if session.portal_screen_cast.is_some() {
+        return Ok(Some(RecordingSourceStatus {
+            source_type: session.options.source_type,
+            source_id: session.options.source_id.clone(),
+            available: true,
+            fallback_source: None,
+        }));
+    }
+
     match session.options.source_type {
-        SourceType::Display => {
+        SourceType::Display | SourceType::Region => {
             let requested_display_id =
                 parse_linux_source_id(&session.options.source_id, "display")?;
             let displays = linux_list_display_sources()?;
@@ -1895,7 +3222,7 @@ pub fn get_recording_source_status(
                 .any(|display| display.id == requested_display_id)
             {
                 return Ok(Some(RecordingSourceStatus {
-                    source_type: SourceType::Display,
+                    source_type: session.options.source_type,
                     source_id: session.options.source_id.clone(),
                     available: true,
                     fallback_source: None,
@@ -1903,7 +3230,7 @@ pub fn get_recording_source_status(
             }
             if displays.is_empty() {
                 return Ok(Some(RecordingSourceStatus {
-                    source_type: SourceType::Display,
+                    source_type: session.options.source_type,
                     source_id: session.options.source_id.clone(),
                     available: false,
                     fallback_source: None,
@@ -1919,7 +3246,7 @@ pub fn get_recording_source_status(
                 )
             })?;
             Ok(Some(RecordingSourceStatus {
-                source_type: SourceType::Display,
+                source_type: session.options.source_type,
                 source_id: session.options.source_id.clone(),
                 available: false,
                 fallback_source: Some(RecordingSourceFallback {
@@ -1928,6 +3255,7 @@ pub fn get_recording_source_status(
                 }),
             }))
         }
+        SourceType::Camera | SourceType::Microphone => Err(device_source_error()),
         SourceType::Window => {
             let requested_window_id = parse_linux_source_id(&session.options.source_id, "window")?;
             let windows = linux_list_window_sources()?;
@@ -1959,6 +3287,114 @@ pub fn get_recording_source_status(
     }
 }
 
+#[cfg(target_os = "linux")]
+pub fn detect_capture_interruption(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<Option<CaptureInterruption>, AppError> {
+    {
+        let mut state_guard = state
+            .lock()
+            .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+        let Some(session) = state_guard.sessions.get_mut(project_id) else {
+            return Ok(None);
+        };
+        if session.state != RecordingState::Recording
+            || session.options.replay_buffer_seconds.is_some()
+        {
+            return Ok(None);
+        }
+        if let Some(child) = session.ffmpeg_child.as_mut() {
+            let exited = child.try_wait().map_err(|error| {
+                AppError::Io(format!("Failed to poll ffmpeg process state: {error}"))
+            })?;
+            if exited.is_some() {
+                return Ok(Some(CaptureInterruption::CaptureExited));
+            }
+        }
+    }
+    Ok(source_lost_interruption(get_recording_source_status(
+        state, project_id,
+    )?))
+}
+
+/// Close the current segment and continue on a new one, re-resolving the source.
+///
+/// Unlike a pause, failures to close the old capture are logged rather than returned, since
+/// ffmpeg has usually already exited. A segment that never received data is dropped.
+#[cfg(target_os = "linux")]
+pub fn switch_capture_segment(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<Option<RecordingSourceFallbackUpdate>, AppError> {
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let session = state_guard
+        .sessions
+        .get_mut(project_id)
+        .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    ensure_not_replay_session(session)?;
+    if session.state != RecordingState::Recording {
+        return Err(AppError::Message(format!(
+            "Recording is not active for project {}",
+            project_id
+        )));
+    }
+
+    if let Some(cursor_telemetry) = session.cursor_telemetry.take() {
+        cursor_telemetry.stop();
+    }
+    session.health_monitor = None;
+    if let Err(error) = stop_linux_segment(session, "switching source for", Duration::from_secs(20))
+    {
+        eprintln!("Interrupted segment may be incomplete: {}", error);
+    }
+    let segment_is_empty = std::fs::metadata(&session.current_segment_path)
+        .map(|metadata| metadata.len() == 0)
+        .unwrap_or(true);
+    if segment_is_empty && session.screen_video_path.as_ref() != Some(&session.current_segment_path)
+    {
+        let _ = std::fs::remove_file(&session.current_segment_path);
+        session
+            .screen_segments
+            .retain(|path| path != &session.current_segment_path);
+        // Drop the backend tracks' matching segments too, so the rest stay paired.
+        let segment_index = session.segment_index;
+        for (segments, offsets, segment_path) in [
+            (
+                &mut session.camera_segments,
+                &mut session.segment_offsets.camera_ms,
+                camera_segment_path(&session.project_dir, segment_index),
+            ),
+            (
+                &mut session.microphone_segments,
+                &mut session.segment_offsets.microphone_ms,
+                microphone_segment_path(&session.project_dir, segment_index),
+            ),
+            (
+                &mut session.system_audio_segments,
+                &mut session.segment_offsets.system_audio_ms,
+                system_audio_segment_path(&session.project_dir, segment_index),
+            ),
+        ] {
+            if segments.last() == Some(&segment_path) {
+                segments.pop();
+                offsets.pop();
+                let _ = std::fs::remove_file(&segment_path);
+            }
+        }
+    }
+
+    if let Some(last_resume) = session.last_resume_instant.take() {
+        let elapsed = last_resume.elapsed().as_millis() as u64;
+        session.active_duration_ms = session.active_duration_ms.saturating_add(elapsed);
+    }
+    session.state = RecordingState::Paused;
+    // Resume under the same lock so no other command sees the take paused in between.
+    resume_session(session)
+}
+
 #[cfg(target_os = "linux")]
 pub fn get_recording_state(
     state: &SharedRecorderState,
@@ -1986,10 +3422,173 @@ pub fn get_recording_snapshot(
         RecordingSessionSnapshot {
             state: session.state,
             elapsed_seconds,
+            health: session
+                .health_monitor
+                .as_ref()
+                .filter(|_| session.state == RecordingState::Recording)
+                .and_then(RecordingHealthMonitor::latest),
         }
     }))
 }
 
+#[cfg(target_os = "linux")]
+fn ensure_not_replay_session(session: &RecordingSession) -> Result<(), AppError> {
+    if session.options.replay_buffer_seconds.is_some() {
+        return Err(AppError::Message(format!(
+            "Recording {} is a replay buffer; save a replay or stop the buffer instead",
+            session.project_id
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn replay_buffer_seconds(session: &RecordingSession) -> Result<u32, AppError> {
+    session.options.replay_buffer_seconds.ok_or_else(|| {
+        AppError::Message(format!(
+            "Recording {} is not a replay buffer",
+            session.project_id
+        ))
+    })
+}
+
+/// Stop the capture and its pruner so every segment left in the buffer is complete.
+#[cfg(target_os = "linux")]
+fn stop_linux_replay_capture(session: &mut RecordingSession, context: &str) {
+    if let Some(replay_pruner) = session.replay_pruner.take() {
+        replay_pruner.stop();
+    }
+    if let Some(mut child) = session.ffmpeg_child.take() {
+        if let Err(error) = stop_linux_capture(&mut child, session.pipewire_feed.take(), context) {
+            eprintln!(
+                "Replay capture for {} did not stop cleanly: {}",
+                session.project_id, error
+            );
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn remove_replay_buffer_dir(session: &RecordingSession) {
+    let buffer_dir = &session.project_dir;
+    if let Err(error) = std::fs::remove_dir_all(buffer_dir) {
+        eprintln!(
+            "Failed to remove replay buffer {}: {}",
+            buffer_dir.display(),
+            error
+        );
+    }
+}
+
+/// Length of a replay segment as ffprobe reads it, if it can.
+#[cfg(target_os = "linux")]
+fn probe_replay_segment_seconds(path: &Path) -> Option<f64> {
+    let output = std::process::Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("format=duration")
+        .arg("-of")
+        .arg("default=noprint_wrappers=1:nokey=1")
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_ffprobe_duration_seconds(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Copy the retained replay window into a new take while the buffer keeps recording.
+///
+/// Waits for the segment being written to close, without holding the recorder lock. The result
+/// describes the new take under its own project id, ready for finalization.
+#[cfg(target_os = "linux")]
+pub fn save_replay(
+    state: &SharedRecorderState,
+    project_id: &str,
+) -> Result<StopRecordingResult, AppError> {
+    let (recordings_dir, buffer_dir, buffer_seconds, source_width, source_height) = {
+        let state_guard = state
+            .lock()
+            .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+        let session = state_guard.sessions.get(project_id).ok_or_else(|| {
+            AppError::Message(format!("Recording session not found: {}", project_id))
+        })?;
+        let buffer_seconds = replay_buffer_seconds(session)?;
+        if session.state != RecordingState::Recording {
+            return Err(AppError::Message(format!(
+                "Recording is not active for project {}",
+                project_id
+            )));
+        }
+        (
+            state_guard.recordings_dir.clone(),
+            session.project_dir.clone(),
+            buffer_seconds,
+            session.capture_width,
+            session.capture_height,
+        )
+    };
+
+    let last_index = wait_for_closed_replay_segment(
+        &buffer_dir,
+        Duration::from_secs(u64::from(REPLAY_SEGMENT_SECONDS) * 2),
+    )?;
+    let replay_project_id = Uuid::new_v4().to_string();
+    let screen_segment_paths = copy_replay_segments(
+        &buffer_dir,
+        &recordings_dir.join(&replay_project_id),
+        last_index,
+        replay_segments_to_keep(buffer_seconds) - 1,
+    )?;
+    let duration_seconds = screen_segment_paths
+        .iter()
+        .map(|path| probe_replay_segment_seconds(path).unwrap_or(f64::from(REPLAY_SEGMENT_SECONDS)))
+        .sum();
+
+    Ok(StopRecordingResult {
+        project_id: replay_project_id,
+        screen_video_path: Some(screen_segment_paths[0].clone()),
+        screen_segment_paths,
+        camera_video_path: None,
+        camera_segment_paths: Vec::new(),
+        microphone_audio_path: None,
+        microphone_segment_paths: Vec::new(),
+        system_audio_path: None,
+        system_audio_segment_paths: Vec::new(),
+        duration_seconds,
+        source_width,
+        source_height,
+        camera_offset_ms: None,
+        microphone_offset_ms: None,
+        system_audio_offset_ms: None,
+        segment_offsets: SegmentOffsets::default(),
+        cursor_log_path: None,
+        fragmented_capture: true,
+        marker_times_ms: Vec::new(),
+    })
+}
+
+/// Stop a replay buffer and delete its unsaved footage.
+#[cfg(target_os = "linux")]
+pub fn stop_replay_buffer(state: &SharedRecorderState, project_id: &str) -> Result<(), AppError> {
+    let mut state_guard = state
+        .lock()
+        .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
+    let session = state_guard
+        .sessions
+        .get(project_id)
+        .ok_or_else(|| AppError::Message(format!("Recording session not found: {}", project_id)))?;
+    replay_buffer_seconds(session)?;
+    if let Some(mut session) = state_guard.sessions.remove(project_id) {
+        stop_disk_space_watchdog(&mut session);
+        stop_linux_replay_capture(&mut session, "stopping the replay buffer");
+        session.portal_screen_cast = None;
+        session.state = RecordingState::Stopped;
+        remove_replay_buffer_dir(&session);
+    }
+    Ok(())
+}
+
 #[cfg(target_os = "linux")]
 pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppError> {
     let mut state_guard = state
@@ -1997,25 +3596,23 @@ pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppE
         .map_err(|e| AppError::Lock(format!("Lock error: {}", e)))?;
 
     for session in state_guard.sessions.values_mut() {
-        if let Some(mut child) = session.ffmpeg_child.take() {
-            if let Err(error) = stop_linux_ffmpeg_capture(&mut child, "cleaning up recording") {
-                eprintln!(
-                    "Failed to stop active ffmpeg capture during cleanup for {}: {}",
-                    session.project_id, error
-                );
-            }
+        stop_disk_space_watchdog(session);
+        if session.replay_pruner.is_some() {
+            stop_linux_replay_capture(session, "cleaning up the replay buffer");
+            session.state = RecordingState::Stopped;
+            remove_replay_buffer_dir(session);
+            continue;
         }
-        session.state = RecordingState::Stopped;
-        if let Err(error) =
-            wait_for_file_ready_linux(&session.current_segment_path, Duration::from_secs(5))
-        {
+        if let Some(cursor_telemetry) = session.cursor_telemetry.take() {
+            cursor_telemetry.stop();
+        }
+        if let Err(error) = stop_linux_segment(session, "cleaning up", Duration::from_secs(5)) {
             eprintln!(
-                "Recording file was not finalized during Linux cleanup for {} ({}): {}",
-                session.project_id,
-                session.current_segment_path.display(),
-                error
+                "Recording was not finalized during Linux cleanup for {}: {}",
+                session.project_id, error
             );
         }
+        session.state = RecordingState::Stopped;
     }
 
     state_guard.sessions.clear();
@@ -2027,6 +3624,7 @@ pub fn cleanup_active_recordings(state: &SharedRecorderState) -> Result<(), AppE
 pub fn start_recording(
     _state: &SharedRecorderState,
     _options: RecordingOptions,
+    _project_id: String,
 ) -> Result<StartRecordingResult, AppError> {
     Err(AppError::Message(
         "Screen capture is only supported on macOS and Linux".to_string(),
@@ -2072,6 +3670,16 @@ pub fn set_media_offsets(
     ))
 }
 
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn add_recording_marker(
+    _state: &SharedRecorderState,
+    _project_id: &str,
+) -> Result<f64, AppError> {
+    Err(AppError::Message(
+        "Screen capture is only supported on macOS and Linux".to_string(),
+    ))
+}
+
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
 pub fn get_recording_source_status(
     _state: &SharedRecorderState,
@@ -2096,11 +3704,42 @@ pub fn get_recording_snapshot(
     Ok(None)
 }
 
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn detect_capture_interruption(
+    _state: &SharedRecorderState,
+    _project_id: &str,
+) -> Result<Option<CaptureInterruption>, AppError> {
+    Ok(None)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn switch_capture_segment(
+    _state: &SharedRecorderState,
+    _project_id: &str,
+) -> Result<Option<RecordingSourceFallbackUpdate>, AppError> {
+    Err(AppError::Message(
+        "Screen capture is only supported on macOS and Linux".to_string(),
+    ))
+}
+
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
 pub fn cleanup_active_recordings(_state: &SharedRecorderState) -> Result<(), AppError> {
     Ok(())
 }
 
+#[cfg(not(target_os = "linux"))]
+pub fn save_replay(
+    _state: &SharedRecorderState,
+    _project_id: &str,
+) -> Result<StopRecordingResult, AppError> {
+    Err(replay_unsupported_error())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn stop_replay_buffer(_state: &SharedRecorderState, _project_id: &str) -> Result<(), AppError> {
+    Err(replay_unsupported_error())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2114,6 +3753,59 @@ mod tests {
         assert_eq!(resolve_display_fallback_index(0, Some(1)), 0);
     }
 
+    #[test]
+    fn treats_a_lost_source_as_an_interruption_only_with_a_fallback() {
+        let status = |available: bool, fallback: bool| RecordingSourceStatus {
+            source_type: SourceType::Display,
+            source_id: "1".to_string(),
+            available,
+            fallback_source: fallback.then(|| RecordingSourceFallback {
+                source_id: "2".to_string(),
+                source_ordinal: Some(0),
+            }),
+        };
+
+        assert_eq!(source_lost_interruption(None), None);
+        assert_eq!(source_lost_interruption(Some(status(true, false))), None);
+        assert_eq!(source_lost_interruption(Some(status(false, false))), None);
+        assert_eq!(
+            source_lost_interruption(Some(status(false, true))),
+            Some(CaptureInterruption::SourceLost)
+        );
+    }
+
+    #[test]
+    fn backs_off_capture_restarts_and_gives_up_until_the_capture_recovers() {
+        let mut backoff = CaptureRestartBackoff::default();
+        let start = Instant::now();
+        let delays = (0..MAX_CAPTURE_RESTARTS)
+            .map(|_| backoff.next_delay(start).map(|delay| delay.as_secs()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(delays, vec![Some(2), Some(4), Some(8), Some(16), Some(32)]);
+        assert_eq!(backoff.next_delay(start), None);
+        // A capture that ran for a while after its last restart starts over.
+        assert_eq!(
+            backoff
+                .next_delay(start + Duration::from_secs(32) + CAPTURE_RESTART_RESET_AFTER)
+                .map(|delay| delay.as_secs()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn requires_another_track_when_the_screen_is_off() {
+        let mut options = build_test_session(RecordingState::Recording, 0, None).options;
+        assert!(validate_capture_tracks(&options).is_ok());
+
+        options.capture_screen = false;
+        assert!(validate_capture_tracks(&options).is_err());
+        options.capture_microphone = true;
+        assert!(validate_capture_tracks(&options).is_ok());
+        options.replay_buffer_seconds = Some(30);
+        assert!(validate_capture_tracks(&options).is_err());
+    }
+
     #[test]
     fn resolves_window_fallback_id_from_available_sources() {
         assert_eq!(
@@ -2127,6 +3819,22 @@ mod tests {
         assert_eq!(resolve_window_fallback_id(99, &[]), None);
     }
 
+    #[test]
+    fn validates_capture_region_against_display_bounds() {
+        let region = |x, y, width, height| CaptureRegion {
+            x,
+            y,
+            width,
+            height,
+        };
+        assert!(validate_capture_region(&region(0, 0, 1280, 720), 2560, 1440).is_ok());
+        assert!(validate_capture_region(&region(1280, 720, 1280, 720), 2560, 1440).is_ok());
+        assert!(validate_capture_region(&region(1281, 0, 1280, 720), 2560, 1440).is_err());
+        assert!(validate_capture_region(&region(0, 800, 1280, 720), 2560, 1440).is_err());
+        assert!(validate_capture_region(&region(10, 10, 0, 720), 2560, 1440).is_err());
+        assert!(validate_capture_region(&region(u32::MAX, 0, 2, 2), 2560, 1440).is_err());
+    }
+
     fn build_test_session(
         state: RecordingState,
         active_duration_ms: u64,
@@ -2138,14 +3846,22 @@ mod tests {
                 source_id: "source-1".to_string(),
                 source_type: SourceType::Display,
                 preferred_display_ordinal: Some(0),
+                capture_screen: true,
                 capture_camera: false,
+                camera_device_id: None,
                 capture_microphone: false,
+                microphone_device_id: None,
                 capture_system_audio: false,
+                region: None,
+                replay_buffer_seconds: None,
+                disk_space: DiskSpacePolicy::default(),
+                auto_source_fallback: false,
                 quality_preset: RecordingQualityPreset::P1080P30,
                 codec: RecordingCodec::H264,
             },
             state,
-            screen_video_path: PathBuf::from("/tmp/screen.mp4"),
+            project_dir: PathBuf::from("/tmp"),
+            screen_video_path: Some(PathBuf::from("/tmp/screen.mp4")),
             camera_video_path: None,
             microphone_audio_path: None,
             start_time: chrono::Utc::now(),
@@ -2156,17 +3872,41 @@ mod tests {
             capture_fps: 30,
             recording_codec: RecordingCodec::H264,
             screen_segments: vec![],
+            camera_segments: vec![],
+            microphone_segments: vec![],
+            system_audio_path: None,
+            system_audio_segments: vec![],
+            system_audio_offset_ms: None,
             current_segment_path: PathBuf::from("/tmp/screen.mp4"),
             active_duration_ms,
             last_resume_instant,
             camera_offset_ms: None,
             microphone_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            marker_times_ms: Vec::new(),
+            disk_watchdog: None,
             #[cfg(target_os = "macos")]
             stream: None,
             #[cfg(target_os = "macos")]
             recording_output: None,
             #[cfg(target_os = "linux")]
             ffmpeg_child: None,
+            #[cfg(target_os = "linux")]
+            health_monitor: None,
+            #[cfg(target_os = "linux")]
+            pipewire_feed: None,
+            #[cfg(target_os = "linux")]
+            portal_screen_cast: None,
+            #[cfg(target_os = "linux")]
+            cursor_telemetry: None,
+            #[cfg(target_os = "linux")]
+            camera_ffmpeg_child: None,
+            #[cfg(target_os = "linux")]
+            microphone_ffmpeg_child: None,
+            #[cfg(target_os = "linux")]
+            system_audio_ffmpeg_child: None,
+            #[cfg(target_os = "linux")]
+            replay_pruner: None,
         }
     }
 
@@ -2187,6 +3927,44 @@ mod tests {
         assert!(elapsed >= 2_500);
     }
 
+    #[test]
+    fn markers_are_stamped_with_the_active_duration() {
+        let state: SharedRecorderState = Arc::new(Mutex::new(RecorderState {
+            sessions: HashMap::from([(
+                "test-project".to_string(),
+                build_test_session(RecordingState::Paused, 4_250, None),
+            )]),
+            recordings_dir: PathBuf::from("/tmp"),
+        }));
+
+        assert_eq!(add_recording_marker(&state, "test-project").unwrap(), 4.25);
+        assert!(add_recording_marker(&state, "missing-project").is_err());
+        let guard = state.lock().unwrap();
+        assert_eq!(guard.sessions["test-project"].marker_times_ms, vec![4_250]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn picks_a_camera_size_the_device_lists() {
+        let listing = "\
+[video4linux2,v4l2 @ 0x5581] Raw       :     yuyv422 :           YUYV 4:2:2 : 640x480 320x240 800x600
+[video4linux2,v4l2 @ 0x5581] Compressed:       mjpeg :          Motion-JPEG : 640x480 1920x1080
+[video4linux2,v4l2 @ 0x5582] Raw       :     yuyv422 :           YUYV 4:2:2 : {32-1920, 2}x{32-1080, 2}
+/dev/video0: Immediate exit requested";
+        let sizes = parse_v4l2_frame_sizes(listing);
+        assert_eq!(
+            sizes,
+            vec![(640, 480), (320, 240), (800, 600), (1920, 1080)]
+        );
+        assert_eq!(choose_camera_video_size(&sizes), Some((800, 600)));
+        assert_eq!(
+            choose_camera_video_size(&[(1920, 1080), (1280, 720)]),
+            Some((1280, 720))
+        );
+        assert_eq!(choose_camera_video_size(&[(1920, 1080)]), None);
+        assert_eq!(choose_camera_video_size(&[]), None);
+    }
+
     #[test]
     fn parses_ffprobe_duration_output() {
         assert_eq!(parse_ffprobe_duration_seconds("12.34"), Some(12.34));
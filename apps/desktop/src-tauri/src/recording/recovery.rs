@@ -0,0 +1,481 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::cursor::cursor_log_path;
+use super::StopRecordingResult;
+use crate::error::AppError;
+
+/// Written next to the media while a stopped recording still needs finalizing.
+pub const PENDING_FINALIZATION_FILE_NAME: &str = "pending_finalization.json";
+
+/// Holds the id of the process recording or finalizing a take, e.g. `open-rec record`.
+pub const RECORDING_LOCK_FILE_NAME: &str = "recording.lock";
+
+/// Marks a take as in use by this process until dropped, so the app leaves it alone.
+#[derive(Debug)]
+pub struct RecordingLock {
+    path: PathBuf,
+}
+
+impl RecordingLock {
+    pub fn acquire(project_dir: &Path) -> Result<Self, AppError> {
+        let path = project_dir.join(RECORDING_LOCK_FILE_NAME);
+        std::fs::write(&path, std::process::id().to_string()).map_err(|error| {
+            AppError::Io(format!(
+                "Failed to write recording lock {}: {}",
+                path.display(),
+                error
+            ))
+        })?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for RecordingLock {
+    fn drop(&mut self) {
+        if let Err(error) = std::fs::remove_file(&self.path) {
+            if error.kind() != std::io::ErrorKind::NotFound {
+                eprintln!(
+                    "Failed to remove recording lock {}: {}",
+                    self.path.display(),
+                    error
+                );
+            }
+        }
+    }
+}
+
+/// Whether a live process holds the take's recording lock. A lock left by a crashed process
+/// does not count.
+pub fn is_recording_locked(project_dir: &Path) -> bool {
+    let Ok(content) = std::fs::read_to_string(project_dir.join(RECORDING_LOCK_FILE_NAME)) else {
+        return false;
+    };
+    let Ok(pid) = content.trim().parse::<u32>() else {
+        return false;
+    };
+    // When in doubt keep the footage; the next launch looks again.
+    crate::is_process_running(pid).unwrap_or(true)
+}
+
+/// A recording directory with captured media but no `project.json`, usually left by a crash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoverableRecording {
+    pub project_id: String,
+    pub screen_segment_paths: Vec<PathBuf>,
+    pub camera_segment_paths: Vec<PathBuf>,
+    pub microphone_segment_paths: Vec<PathBuf>,
+    pub system_audio_segment_paths: Vec<PathBuf>,
+    pub cursor_log_path: Option<PathBuf>,
+    pub size_bytes: u64,
+}
+
+/// Segment files of one track, e.g. `screen.mp4` then `screen_part1.mp4`, `screen_part2.mp4`.
+fn find_track_segments(project_dir: &Path, track: &str, extensions: &[&str]) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(project_dir) else {
+        return Vec::new();
+    };
+    let mut segments = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let extension = path.extension()?.to_str()?;
+            if !extensions.contains(&extension) {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?;
+            let index = if stem == track {
+                0
+            } else {
+                stem.strip_prefix(track)?
+                    .strip_prefix("_part")?
+                    .parse::<u32>()
+                    .ok()?
+            };
+            Some((index, path))
+        })
+        .collect::<Vec<_>>();
+    segments.sort();
+    segments.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Index of a segment file within its track: 0 for `screen.mp4`, 2 for `screen_part2.mp4`.
+pub fn segment_part_index(path: &Path) -> u32 {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit_once("_part"))
+        .and_then(|(_, index)| index.parse().ok())
+        .unwrap_or(0)
+}
+
+fn inspect_recording_dir(project_dir: &Path) -> Option<RecoverableRecording> {
+    // Takes with a finalization journal are retried through the pending finalizations instead.
+    // A locked take is still being recorded or finalized by another process.
+    if project_dir.join("project.json").exists()
+        || project_dir.join(PENDING_FINALIZATION_FILE_NAME).exists()
+        || is_recording_locked(project_dir)
+    {
+        return None;
+    }
+    let screen_segment_paths = find_track_segments(project_dir, "screen", &["mp4"]);
+    let camera_segment_paths = find_track_segments(project_dir, "camera", &["mp4", "webm"]);
+    let microphone_segment_paths =
+        find_track_segments(project_dir, "microphone", &["flac", "webm"]);
+    let system_audio_segment_paths = find_track_segments(project_dir, "system_audio", &["flac"]);
+    // Camera-only and audio-only takes have no screen file; any captured track is worth keeping.
+    if screen_segment_paths.is_empty()
+        && camera_segment_paths.is_empty()
+        && microphone_segment_paths.is_empty()
+        && system_audio_segment_paths.is_empty()
+    {
+        return None;
+    }
+    let size_bytes = screen_segment_paths
+        .iter()
+        .chain(&camera_segment_paths)
+        .chain(&microphone_segment_paths)
+        .chain(&system_audio_segment_paths)
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    Some(RecoverableRecording {
+        project_id: project_dir.file_name()?.to_str()?.to_string(),
+        screen_segment_paths,
+        camera_segment_paths,
+        microphone_segment_paths,
+        system_audio_segment_paths,
+        cursor_log_path: Some(cursor_log_path(project_dir)).filter(|path| path.exists()),
+        size_bytes,
+    })
+}
+
+/// Scan the recordings directory for takes that never got a project file.
+///
+/// Only call this while this process records nothing, since a live session has no project file
+/// yet. Takes locked by another process, like the CLI, are skipped.
+pub fn find_recoverable_recordings(recordings_dir: &Path) -> Vec<RecoverableRecording> {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return Vec::new();
+    };
+    let mut recordings = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| inspect_recording_dir(&entry.path()))
+        .collect::<Vec<_>>();
+    recordings.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+    recordings
+}
+
+fn pending_finalization_journal_path(stop_result: &StopRecordingResult) -> Option<PathBuf> {
+    stop_result
+        .project_dir()
+        .map(|project_dir| project_dir.join(PENDING_FINALIZATION_FILE_NAME))
+}
+
+/// Persist a stop result so its finalization can be retried after a restart.
+pub fn write_pending_finalization_journal(
+    stop_result: &StopRecordingResult,
+) -> Result<(), AppError> {
+    let journal_path = pending_finalization_journal_path(stop_result).ok_or_else(|| {
+        AppError::Message(format!(
+            "No recorded media path for project {}",
+            stop_result.project_id
+        ))
+    })?;
+    let content = serde_json::to_string_pretty(stop_result).map_err(|error| {
+        AppError::Message(format!(
+            "Failed to serialize pending finalization: {}",
+            error
+        ))
+    })?;
+    std::fs::write(&journal_path, content).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to write pending finalization {}: {}",
+            journal_path.display(),
+            error
+        ))
+    })
+}
+
+/// Rewrite a stop result's journal if it has one, e.g. once some of its tracks are joined.
+pub fn refresh_pending_finalization_journal(
+    stop_result: &StopRecordingResult,
+) -> Result<(), AppError> {
+    match pending_finalization_journal_path(stop_result) {
+        Some(journal_path) if journal_path.is_file() => {
+            write_pending_finalization_journal(stop_result)
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn remove_pending_finalization_journal(stop_result: &StopRecordingResult) {
+    let Some(journal_path) = pending_finalization_journal_path(stop_result) else {
+        return;
+    };
+    if let Err(error) = std::fs::remove_file(&journal_path) {
+        if error.kind() != std::io::ErrorKind::NotFound {
+            eprintln!(
+                "Failed to remove pending finalization {}: {}",
+                journal_path.display(),
+                error
+            );
+        }
+    }
+}
+
+/// Read every finalization journal left in the recordings directory.
+///
+/// A journal beside a `project.json` outlived a finalization that did save its project, e.g.
+/// after a quit right before the journal was removed. It is deleted rather than offered for a
+/// retry that would overwrite the project.
+pub fn load_pending_finalization_journals(recordings_dir: &Path) -> Vec<StopRecordingResult> {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return Vec::new();
+    };
+    let mut stop_results = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join(PENDING_FINALIZATION_FILE_NAME))
+        .filter(|journal_path| journal_path.is_file())
+        .filter(|journal_path| {
+            if !journal_path.with_file_name("project.json").exists() {
+                return true;
+            }
+            if let Err(error) = std::fs::remove_file(journal_path) {
+                eprintln!(
+                    "Failed to remove stale pending finalization {}: {}",
+                    journal_path.display(),
+                    error
+                );
+            }
+            false
+        })
+        .filter_map(|journal_path| {
+            let parsed = std::fs::read_to_string(&journal_path)
+                .map_err(|error| error.to_string())
+                .and_then(|content| {
+                    serde_json::from_str::<StopRecordingResult>(&content)
+                        .map_err(|error| error.to_string())
+                });
+            match parsed {
+                Ok(stop_result) => Some(stop_result),
+                Err(error) => {
+                    eprintln!(
+                        "Skipping unreadable pending finalization {}: {}",
+                        journal_path.display(),
+                        error
+                    );
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    stop_results.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+    stop_results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::SegmentOffsets;
+    use uuid::Uuid;
+
+    #[test]
+    fn finds_recordings_without_project_file_and_orders_their_segments() {
+        let recordings_dir =
+            std::env::temp_dir().join(format!("openrec-recovery-{}", Uuid::new_v4()));
+        let orphan_dir = recordings_dir.join("orphan");
+        let saved_dir = recordings_dir.join("saved");
+        let empty_dir = recordings_dir.join("empty");
+        let camera_only_dir = recordings_dir.join("camera_only");
+        for dir in [&orphan_dir, &saved_dir, &empty_dir, &camera_only_dir] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        for file in [
+            "screen.mp4",
+            "screen_part10.mp4",
+            "screen_part2.mp4",
+            "screen_merged.mp4",
+            "microphone.flac",
+            "system_audio.flac",
+            "system_audio_part2.flac",
+            "cursor.jsonl",
+        ] {
+            std::fs::write(orphan_dir.join(file), b"data").unwrap();
+        }
+        std::fs::write(saved_dir.join("screen.mp4"), b"data").unwrap();
+        std::fs::write(saved_dir.join("project.json"), b"{}").unwrap();
+        std::fs::write(empty_dir.join("cursor.jsonl"), b"data").unwrap();
+        std::fs::write(camera_only_dir.join("camera.webm"), b"data").unwrap();
+
+        let recordings = find_recoverable_recordings(&recordings_dir);
+        std::fs::remove_dir_all(&recordings_dir).unwrap();
+
+        assert_eq!(
+            recordings,
+            vec![
+                RecoverableRecording {
+                    project_id: "camera_only".to_string(),
+                    screen_segment_paths: vec![],
+                    camera_segment_paths: vec![camera_only_dir.join("camera.webm")],
+                    microphone_segment_paths: vec![],
+                    system_audio_segment_paths: vec![],
+                    cursor_log_path: None,
+                    size_bytes: 4,
+                },
+                RecoverableRecording {
+                    project_id: "orphan".to_string(),
+                    screen_segment_paths: vec![
+                        orphan_dir.join("screen.mp4"),
+                        orphan_dir.join("screen_part2.mp4"),
+                        orphan_dir.join("screen_part10.mp4"),
+                    ],
+                    camera_segment_paths: vec![],
+                    microphone_segment_paths: vec![orphan_dir.join("microphone.flac")],
+                    system_audio_segment_paths: vec![
+                        orphan_dir.join("system_audio.flac"),
+                        orphan_dir.join("system_audio_part2.flac"),
+                    ],
+                    cursor_log_path: Some(orphan_dir.join("cursor.jsonl")),
+                    size_bytes: 24,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn pending_finalization_journal_survives_a_reload_and_hides_the_take_from_recovery() {
+        let recordings_dir =
+            std::env::temp_dir().join(format!("openrec-journal-{}", Uuid::new_v4()));
+        let project_dir = recordings_dir.join("pending");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(project_dir.join("screen.mp4"), b"data").unwrap();
+        std::fs::write(project_dir.join("screen_part1.mp4"), b"data").unwrap();
+        let stop_result = StopRecordingResult {
+            project_id: "pending".to_string(),
+            screen_video_path: Some(project_dir.join("screen.mp4")),
+            screen_segment_paths: vec![
+                project_dir.join("screen.mp4"),
+                project_dir.join("screen_part1.mp4"),
+            ],
+            camera_video_path: None,
+            camera_segment_paths: vec![],
+            microphone_audio_path: None,
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
+            duration_seconds: 42.5,
+            source_width: 2560,
+            source_height: 1440,
+            camera_offset_ms: Some(-120),
+            microphone_offset_ms: None,
+            system_audio_offset_ms: Some(35),
+            segment_offsets: SegmentOffsets {
+                camera_ms: vec![-120, 40],
+                microphone_ms: vec![],
+                system_audio_ms: vec![35, 60],
+            },
+            cursor_log_path: None,
+            fragmented_capture: true,
+            marker_times_ms: Vec::new(),
+        };
+
+        write_pending_finalization_journal(&stop_result).unwrap();
+        let reloaded = load_pending_finalization_journals(&recordings_dir);
+        let recoverable = find_recoverable_recordings(&recordings_dir);
+        remove_pending_finalization_journal(&stop_result);
+        let after_removal = load_pending_finalization_journals(&recordings_dir);
+        std::fs::remove_dir_all(&recordings_dir).unwrap();
+
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(
+            reloaded[0].screen_segment_paths,
+            stop_result.screen_segment_paths
+        );
+        assert_eq!(reloaded[0].camera_offset_ms, Some(-120));
+        assert_eq!(reloaded[0].system_audio_offset_ms, Some(35));
+        assert_eq!(reloaded[0].segment_offsets, stop_result.segment_offsets);
+        assert_eq!(
+            (reloaded[0].source_width, reloaded[0].source_height),
+            (2560, 1440)
+        );
+        assert!(recoverable.is_empty());
+        assert!(after_removal.is_empty());
+    }
+
+    #[test]
+    fn drops_journals_whose_project_was_already_saved() {
+        let recordings_dir =
+            std::env::temp_dir().join(format!("openrec-saved-journal-{}", Uuid::new_v4()));
+        let project_dir = recordings_dir.join("saved");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(project_dir.join("screen.mp4"), b"data").unwrap();
+        std::fs::write(project_dir.join("project.json"), b"{}").unwrap();
+        let stop_result = StopRecordingResult {
+            project_id: "saved".to_string(),
+            screen_video_path: Some(project_dir.join("screen.mp4")),
+            screen_segment_paths: vec![project_dir.join("screen.mp4")],
+            camera_video_path: None,
+            camera_segment_paths: vec![],
+            microphone_audio_path: None,
+            microphone_segment_paths: vec![],
+            system_audio_path: None,
+            system_audio_segment_paths: vec![],
+            duration_seconds: 12.0,
+            source_width: 1920,
+            source_height: 1080,
+            camera_offset_ms: None,
+            microphone_offset_ms: None,
+            system_audio_offset_ms: None,
+            segment_offsets: SegmentOffsets::default(),
+            cursor_log_path: None,
+            fragmented_capture: false,
+            marker_times_ms: Vec::new(),
+        };
+        write_pending_finalization_journal(&stop_result).unwrap();
+
+        let reloaded = load_pending_finalization_journals(&recordings_dir);
+        let journal_left = project_dir.join(PENDING_FINALIZATION_FILE_NAME).exists();
+        let project_left = project_dir.join("project.json").exists();
+        std::fs::remove_dir_all(&recordings_dir).unwrap();
+
+        assert!(reloaded.is_empty());
+        assert!(!journal_left);
+        assert!(project_left);
+    }
+
+    #[test]
+    fn skips_takes_locked_by_a_live_process() {
+        let recordings_dir =
+            std::env::temp_dir().join(format!("openrec-recording-lock-{}", Uuid::new_v4()));
+        let live_dir = recordings_dir.join("live");
+        let crashed_dir = recordings_dir.join("crashed");
+        for dir in [&live_dir, &crashed_dir] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("screen.mp4"), b"data").unwrap();
+        }
+        let mut exited = std::process::Command::new("true").spawn().unwrap();
+        exited.wait().unwrap();
+        std::fs::write(
+            crashed_dir.join(RECORDING_LOCK_FILE_NAME),
+            exited.id().to_string(),
+        )
+        .unwrap();
+
+        let lock = RecordingLock::acquire(&live_dir).unwrap();
+        let while_locked = find_recoverable_recordings(&recordings_dir);
+        drop(lock);
+        let after_unlock = find_recoverable_recordings(&recordings_dir);
+        std::fs::remove_dir_all(&recordings_dir).unwrap();
+
+        let ids = |recordings: &[RecoverableRecording]| {
+            recordings
+                .iter()
+                .map(|recording| recording.project_id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&while_locked), vec!["crashed"]);
+        assert_eq!(ids(&after_unlock), vec!["crashed", "live"]);
+    }
+}
@@ -0,0 +1,360 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+/// Length of each rolling replay segment; also the forced keyframe interval.
+pub(super) const REPLAY_SEGMENT_SECONDS: u32 = 10;
+/// Longest replay window a buffer may keep.
+pub(super) const MAX_REPLAY_BUFFER_SECONDS: u32 = 30 * 60;
+const REPLAY_SEGMENT_PREFIX: &str = "replay_";
+const PRUNE_INTERVAL: Duration = Duration::from_secs(2);
+
+pub(super) fn validate_replay_buffer_seconds(seconds: u32) -> Result<(), AppError> {
+    if seconds == 0 || seconds > MAX_REPLAY_BUFFER_SECONDS {
+        return Err(AppError::Message(format!(
+            "Replay buffer length must be between 1 and {} seconds",
+            MAX_REPLAY_BUFFER_SECONDS
+        )));
+    }
+    Ok(())
+}
+
+/// ffmpeg segment muxer output pattern inside the buffer directory.
+pub(super) fn replay_segment_pattern(buffer_dir: &Path) -> PathBuf {
+    buffer_dir.join(format!("{REPLAY_SEGMENT_PREFIX}%05d.mp4"))
+}
+
+/// Output options that split the capture into fragmented MP4 segments of fixed length.
+pub(super) fn replay_output_args() -> Vec<String> {
+    vec![
+        "-force_key_frames".to_string(),
+        format!("expr:gte(t,n_forced*{REPLAY_SEGMENT_SECONDS})"),
+        "-f".to_string(),
+        "segment".to_string(),
+        "-segment_time".to_string(),
+        REPLAY_SEGMENT_SECONDS.to_string(),
+        "-segment_format".to_string(),
+        "mp4".to_string(),
+        "-segment_format_options".to_string(),
+        "movflags=+frag_keyframe+empty_moov+default_base_moof".to_string(),
+        "-reset_timestamps".to_string(),
+        "1".to_string(),
+    ]
+}
+
+/// Segments to retain for a window of `buffer_seconds`, plus the one still being written.
+pub(super) fn replay_segments_to_keep(buffer_seconds: u32) -> usize {
+    buffer_seconds.div_ceil(REPLAY_SEGMENT_SECONDS).max(1) as usize + 1
+}
+
+/// Muxer index of a replay segment, e.g. 12 for `replay_00012.mp4`.
+fn replay_segment_index(path: &Path) -> Option<u32> {
+    if path.extension()?.to_str()? != "mp4" {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(REPLAY_SEGMENT_PREFIX)?
+        .parse::<u32>()
+        .ok()
+}
+
+/// Replay segments in the buffer directory, oldest first.
+pub(super) fn list_replay_segments(buffer_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(buffer_dir) else {
+        return Vec::new();
+    };
+    let mut segments = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            Some((replay_segment_index(&path)?, path))
+        })
+        .collect::<Vec<_>>();
+    segments.sort();
+    segments.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Delete all but the newest `keep` segments; returns how many were removed.
+pub(super) fn prune_replay_segments(buffer_dir: &Path, keep: usize) -> usize {
+    let segments = list_replay_segments(buffer_dir);
+    let excess = segments.len().saturating_sub(keep);
+    segments[..excess]
+        .iter()
+        .filter(|path| match std::fs::remove_file(path) {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!(
+                    "Failed to prune replay segment {}: {}",
+                    path.display(),
+                    error
+                );
+                false
+            }
+        })
+        .count()
+}
+
+fn no_replay_footage_error() -> AppError {
+    AppError::Message("The replay buffer has not recorded any footage yet".to_string())
+}
+
+/// Wait for the segment being written to close, so a saved replay runs up to the request.
+///
+/// Returns the index of the newest complete segment; on timeout that is the one before it.
+pub(super) fn wait_for_closed_replay_segment(
+    buffer_dir: &Path,
+    timeout: Duration,
+) -> Result<u32, AppError> {
+    let newest_index = |buffer_dir: &Path| {
+        list_replay_segments(buffer_dir)
+            .last()
+            .and_then(|path| replay_segment_index(path))
+    };
+    let writing = newest_index(buffer_dir).ok_or_else(no_replay_footage_error)?;
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if !buffer_dir.is_dir() {
+            return Err(no_replay_footage_error());
+        }
+        if newest_index(buffer_dir).is_some_and(|index| index > writing) {
+            return Ok(writing);
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    writing.checked_sub(1).ok_or_else(no_replay_footage_error)
+}
+
+/// Copy the newest `count` non-empty segments up to `last_index` into `project_dir` as a take.
+///
+/// The copies become `screen.mp4`, `screen_part1.mp4`, ... in recording order, and the buffer
+/// keeps recording untouched. A segment the pruner removes mid-copy is skipped.
+pub(super) fn copy_replay_segments(
+    buffer_dir: &Path,
+    project_dir: &Path,
+    last_index: u32,
+    count: usize,
+) -> Result<Vec<PathBuf>, AppError> {
+    let segments = list_replay_segments(buffer_dir)
+        .into_iter()
+        .filter(|path| replay_segment_index(path).is_some_and(|index| index <= last_index))
+        .filter(|path| std::fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0))
+        .collect::<Vec<_>>();
+    let (_, retained) = segments.split_at(segments.len().saturating_sub(count));
+    if retained.is_empty() {
+        return Err(no_replay_footage_error());
+    }
+    std::fs::create_dir_all(project_dir)
+        .map_err(|error| AppError::Io(format!("Failed to create replay project dir: {}", error)))?;
+    let mut taken = Vec::with_capacity(retained.len());
+    for segment in retained {
+        let target = if taken.is_empty() {
+            project_dir.join("screen.mp4")
+        } else {
+            project_dir.join(format!("screen_part{}.mp4", taken.len()))
+        };
+        match std::fs::copy(segment, &target) {
+            Ok(_) => taken.push(target),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                let _ = std::fs::remove_dir_all(project_dir);
+                return Err(AppError::Io(format!(
+                    "Failed to copy replay segment {}: {}",
+                    segment.display(),
+                    error
+                )));
+            }
+        }
+    }
+    if taken.is_empty() {
+        let _ = std::fs::remove_dir_all(project_dir);
+        return Err(no_replay_footage_error());
+    }
+    Ok(taken)
+}
+
+/// Delete the replay segments of buffers that outlived the app, e.g. after a crash.
+///
+/// A buffer directory never gets a project file, so only call this while this process records
+/// nothing. Directories locked by another live process are skipped.
+pub fn prune_orphaned_replay_buffers(recordings_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return;
+    };
+    for buffer_dir in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if !buffer_dir.is_dir()
+            || buffer_dir.join("project.json").exists()
+            || super::is_recording_locked(&buffer_dir)
+        {
+            continue;
+        }
+        let segments = list_replay_segments(&buffer_dir);
+        if segments.is_empty() {
+            continue;
+        }
+        for segment in segments {
+            if let Err(error) = std::fs::remove_file(&segment) {
+                eprintln!(
+                    "Failed to remove orphaned replay segment {}: {}",
+                    segment.display(),
+                    error
+                );
+            }
+        }
+        // Only succeeds when the buffer held nothing else worth recovering.
+        let _ = std::fs::remove_dir(&buffer_dir);
+    }
+}
+
+/// Background thread that keeps the buffer directory at a fixed number of segments.
+#[derive(Debug)]
+pub struct ReplayBufferPruner {
+    stop_requested: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReplayBufferPruner {
+    pub(super) fn start(buffer_dir: PathBuf, keep: usize) -> Self {
+        let stop_requested = Arc::new(AtomicBool::new(false));
+        let thread_stop_requested = Arc::clone(&stop_requested);
+        let handle = thread::spawn(move || {
+            let mut last_prune = Instant::now();
+            while !thread_stop_requested.load(Ordering::Relaxed) {
+                if last_prune.elapsed() >= PRUNE_INTERVAL {
+                    prune_replay_segments(&buffer_dir, keep);
+                    last_prune = Instant::now();
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        Self {
+            stop_requested,
+            handle: Some(handle),
+        }
+    }
+
+    pub(super) fn stop(mut self) {
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                eprintln!("Replay buffer pruner thread panicked while stopping");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn keeps_enough_segments_to_cover_the_buffer_window() {
+        assert_eq!(replay_segments_to_keep(1), 2);
+        assert_eq!(replay_segments_to_keep(10), 2);
+        assert_eq!(replay_segments_to_keep(11), 3);
+        assert_eq!(replay_segments_to_keep(120), 13);
+        assert!(validate_replay_buffer_seconds(0).is_err());
+        assert!(validate_replay_buffer_seconds(MAX_REPLAY_BUFFER_SECONDS).is_ok());
+        assert!(validate_replay_buffer_seconds(MAX_REPLAY_BUFFER_SECONDS + 1).is_err());
+    }
+
+    #[test]
+    fn prunes_the_oldest_replay_segments() {
+        let buffer_dir = std::env::temp_dir().join(format!("openrec-replay-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&buffer_dir).unwrap();
+        for file in [
+            "replay_00009.mp4",
+            "replay_00010.mp4",
+            "replay_00011.mp4",
+            "replay_00002.mp4",
+            "cursor.jsonl",
+        ] {
+            std::fs::write(buffer_dir.join(file), b"data").unwrap();
+        }
+
+        let removed = prune_replay_segments(&buffer_dir, 2);
+        let remaining = list_replay_segments(&buffer_dir);
+        let cursor_log_kept = buffer_dir.join("cursor.jsonl").exists();
+        std::fs::remove_dir_all(&buffer_dir).unwrap();
+
+        assert_eq!(removed, 2);
+        assert_eq!(
+            remaining,
+            vec![
+                buffer_dir.join("replay_00010.mp4"),
+                buffer_dir.join("replay_00011.mp4"),
+            ]
+        );
+        assert!(cursor_log_kept);
+    }
+
+    #[test]
+    fn copies_the_newest_complete_replay_segments_as_screen_segments() {
+        let root_dir = std::env::temp_dir().join(format!("openrec-replay-{}", Uuid::new_v4()));
+        let buffer_dir = root_dir.join("buffer");
+        let project_dir = root_dir.join("take");
+        std::fs::create_dir_all(&buffer_dir).unwrap();
+        for file in [
+            "replay_00000.mp4",
+            "replay_00001.mp4",
+            "replay_00002.mp4",
+            "replay_00004.mp4",
+        ] {
+            std::fs::write(buffer_dir.join(file), b"data").unwrap();
+        }
+        std::fs::write(buffer_dir.join("replay_00003.mp4"), b"").unwrap();
+
+        let taken = copy_replay_segments(&buffer_dir, &project_dir, 3, 2).unwrap();
+        let left_in_buffer = list_replay_segments(&buffer_dir).len();
+        let timed_out_index =
+            wait_for_closed_replay_segment(&buffer_dir, Duration::from_millis(10)).unwrap();
+        let empty_buffer_result =
+            copy_replay_segments(&root_dir.join("missing"), &project_dir, 3, 2);
+        std::fs::remove_dir_all(&root_dir).unwrap();
+
+        assert_eq!(
+            taken,
+            vec![
+                project_dir.join("screen.mp4"),
+                project_dir.join("screen_part1.mp4"),
+            ]
+        );
+        assert_eq!(left_in_buffer, 5);
+        assert_eq!(timed_out_index, 3);
+        assert!(empty_buffer_result.is_err());
+    }
+
+    #[test]
+    fn prunes_replay_segments_of_orphaned_buffers_only() {
+        let recordings_dir =
+            std::env::temp_dir().join(format!("openrec-replay-orphans-{}", Uuid::new_v4()));
+        let orphan_dir = recordings_dir.join("orphan");
+        let saved_dir = recordings_dir.join("saved");
+        let crashed_dir = recordings_dir.join("crashed");
+        for dir in [&orphan_dir, &saved_dir, &crashed_dir] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(orphan_dir.join("replay_00007.mp4"), b"data").unwrap();
+        std::fs::write(saved_dir.join("replay_00001.mp4"), b"data").unwrap();
+        std::fs::write(saved_dir.join("project.json"), b"{}").unwrap();
+        std::fs::write(crashed_dir.join("screen.mp4"), b"data").unwrap();
+
+        prune_orphaned_replay_buffers(&recordings_dir);
+        let orphan_removed = !orphan_dir.exists();
+        let saved_kept = saved_dir.join("replay_00001.mp4").exists();
+        let crashed_kept = crashed_dir.join("screen.mp4").exists();
+        std::fs::remove_dir_all(&recordings_dir).unwrap();
+
+        assert!(orphan_removed);
+        assert!(saved_kept);
+        assert!(crashed_kept);
+    }
+}
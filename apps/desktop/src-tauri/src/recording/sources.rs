@@ -1,13 +1,17 @@
 use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::path::Path;
 
 use crate::error::AppError;
 
+#[cfg(target_os = "linux")]
+use super::portal::{wayland_session_active, WAYLAND_PORTAL_SOURCE_ID};
 #[cfg(target_os = "macos")]
 use screencapturekit::shareable_content::SCShareableContent;
 #[cfg(target_os = "linux")]
 use xcap::{Monitor, Window};
 
-/// Represents a capture source (display or window)
+/// Represents a capture source (display, window, camera or microphone)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CaptureSource {
@@ -23,6 +27,19 @@ pub struct CaptureSource {
 pub enum SourceType {
     Display,
     Window,
+    Region,
+    Camera,
+    Microphone,
+}
+
+impl SourceType {
+    /// Whether this source can be the recording's screen track.
+    pub fn is_screen(self) -> bool {
+        matches!(
+            self,
+            SourceType::Display | SourceType::Window | SourceType::Region
+        )
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -45,6 +62,25 @@ pub(super) struct LinuxWindowSource {
     pub height: u32,
 }
 
+/// A V4L2 video capture node such as `/dev/video0`.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct LinuxCameraSource {
+    pub device_path: String,
+    pub name: String,
+}
+
+/// A PulseAudio/PipeWire input source such as a USB microphone.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct LinuxMicrophoneSource {
+    pub name: String,
+    pub description: String,
+}
+
+#[cfg(target_os = "linux")]
+const V4L2_SYSFS_DIR: &str = "/sys/class/video4linux";
+
 #[cfg(target_os = "linux")]
 fn xcap_error_context(context: &str, error: impl std::fmt::Display) -> AppError {
     AppError::Message(format!("{context}: {error}"))
@@ -132,6 +168,98 @@ pub(super) fn linux_list_window_sources() -> Result<Vec<LinuxWindowSource>, AppE
     Ok(windows)
 }
 
+/// Read the capture nodes listed under a `video4linux` sysfs class directory.
+///
+/// UVC cameras also register metadata nodes; only interface index 0 carries frames.
+#[cfg(target_os = "linux")]
+fn read_v4l2_camera_sources(sysfs_dir: &Path) -> Vec<LinuxCameraSource> {
+    let Ok(entries) = std::fs::read_dir(sysfs_dir) else {
+        return Vec::new();
+    };
+    let mut cameras = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let node = entry.file_name().to_string_lossy().to_string();
+            let number = node.strip_prefix("video")?.parse::<u32>().ok()?;
+            let read_attribute = |attribute: &str| {
+                std::fs::read_to_string(entry.path().join(attribute))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+            };
+            if read_attribute("index").is_some_and(|index| index != "0") {
+                return None;
+            }
+            let name = read_attribute("name")
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("Camera {}", number));
+            Some((
+                number,
+                LinuxCameraSource {
+                    device_path: format!("/dev/{}", node),
+                    name,
+                },
+            ))
+        })
+        .collect::<Vec<_>>();
+    cameras.sort_by_key(|(number, _)| *number);
+    cameras.into_iter().map(|(_, camera)| camera).collect()
+}
+
+#[cfg(target_os = "linux")]
+pub(super) fn linux_list_camera_sources() -> Vec<LinuxCameraSource> {
+    read_v4l2_camera_sources(Path::new(V4L2_SYSFS_DIR))
+}
+
+/// Parse `pactl list sources`, leaving out the monitors of output sinks.
+#[cfg(target_os = "linux")]
+fn parse_pulse_input_sources(raw: &str) -> Vec<LinuxMicrophoneSource> {
+    let mut sources = Vec::new();
+    let mut push_source = |name: Option<String>, description: Option<String>, monitor: bool| {
+        if let (Some(name), false) = (name, monitor) {
+            sources.push(LinuxMicrophoneSource {
+                description: description
+                    .filter(|description| !description.is_empty())
+                    .unwrap_or_else(|| name.clone()),
+                name,
+            });
+        }
+    };
+    let mut name = None;
+    let mut description = None;
+    let mut monitor = false;
+    for line in raw.lines() {
+        if line.starts_with("Source #") {
+            push_source(name.take(), description.take(), monitor);
+            monitor = false;
+            continue;
+        }
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Description:") {
+            description = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Monitor of Sink:") {
+            monitor = value.trim() != "n/a";
+        }
+    }
+    push_source(name, description, monitor);
+    sources
+}
+
+#[cfg(target_os = "linux")]
+pub(super) fn linux_list_microphone_sources() -> Vec<LinuxMicrophoneSource> {
+    let output = std::process::Command::new("pactl")
+        .env("LC_ALL", "C")
+        .args(["list", "sources"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_pulse_input_sources(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Check if screen recording permission is granted
 #[cfg(target_os = "macos")]
 pub fn check_screen_recording_permission() -> bool {
@@ -231,13 +359,31 @@ pub fn list_capture_sources(source_type: SourceType) -> Result<Vec<CaptureSource
                 })
                 .collect())
         }
+        // Cameras and microphones are captured by the webview on macOS.
+        SourceType::Region | SourceType::Camera | SourceType::Microphone => Ok(vec![]),
     }
 }
 
 #[cfg(target_os = "linux")]
 pub fn list_capture_sources(source_type: SourceType) -> Result<Vec<CaptureSource>, AppError> {
+    // Wayland only exposes screens and windows through the portal's own picker.
+    if wayland_session_active() && source_type.is_screen() {
+        let name = match source_type {
+            SourceType::Window => "Window (chosen when recording starts)",
+            SourceType::Region => return Ok(vec![]),
+            _ => "Screen (chosen when recording starts)",
+        };
+        return Ok(vec![CaptureSource {
+            id: WAYLAND_PORTAL_SOURCE_ID.to_string(),
+            name: name.to_string(),
+            source_type,
+            thumbnail: None,
+        }]);
+    }
+
     match source_type {
-        SourceType::Display => Ok(linux_list_display_sources()?
+        // A region is drawn on one of the displays, so offer those as its source.
+        SourceType::Display | SourceType::Region => Ok(linux_list_display_sources()?
             .into_iter()
             .enumerate()
             .map(|(index, display)| CaptureSource {
@@ -247,7 +393,7 @@ pub fn list_capture_sources(source_type: SourceType) -> Result<Vec<CaptureSource
                 } else {
                     display.name
                 },
-                source_type: SourceType::Display,
+                source_type,
                 thumbnail: None,
             })
             .collect()),
@@ -264,6 +410,24 @@ pub fn list_capture_sources(source_type: SourceType) -> Result<Vec<CaptureSource
                 thumbnail: None,
             })
             .collect()),
+        SourceType::Camera => Ok(linux_list_camera_sources()
+            .into_iter()
+            .map(|camera| CaptureSource {
+                id: camera.device_path,
+                name: camera.name,
+                source_type: SourceType::Camera,
+                thumbnail: None,
+            })
+            .collect()),
+        SourceType::Microphone => Ok(linux_list_microphone_sources()
+            .into_iter()
+            .map(|microphone| CaptureSource {
+                id: microphone.name,
+                name: microphone.description,
+                source_type: SourceType::Microphone,
+                thumbnail: None,
+            })
+            .collect()),
     }
 }
 
@@ -274,3 +438,71 @@ pub fn list_capture_sources(_source_type: SourceType) -> Result<Vec<CaptureSourc
         "Screen capture is only supported on macOS and Linux".to_string(),
     ))
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn lists_v4l2_capture_nodes_and_skips_metadata_nodes() {
+        let sysfs_dir =
+            std::env::temp_dir().join(format!("openrec-video4linux-{}", Uuid::new_v4()));
+        for (node, index, name) in [
+            ("video2", "0", "USB Camera\n"),
+            ("video0", "0", "Integrated Camera: Integrated C\n"),
+            ("video1", "1", "Integrated Camera: Integrated C\n"),
+            ("vbi0", "0", "VBI\n"),
+        ] {
+            let node_dir = sysfs_dir.join(node);
+            std::fs::create_dir_all(&node_dir).unwrap();
+            std::fs::write(node_dir.join("index"), index).unwrap();
+            std::fs::write(node_dir.join("name"), name).unwrap();
+        }
+
+        let cameras = read_v4l2_camera_sources(&sysfs_dir);
+        std::fs::remove_dir_all(&sysfs_dir).unwrap();
+
+        assert_eq!(
+            cameras,
+            vec![
+                LinuxCameraSource {
+                    device_path: "/dev/video0".to_string(),
+                    name: "Integrated Camera: Integrated C".to_string(),
+                },
+                LinuxCameraSource {
+                    device_path: "/dev/video2".to_string(),
+                    name: "USB Camera".to_string(),
+                },
+            ]
+        );
+        assert!(read_v4l2_camera_sources(&sysfs_dir).is_empty());
+    }
+
+    #[test]
+    fn parses_pulse_input_sources_without_sink_monitors() {
+        let raw = concat!(
+            "Source #55\n",
+            "\tState: SUSPENDED\n",
+            "\tName: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor\n",
+            "\tDescription: Monitor of Built-in Audio Analog Stereo\n",
+            "\tMonitor of Sink: alsa_output.pci-0000_00_1f.3.analog-stereo\n",
+            "\n",
+            "Source #56\n",
+            "\tState: RUNNING\n",
+            "\tName: alsa_input.usb-Blue_Yeti-00.analog-stereo\n",
+            "\tDescription: Yeti Stereo Microphone Analog Stereo\n",
+            "\tMonitor of Sink: n/a\n",
+            "\tProperties:\n",
+            "\t\tdevice.description = \"Yeti Stereo Microphone\"\n",
+        );
+
+        assert_eq!(
+            parse_pulse_input_sources(raw),
+            vec![LinuxMicrophoneSource {
+                name: "alsa_input.usb-Blue_Yeti-00.analog-stereo".to_string(),
+                description: "Yeti Stereo Microphone Analog Stereo".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+const SHORTCUT_BINDINGS_FILE_NAME: &str = "shortcuts.json";
+
+/// Something a global shortcut can trigger.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShortcutAction {
+    StartStop,
+    PauseResume,
+    AddMarker,
+    OpenRecorder,
+    OpenProjects,
+    QuickRecord,
+}
+
+impl ShortcutAction {
+    pub const ALL: [Self; 6] = [
+        Self::StartStop,
+        Self::PauseResume,
+        Self::AddMarker,
+        Self::OpenRecorder,
+        Self::OpenProjects,
+        Self::QuickRecord,
+    ];
+
+    pub fn default_accelerator(self) -> Option<&'static str> {
+        match self {
+            Self::StartStop => Some("CmdOrCtrl+Shift+2"),
+            Self::PauseResume => Some("CmdOrCtrl+Shift+P"),
+            Self::AddMarker => Some("CmdOrCtrl+Shift+M"),
+            Self::OpenRecorder | Self::OpenProjects | Self::QuickRecord => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::StartStop => "Start/Stop Recording",
+            Self::PauseResume => "Pause/Resume Recording",
+            Self::AddMarker => "Add Marker",
+            Self::OpenRecorder => "Open Recorder",
+            Self::OpenProjects => "Open Projects",
+            Self::QuickRecord => "Quick Record",
+        }
+    }
+}
+
+/// Canonical spelling and order of accelerator modifiers.
+const MODIFIERS: [(&str, &[&str]); 5] = [
+    (
+        "CmdOrCtrl",
+        &["cmdorctrl", "commandorcontrol", "cmdorcontrol"],
+    ),
+    ("Super", &["super", "cmd", "command", "meta"]),
+    ("Ctrl", &["ctrl", "control"]),
+    ("Alt", &["alt", "option"]),
+    ("Shift", &["shift"]),
+];
+
+fn parse_accelerator(accelerator: &str) -> Result<(Vec<&'static str>, String), AppError> {
+    let invalid = |reason: &str| {
+        AppError::Message(format!(
+            "Invalid shortcut \"{}\": {}",
+            accelerator.trim(),
+            reason
+        ))
+    };
+    let mut parts = accelerator.split('+').map(str::trim).collect::<Vec<_>>();
+    let key = parts.pop().unwrap_or_default();
+    if key.is_empty() {
+        return Err(invalid("it needs a key"));
+    }
+    let mut modifiers = Vec::with_capacity(parts.len());
+    for part in parts {
+        let lowercase = part.to_ascii_lowercase();
+        let Some((modifier, _)) = MODIFIERS
+            .iter()
+            .find(|(_, aliases)| aliases.contains(&lowercase.as_str()))
+        else {
+            return Err(invalid(&format!("\"{part}\" is not a modifier key")));
+        };
+        if !modifiers.contains(modifier) {
+            modifiers.push(*modifier);
+        }
+    }
+    if modifiers.is_empty() {
+        return Err(invalid("global shortcuts need at least one modifier key"));
+    }
+    modifiers.sort_by_key(|modifier| MODIFIERS.iter().position(|(name, _)| name == modifier));
+    let key = if key.chars().count() == 1 {
+        key.to_uppercase()
+    } else {
+        key.to_string()
+    };
+    Ok((modifiers, key))
+}
+
+/// Trim and reorder an accelerator, e.g. `shift + cmdorctrl + r` becomes `CmdOrCtrl+Shift+R`.
+pub fn normalize_accelerator(accelerator: &str) -> Result<String, AppError> {
+    let (mut parts, key) = parse_accelerator(accelerator)?;
+    parts.push(&key);
+    Ok(parts.join("+"))
+}
+
+/// The key combination an accelerator presses on this platform, for spotting duplicates.
+fn accelerator_chord(accelerator: &str) -> Option<(Vec<&'static str>, String)> {
+    let (modifiers, key) = parse_accelerator(accelerator).ok()?;
+    let platform_command = if cfg!(target_os = "macos") {
+        "Super"
+    } else {
+        "Ctrl"
+    };
+    let mut modifiers = modifiers
+        .into_iter()
+        .map(|modifier| {
+            if modifier == "CmdOrCtrl" {
+                platform_command
+            } else {
+                modifier
+            }
+        })
+        .collect::<Vec<_>>();
+    modifiers.sort_unstable();
+    modifiers.dedup();
+    Some((modifiers, key.to_ascii_uppercase()))
+}
+
+/// Accelerator overrides keyed by action. A missing action keeps its default and a `null`
+/// one is unbound.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ShortcutBindings(BTreeMap<ShortcutAction, Option<String>>);
+
+impl ShortcutBindings {
+    pub fn accelerator(&self, action: ShortcutAction) -> Option<&str> {
+        match self.0.get(&action) {
+            Some(accelerator) => accelerator.as_deref(),
+            None => action.default_accelerator(),
+        }
+    }
+
+    /// Every bound action with its accelerator.
+    pub fn bound(&self) -> Vec<(ShortcutAction, &str)> {
+        ShortcutAction::ALL
+            .into_iter()
+            .filter_map(|action| Some((action, self.accelerator(action)?)))
+            .collect()
+    }
+
+    /// Rebind `action`, rejecting malformed accelerators and ones another action already uses.
+    pub fn rebind(
+        &self,
+        action: ShortcutAction,
+        accelerator: Option<&str>,
+    ) -> Result<Self, AppError> {
+        let accelerator = accelerator
+            .map(str::trim)
+            .filter(|accelerator| !accelerator.is_empty())
+            .map(normalize_accelerator)
+            .transpose()?;
+        if let Some(accelerator) = accelerator.as_deref() {
+            let chord = accelerator_chord(accelerator);
+            if let Some((conflict, _)) = self
+                .bound()
+                .into_iter()
+                .find(|(other, bound)| *other != action && accelerator_chord(bound) == chord)
+            {
+                return Err(AppError::Message(format!(
+                    "{} is already used by {}",
+                    accelerator,
+                    conflict.label()
+                )));
+            }
+        }
+        let mut bindings = self.clone();
+        if accelerator.as_deref() == action.default_accelerator() {
+            bindings.0.remove(&action);
+        } else {
+            bindings.0.insert(action, accelerator);
+        }
+        Ok(bindings)
+    }
+}
+
+/// A binding as shown in settings, with why the OS refused it if it did.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBindingStatus {
+    pub action: ShortcutAction,
+    pub accelerator: Option<String>,
+    pub default_accelerator: Option<String>,
+    pub registration_error: Option<String>,
+}
+
+/// Bindings in effect and the registrations that failed for them.
+#[derive(Debug, Default)]
+pub struct GlobalShortcutState {
+    pub bindings: ShortcutBindings,
+    pub registration_errors: BTreeMap<ShortcutAction, String>,
+}
+
+impl GlobalShortcutState {
+    pub fn statuses(&self) -> Vec<ShortcutBindingStatus> {
+        ShortcutAction::ALL
+            .into_iter()
+            .map(|action| ShortcutBindingStatus {
+                action,
+                accelerator: self.bindings.accelerator(action).map(str::to_string),
+                default_accelerator: action.default_accelerator().map(str::to_string),
+                registration_error: self.registration_errors.get(&action).cloned(),
+            })
+            .collect()
+    }
+}
+
+pub fn shortcut_bindings_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(SHORTCUT_BINDINGS_FILE_NAME)
+}
+
+/// Saved bindings, or the defaults when none are saved or the file is unreadable.
+pub fn load_shortcut_bindings(path: &Path) -> ShortcutBindings {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return ShortcutBindings::default()
+        }
+        Err(error) => {
+            eprintln!(
+                "Failed to read shortcut bindings {}: {}",
+                path.display(),
+                error
+            );
+            return ShortcutBindings::default();
+        }
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|error| {
+        eprintln!(
+            "Ignoring invalid shortcut bindings {}: {}",
+            path.display(),
+            error
+        );
+        ShortcutBindings::default()
+    })
+}
+
+pub fn save_shortcut_bindings(path: &Path, bindings: &ShortcutBindings) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::Io(format!("Failed to create {}: {}", parent.display(), error))
+        })?;
+    }
+    let contents = serde_json::to_string_pretty(bindings).map_err(|error| {
+        AppError::Message(format!("Failed to serialize shortcut bindings: {}", error))
+    })?;
+    std::fs::write(path, contents).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to write shortcut bindings {}: {}",
+            path.display(),
+            error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_accelerator_spelling_and_modifier_order() {
+        assert_eq!(
+            normalize_accelerator(" shift + commandorcontrol + r ").unwrap(),
+            "CmdOrCtrl+Shift+R"
+        );
+        assert_eq!(
+            normalize_accelerator("Option+Ctrl+Alt+F5").unwrap(),
+            "Ctrl+Alt+F5"
+        );
+        assert!(normalize_accelerator("R").is_err());
+        assert!(normalize_accelerator("CmdOrCtrl+").is_err());
+        assert!(normalize_accelerator("Hyper+R").is_err());
+    }
+
+    #[test]
+    fn rebinding_rejects_shortcuts_another_action_uses() {
+        let bindings = ShortcutBindings::default();
+        let error = bindings
+            .rebind(ShortcutAction::QuickRecord, Some("shift+cmdorctrl+p"))
+            .unwrap_err();
+        assert!(error.to_string().contains("Pause/Resume Recording"));
+
+        // Freeing the default first makes it available to another action.
+        let bindings = bindings
+            .rebind(ShortcutAction::PauseResume, None)
+            .unwrap()
+            .rebind(ShortcutAction::QuickRecord, Some("shift+cmdorctrl+p"))
+            .unwrap();
+        assert_eq!(bindings.accelerator(ShortcutAction::PauseResume), None);
+        assert_eq!(
+            bindings.accelerator(ShortcutAction::QuickRecord),
+            Some("CmdOrCtrl+Shift+P")
+        );
+        // An action may keep its own shortcut.
+        assert!(bindings
+            .rebind(ShortcutAction::QuickRecord, Some("CmdOrCtrl+Shift+P"))
+            .is_ok());
+    }
+
+    #[test]
+    fn persists_only_overrides_of_the_defaults() {
+        let bindings = ShortcutBindings::default()
+            .rebind(ShortcutAction::StartStop, Some("CmdOrCtrl+Alt+R"))
+            .unwrap()
+            .rebind(ShortcutAction::AddMarker, None)
+            .unwrap()
+            .rebind(ShortcutAction::PauseResume, Some("CmdOrCtrl+Shift+P"))
+            .unwrap();
+
+        let json = serde_json::to_value(&bindings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "start-stop": "CmdOrCtrl+Alt+R", "add-marker": null })
+        );
+        let restored: ShortcutBindings = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            restored.bound(),
+            vec![
+                (ShortcutAction::StartStop, "CmdOrCtrl+Alt+R"),
+                (ShortcutAction::PauseResume, "CmdOrCtrl+Shift+P"),
+            ]
+        );
+    }
+}
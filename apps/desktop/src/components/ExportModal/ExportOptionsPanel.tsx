@@ -22,6 +22,7 @@ interface ExportOptionsPanelProps {
   activePreset: "youtube-4k" | "web-discord" | "prores-master" | "custom";
   isAudioOnlyFormat: boolean;
   usesCompression: boolean;
+  hasChapters: boolean;
 }
 
 export function ExportOptionsPanel({
@@ -30,6 +31,7 @@ export function ExportOptionsPanel({
   activePreset,
   isAudioOnlyFormat,
   usesCompression,
+  hasChapters,
 }: ExportOptionsPanelProps) {
   return (
     <div className="flex flex-col gap-5 py-4">
@@ -124,6 +126,18 @@ export function ExportOptionsPanel({
           Audio-only export selected: video tracks will be skipped.
         </div>
       )}
+
+      {hasChapters && (
+        <label className="flex items-center gap-2 text-xs text-muted-foreground">
+          <input
+            type="checkbox"
+            checked={options.chapterList ?? false}
+            onChange={(event) => setOptions({ ...options, chapterList: event.target.checked })}
+            className="size-3.5 accent-primary"
+          />
+          Save a YouTube chapter list next to the export
+        </label>
+      )}
     </div>
   );
 }
@@ -94,6 +94,7 @@ export function ExportModal({ project, editedDuration, open, onOpenChange, onSav
           activePreset={activePreset}
           isAudioOnlyFormat={isAudioOnlyFormat}
           usesCompression={usesCompression}
+          hasChapters={(project.edits.chapters?.length ?? 0) > 0}
         />
 
         <DialogFooter className="flex-col gap-4 sm:flex-col">
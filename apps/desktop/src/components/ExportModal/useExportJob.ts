@@ -16,6 +16,7 @@ interface ExportProgressEvent {
 interface ExportCompleteEvent {
   jobId: string;
   outputPath: string;
+  chapterListPath?: string | null;
 }
 
 interface ExportErrorEvent {
@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { Annotation, AudioMixSettings, CameraOverlaySettings, ColorCorrectionSettings, Project, Segment, ZoomEffect, SpeedEffect } from "../types/project";
 
 const MAX_HISTORY_SIZE = 50;
+const DEFAULT_ZOOM_EASE_SECONDS = 0.4;
 
 export function useProject(initialProject: Project | null) {
   const [project, setProjectState] = useState<Project | null>(initialProject);
@@ -223,6 +224,9 @@ export function useProject(initialProject: Project | null) {
               scale,
               x: 0,
               y: 0,
+              easeIn: DEFAULT_ZOOM_EASE_SECONDS,
+              easeOut: DEFAULT_ZOOM_EASE_SECONDS,
+              easing: "ease-in-out",
             },
           ],
         },
@@ -308,7 +312,10 @@ export function useProject(initialProject: Project | null) {
         nextZoomState.endTime === currentZoom.endTime &&
         nextZoomState.scale === currentZoom.scale &&
         nextZoomState.x === currentZoom.x &&
-        nextZoomState.y === currentZoom.y;
+        nextZoomState.y === currentZoom.y &&
+        nextZoomState.easeIn === currentZoom.easeIn &&
+        nextZoomState.easeOut === currentZoom.easeOut &&
+        nextZoomState.easing === currentZoom.easing;
       if (zoomUnchanged) {
         return p;
       }
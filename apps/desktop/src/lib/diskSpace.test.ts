@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest";
+import { describeDiskSpaceReport } from "./diskSpace";
+import { DiskSpaceReport } from "../types/project";
+
+const GIB = 1024 ** 3;
+
+function report(overrides: Partial<DiskSpaceReport>): DiskSpaceReport {
+  return {
+    projectId: "project-1",
+    level: "ok",
+    freeBytes: 20 * GIB,
+    floorBytes: GIB,
+    bytesPerSecond: 0,
+    secondsRemaining: null,
+    action: null,
+    ...overrides,
+  };
+}
+
+describe("describeDiskSpaceReport", () => {
+  it("clears the warning once space recovers", () => {
+    expect(describeDiskSpaceReport(report({ level: "ok" }))).toBeNull();
+  });
+
+  it("estimates the recording time left while space is low", () => {
+    expect(
+      describeDiskSpaceReport(report({ level: "low", freeBytes: 3 * GIB, secondsRemaining: 250 }))
+    ).toBe("Low disk space: 3.00 GB free, about 4 min of recording left.");
+    expect(describeDiskSpaceReport(report({ level: "low", freeBytes: 4 * GIB }))).toBe(
+      "Low disk space: 4.00 GB free."
+    );
+  });
+
+  it("explains the automatic pause or stop at the floor", () => {
+    expect(
+      describeDiskSpaceReport(report({ level: "critical", freeBytes: GIB, action: "stop" }))
+    ).toBe("Recording stopped automatically because free disk space dropped to 1.00 GB.");
+    expect(
+      describeDiskSpaceReport(report({ level: "critical", freeBytes: GIB, action: "pause" }))
+    ).toBe("Recording paused automatically because free disk space dropped to 1.00 GB.");
+  });
+});
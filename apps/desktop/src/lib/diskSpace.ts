@@ -1,4 +1,4 @@
-import { DiskSpaceStatus } from "../types/project";
+import { DiskSpaceReport, DiskSpaceStatus } from "../types/project";
 
 const FALLBACK_MINIMUM_FREE_BYTES = 5 * 1024 ** 3;
 
@@ -9,3 +9,20 @@ export function formatBytesAsGiB(bytes: number): string {
 export function resolveMinimumFreeBytes(status: DiskSpaceStatus): number {
   return status.minimumRequiredBytes ?? FALLBACK_MINIMUM_FREE_BYTES;
 }
+
+export function describeDiskSpaceReport(report: DiskSpaceReport): string | null {
+  const freeGb = formatBytesAsGiB(report.freeBytes);
+  if (report.level === "critical") {
+    const outcome = report.action === "stop" ? "stopped" : "paused";
+    return `Recording ${outcome} automatically because free disk space dropped to ${freeGb} GB.`;
+  }
+  if (report.level === "low") {
+    const secondsRemaining = report.secondsRemaining;
+    if (secondsRemaining !== null && secondsRemaining < 3600) {
+      const minutesRemaining = Math.max(1, Math.floor(secondsRemaining / 60));
+      return `Low disk space: ${freeGb} GB free, about ${minutesRemaining} min of recording left.`;
+    }
+    return `Low disk space: ${freeGb} GB free.`;
+  }
+  return null;
+}
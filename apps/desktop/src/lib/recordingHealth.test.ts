@@ -0,0 +1,30 @@
+import { describe, expect, it } from "vitest";
+import { describeRecordingHealth } from "./recordingHealth";
+import { RecordingHealth } from "../types/project";
+
+const healthy: RecordingHealth = {
+  frames: 300,
+  fps: 30,
+  droppedFrames: 0,
+  duplicatedFrames: 0,
+  speed: 1.0,
+  bitrateKbps: 2400,
+  fallingBehind: false,
+};
+
+describe("describeRecordingHealth", () => {
+  it("stays quiet while the encoder keeps up", () => {
+    expect(describeRecordingHealth(healthy)).toBeNull();
+  });
+
+  it("summarizes speed and dropped frames when falling behind", () => {
+    expect(
+      describeRecordingHealth({ ...healthy, fallingBehind: true, speed: 0.82, droppedFrames: 14 })
+    ).toBe(
+      "Recording is falling behind (encoding at 0.82x, 14 frames dropped). Try a lower quality preset."
+    );
+    expect(describeRecordingHealth({ ...healthy, fallingBehind: true, speed: null })).toBe(
+      "Recording is falling behind. Try a lower quality preset."
+    );
+  });
+});
@@ -0,0 +1,16 @@
+import { RecordingHealth } from "../types/project";
+
+export function describeRecordingHealth(health: RecordingHealth): string | null {
+  if (!health.fallingBehind) {
+    return null;
+  }
+  const details: string[] = [];
+  if (health.speed !== null) {
+    details.push(`encoding at ${health.speed.toFixed(2)}x`);
+  }
+  if (health.droppedFrames > 0) {
+    details.push(`${health.droppedFrames} frames dropped`);
+  }
+  const suffix = details.length > 0 ? ` (${details.join(", ")})` : "";
+  return `Recording is falling behind${suffix}. Try a lower quality preset.`;
+}
@@ -8,10 +8,12 @@ export interface RecordingPreferences {
   selectedDisplaySourceOrdinal?: number | null;
   selectedWindowSourceId?: string | null;
   captureCamera: boolean;
+  cameraDeviceId?: string | null;
   captureMicrophone: boolean;
   captureSystemAudio: boolean;
   qualityPreset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60";
   codec: "h264" | "hevc";
+  autoSourceFallback?: boolean;
 }
 
 const store = new LazyStore("recording-preferences.json");
@@ -16,6 +16,7 @@ interface EditorHeaderProps {
   cameraOverlayPosition: "top-left" | "top-right" | "bottom-left" | "bottom-right" | "custom";
   cameraOverlayScale: number;
   cameraOverlayMargin: number;
+  cameraOverlayFollowZoom: boolean;
   audioSystemVolume: number;
   audioMicrophoneVolume: number;
   microphoneNoiseGate: boolean;
@@ -27,6 +28,7 @@ interface EditorHeaderProps {
   ) => void;
   onCameraOverlayScaleChange: (scale: number) => void;
   onCameraOverlayMarginChange: (margin: number) => void;
+  onCameraOverlayFollowZoomChange: (followZoom: boolean) => void;
   onAudioSystemVolumeChange: (volume: number) => void;
   onAudioMicrophoneVolumeChange: (volume: number) => void;
   onMicrophoneNoiseGateChange: (enabled: boolean) => void;
@@ -50,6 +52,7 @@ export const EditorHeader = memo(function EditorHeader({
   cameraOverlayPosition,
   cameraOverlayScale,
   cameraOverlayMargin,
+  cameraOverlayFollowZoom,
   audioSystemVolume,
   audioMicrophoneVolume,
   microphoneNoiseGate,
@@ -59,6 +62,7 @@ export const EditorHeader = memo(function EditorHeader({
   onCameraOverlayPositionChange,
   onCameraOverlayScaleChange,
   onCameraOverlayMarginChange,
+  onCameraOverlayFollowZoomChange,
   onAudioSystemVolumeChange,
   onAudioMicrophoneVolumeChange,
   onMicrophoneNoiseGateChange,
@@ -112,9 +116,11 @@ export const EditorHeader = memo(function EditorHeader({
           cameraOverlayPosition={cameraOverlayPosition}
           cameraOverlayScale={cameraOverlayScale}
           cameraOverlayMargin={cameraOverlayMargin}
+          cameraOverlayFollowZoom={cameraOverlayFollowZoom}
           onCameraOverlayPositionChange={onCameraOverlayPositionChange}
           onCameraOverlayScaleChange={onCameraOverlayScaleChange}
           onCameraOverlayMarginChange={onCameraOverlayMarginChange}
+          onCameraOverlayFollowZoomChange={onCameraOverlayFollowZoomChange}
         />
         <HeaderActions
           onOpenVideos={onOpenVideos}
@@ -14,6 +14,7 @@ type EditorLayoutHeaderProps = Pick<
   | "onCameraOverlayPositionChange"
   | "onCameraOverlayScaleChange"
   | "onCameraOverlayMarginChange"
+  | "onCameraOverlayFollowZoomChange"
   | "onAudioSystemVolumeChange"
   | "onAudioMicrophoneVolumeChange"
   | "onMicrophoneNoiseGateChange"
@@ -35,6 +36,7 @@ export function EditorLayoutHeader({
   onCameraOverlayPositionChange,
   onCameraOverlayScaleChange,
   onCameraOverlayMarginChange,
+  onCameraOverlayFollowZoomChange,
   onAudioSystemVolumeChange,
   onAudioMicrophoneVolumeChange,
   onMicrophoneNoiseGateChange,
@@ -53,6 +55,7 @@ export function EditorLayoutHeader({
       cameraOverlayPosition={project.edits.cameraOverlay.position}
       cameraOverlayScale={project.edits.cameraOverlay.scale}
       cameraOverlayMargin={project.edits.cameraOverlay.margin}
+      cameraOverlayFollowZoom={project.edits.cameraOverlay.followZoom}
       audioSystemVolume={project.edits.audioMix.systemVolume}
       audioMicrophoneVolume={project.edits.audioMix.microphoneVolume}
       microphoneNoiseGate={project.edits.audioMix.microphoneNoiseGate}
@@ -62,6 +65,7 @@ export function EditorLayoutHeader({
       onCameraOverlayPositionChange={onCameraOverlayPositionChange}
       onCameraOverlayScaleChange={onCameraOverlayScaleChange}
       onCameraOverlayMarginChange={onCameraOverlayMarginChange}
+      onCameraOverlayFollowZoomChange={onCameraOverlayFollowZoomChange}
       onAudioSystemVolumeChange={onAudioSystemVolumeChange}
       onAudioMicrophoneVolumeChange={onAudioMicrophoneVolumeChange}
       onMicrophoneNoiseGateChange={onMicrophoneNoiseGateChange}
@@ -72,6 +72,7 @@ export interface EditorPageLayoutProps {
   ) => void;
   onCameraOverlayScaleChange: (scale: number) => void;
   onCameraOverlayMarginChange: (margin: number) => void;
+  onCameraOverlayFollowZoomChange: (followZoom: boolean) => void;
   onAudioSystemVolumeChange: (volume: number) => void;
   onAudioMicrophoneVolumeChange: (volume: number) => void;
   onMicrophoneNoiseGateChange: (enabled: boolean) => void;
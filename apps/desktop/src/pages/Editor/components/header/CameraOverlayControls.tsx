@@ -3,11 +3,13 @@ interface CameraOverlayControlsProps {
   cameraOverlayPosition: "top-left" | "top-right" | "bottom-left" | "bottom-right" | "custom";
   cameraOverlayScale: number;
   cameraOverlayMargin: number;
+  cameraOverlayFollowZoom: boolean;
   onCameraOverlayPositionChange: (
     position: "top-left" | "top-right" | "bottom-left" | "bottom-right" | "custom"
   ) => void;
   onCameraOverlayScaleChange: (scale: number) => void;
   onCameraOverlayMarginChange: (margin: number) => void;
+  onCameraOverlayFollowZoomChange: (followZoom: boolean) => void;
 }
 
 export function CameraOverlayControls({
@@ -15,9 +17,11 @@ export function CameraOverlayControls({
   cameraOverlayPosition,
   cameraOverlayScale,
   cameraOverlayMargin,
+  cameraOverlayFollowZoom,
   onCameraOverlayPositionChange,
   onCameraOverlayScaleChange,
   onCameraOverlayMarginChange,
+  onCameraOverlayFollowZoomChange,
 }: CameraOverlayControlsProps) {
   if (!hasCameraTrack) return null;
 
@@ -75,6 +79,16 @@ export function CameraOverlayControls({
           className="w-10 bg-transparent text-right text-xs outline-none disabled:cursor-not-allowed disabled:opacity-50"
         />
       </label>
+      <label className="flex items-center gap-1 rounded-md border border-border/60 bg-background px-2 py-1 text-xs text-foreground/80">
+        Zoom
+        <input
+          type="checkbox"
+          checked={cameraOverlayFollowZoom}
+          onChange={(event) => onCameraOverlayFollowZoomChange(event.target.checked)}
+          className="size-3.5 accent-primary"
+          title="Zoom the camera overlay with the screen in exports"
+        />
+      </label>
     </div>
   );
 }
@@ -8,6 +8,7 @@ interface UseEditorBoundedUpdatersOptions {
     scale?: number;
     customX?: number;
     customY?: number;
+    followZoom?: boolean;
   }) => void;
   updateAudioMix: (updates: {
     systemVolume?: number;
@@ -44,6 +45,11 @@ export function useEditorBoundedUpdaters({
     [updateCameraOverlay]
   );
 
+  const handleCameraOverlayFollowZoomChange = useCallback(
+    (followZoom: boolean) => updateCameraOverlay({ followZoom }),
+    [updateCameraOverlay]
+  );
+
   const handleCameraOverlayCustomPositionChange = useCallback(
     (x: number, y: number) =>
       updateCameraOverlay({
@@ -107,6 +113,7 @@ export function useEditorBoundedUpdaters({
     handleCameraOverlayPositionChange,
     handleCameraOverlayScaleChange,
     handleCameraOverlayMarginChange,
+    handleCameraOverlayFollowZoomChange,
     handleCameraOverlayCustomPositionChange,
     handleAudioSystemVolumeChange,
     handleAudioMicrophoneVolumeChange,
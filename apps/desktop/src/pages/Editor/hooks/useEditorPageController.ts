@@ -109,15 +109,18 @@ export function useEditorPageController({
     speedDraft,
   });
 
+  // Camera-only takes play the camera as the main video instead of an overlay.
+  const mainVideoPath = project?.screenVideoPath ?? project?.cameraVideoPath;
+  const overlayCameraPath = project?.screenVideoPath ? project.cameraVideoPath : undefined;
   const videoSrc = useMemo(
-    () => (project?.screenVideoPath ? convertFileSrc(project.screenVideoPath) : ""),
-    [project?.screenVideoPath]
+    () => (mainVideoPath ? convertFileSrc(mainVideoPath) : ""),
+    [mainVideoPath]
   );
   const cameraSrc = useMemo(
-    () => (project?.cameraVideoPath ? convertFileSrc(project.cameraVideoPath) : ""),
-    [project?.cameraVideoPath]
+    () => (overlayCameraPath ? convertFileSrc(overlayCameraPath) : ""),
+    [overlayCameraPath]
   );
-  const screenWaveform = useWaveformData(project?.screenVideoPath);
+  const screenWaveform = useWaveformData(project?.systemAudioPath ?? project?.screenVideoPath);
   const microphoneWaveform = useWaveformData(project?.microphoneAudioPath);
 
   const { enabledSegments, editedDuration, sourceToEditedTime } = useEditedTimelineMetrics(
@@ -247,6 +250,7 @@ export function useEditorPageController({
     handleCameraOverlayPositionChange,
     handleCameraOverlayScaleChange,
     handleCameraOverlayMarginChange,
+    handleCameraOverlayFollowZoomChange,
     handleCameraOverlayCustomPositionChange,
     handleAudioSystemVolumeChange,
     handleAudioMicrophoneVolumeChange,
@@ -334,6 +338,7 @@ export function useEditorPageController({
         onCameraOverlayPositionChange: handleCameraOverlayPositionChange,
         onCameraOverlayScaleChange: handleCameraOverlayScaleChange,
         onCameraOverlayMarginChange: handleCameraOverlayMarginChange,
+        onCameraOverlayFollowZoomChange: handleCameraOverlayFollowZoomChange,
         onAudioSystemVolumeChange: handleAudioSystemVolumeChange,
         onAudioMicrophoneVolumeChange: handleAudioMicrophoneVolumeChange,
         onMicrophoneNoiseGateChange: handleMicrophoneNoiseGateChange,
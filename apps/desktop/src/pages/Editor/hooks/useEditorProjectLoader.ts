@@ -12,7 +12,9 @@ function normalizeProject(project: Project): Project {
   const overlay = project.edits.cameraOverlay;
   const audioMix = project.edits.audioMix;
   const colorCorrection = project.edits.colorCorrection;
+  const cursorEffects = project.edits.cursorEffects;
   const annotations = project.edits.annotations;
+  const zoom = project.edits.zoom;
   return {
     ...project,
     edits: {
@@ -25,6 +27,7 @@ function normalizeProject(project: Project): Project {
         }),
         customX: overlay?.customX ?? 1,
         customY: overlay?.customY ?? 1,
+        followZoom: overlay?.followZoom ?? false,
       },
       audioMix: {
         systemVolume: audioMix?.systemVolume ?? 1,
@@ -36,6 +39,19 @@ function normalizeProject(project: Project): Project {
         contrast: colorCorrection?.contrast ?? 1,
         saturation: colorCorrection?.saturation ?? 1,
       },
+      cursorEffects: {
+        highlight: cursorEffects?.highlight ?? "none",
+        clickRipples: cursorEffects?.clickRipples ?? false,
+        size: cursorEffects?.size ?? 48,
+        color: cursorEffects?.color ?? "#ffd60a",
+        opacity: cursorEffects?.opacity ?? 0.35,
+      },
+      zoom: (zoom ?? []).map((effect) => ({
+        ...effect,
+        easeIn: effect.easeIn ?? 0,
+        easeOut: effect.easeOut ?? 0,
+        easing: effect.easing ?? "ease-in-out",
+      })),
       annotations: (annotations ?? []).map((annotation) => ({
         ...annotation,
         mode: annotation.mode ?? "outline",
@@ -63,6 +79,7 @@ function buildFallbackProject(id: string): Project {
         scale: 0.25,
         customX: 1,
         customY: 1,
+        followZoom: false,
       },
       audioMix: {
         systemVolume: 1,
@@ -74,6 +91,13 @@ function buildFallbackProject(id: string): Project {
         contrast: 1,
         saturation: 1,
       },
+      cursorEffects: {
+        highlight: "none",
+        clickRipples: false,
+        size: 48,
+        color: "#ffd60a",
+        opacity: 0.35,
+      },
     },
   };
 }
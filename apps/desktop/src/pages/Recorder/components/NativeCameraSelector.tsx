@@ -0,0 +1,46 @@
+import { Camera } from "lucide-react";
+import { cn } from "@/lib/utils";
+import { CaptureDevice } from "../../../types/project";
+
+interface NativeCameraSelectorProps {
+  cameras: CaptureDevice[];
+  selectedCameraId: string;
+  isRecording: boolean;
+  disabled: boolean;
+  onSelect: (deviceId: string) => void;
+}
+
+/** Camera picker for takes where the backend records the camera; it shows no live preview. */
+export function NativeCameraSelector({
+  cameras,
+  selectedCameraId,
+  isRecording,
+  disabled,
+  onSelect,
+}: NativeCameraSelectorProps) {
+  return (
+    <div
+      className={cn(
+        "flex w-[220px] flex-col gap-2 rounded-xl border border-border/50 bg-card/50 p-3 backdrop-blur-sm",
+        isRecording && "border-primary/40"
+      )}
+    >
+      <div className="flex items-center gap-2 text-[10px] font-medium uppercase tracking-wider text-muted-foreground">
+        <Camera className="size-3" strokeWidth={2} />
+        {isRecording ? "Recording camera" : "Camera"}
+      </div>
+      <select
+        value={selectedCameraId}
+        onChange={(event) => onSelect(event.target.value)}
+        disabled={disabled}
+        className="rounded-lg border border-border/60 bg-card/60 px-2 py-2 text-xs text-foreground/80 outline-none focus:border-primary/50"
+      >
+        {cameras.map((camera) => (
+          <option key={camera.id} value={camera.id}>
+            {camera.name}
+          </option>
+        ))}
+      </select>
+    </div>
+  );
+}
@@ -4,6 +4,7 @@ import { cn } from "@/lib/utils";
 import { SourceSelector } from "../../../components/SourceSelector";
 import { MicrophoneRecorder } from "../../../components/MicrophoneRecorder";
 import { CameraPreview } from "../../../components/CameraPreview";
+import { NativeCameraSelector } from "./NativeCameraSelector";
 import { SourceTypeButton } from "../../../components/SourceTypeButton";
 import { RecorderHeader } from "./RecorderHeader";
 import { CountdownOverlay } from "./CountdownOverlay";
@@ -22,6 +23,8 @@ export function RecorderMainPanel({
   sources,
   isLoadingSources,
   captureCamera,
+  nativeCameras,
+  nativeCameraDeviceId,
   isActivelyRecording,
   projectId,
   recordingStartTimeMs,
@@ -30,16 +33,19 @@ export function RecorderMainPanel({
   cameraReady,
   qualityPreset,
   codec,
+  autoSourceFallback,
   isRecording,
   onOpenVideos,
   onSetSourceType,
   onSetSelectedSource,
   onSetCameraReady,
   onToggleCamera,
+  onNativeCameraChange,
   onToggleMicrophone,
   onToggleSystemAudio,
   onQualityPresetChange,
   onCodecChange,
+  onToggleAutoSourceFallback,
   showOpenRecordingWidgetButton,
   showRetryFinalizationButton,
   onOpenRecordingWidget,
@@ -95,13 +101,24 @@ export function RecorderMainPanel({
               exit={{ opacity: 0, scale: 0.8, y: -10 }}
               transition={{ type: "spring", stiffness: 300, damping: 25 }}
             >
-              <CameraPreview
-                enabled={captureCamera}
-                isRecording={isActivelyRecording}
-                projectId={projectId}
-                recordingStartTimeMs={recordingStartTimeMs}
-                onCameraReady={onSetCameraReady}
-              />
+              {nativeCameraDeviceId ? (
+                // The backend records this camera, so the webview must not open it too.
+                <NativeCameraSelector
+                  cameras={nativeCameras}
+                  selectedCameraId={nativeCameraDeviceId}
+                  isRecording={isActivelyRecording}
+                  disabled={sourceControlsLocked}
+                  onSelect={onNativeCameraChange}
+                />
+              ) : (
+                <CameraPreview
+                  enabled={captureCamera}
+                  isRecording={isActivelyRecording}
+                  projectId={projectId}
+                  recordingStartTimeMs={recordingStartTimeMs}
+                  onCameraReady={onSetCameraReady}
+                />
+              )}
             </motion.div>
           )}
         </AnimatePresence>
@@ -123,8 +140,10 @@ export function RecorderMainPanel({
         <RecorderQualityControls
           qualityPreset={qualityPreset}
           codec={codec}
+          autoSourceFallback={autoSourceFallback}
           onQualityPresetChange={onQualityPresetChange}
           onCodecChange={onCodecChange}
+          onToggleAutoSourceFallback={onToggleAutoSourceFallback}
         />
         <div className="flex-1" />
         <RecorderActionButtons
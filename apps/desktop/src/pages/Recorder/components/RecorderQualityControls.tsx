@@ -1,15 +1,19 @@
 interface RecorderQualityControlsProps {
   qualityPreset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60";
   codec: "h264" | "hevc";
+  autoSourceFallback: boolean;
   onQualityPresetChange: (value: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60") => void;
   onCodecChange: (value: "h264" | "hevc") => void;
+  onToggleAutoSourceFallback: () => void;
 }
 
 export function RecorderQualityControls({
   qualityPreset,
   codec,
+  autoSourceFallback,
   onQualityPresetChange,
   onCodecChange,
+  onToggleAutoSourceFallback,
 }: RecorderQualityControlsProps) {
   return (
     <div className="animate-fade-up-delay-3 space-y-2">
@@ -41,6 +45,15 @@ export function RecorderQualityControls({
           <option value="hevc">Codec: HEVC</option>
         </select>
       </div>
+      <label className="flex items-center gap-2 text-xs text-foreground/80">
+        <input
+          type="checkbox"
+          checked={autoSourceFallback}
+          onChange={onToggleAutoSourceFallback}
+          className="accent-primary"
+        />
+        Keep recording on another source if this one disappears
+      </label>
     </div>
   );
 }
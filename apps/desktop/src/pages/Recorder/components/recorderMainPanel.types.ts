@@ -1,4 +1,4 @@
-import { CaptureSource } from "../../../types/project";
+import { CaptureDevice, CaptureSource } from "../../../types/project";
 
 export interface RecorderMainPanelProps {
   selectedSource: CaptureSource | null;
@@ -10,6 +10,8 @@ export interface RecorderMainPanelProps {
   sources: CaptureSource[];
   isLoadingSources: boolean;
   captureCamera: boolean;
+  nativeCameras: CaptureDevice[];
+  nativeCameraDeviceId: string | null;
   isActivelyRecording: boolean;
   projectId: string | null;
   recordingStartTimeMs: number | null;
@@ -18,16 +20,19 @@ export interface RecorderMainPanelProps {
   cameraReady: boolean;
   qualityPreset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60";
   codec: "h264" | "hevc";
+  autoSourceFallback: boolean;
   isRecording: boolean;
   onOpenVideos: () => void;
   onSetSourceType: (type: "display" | "window") => void;
   onSetSelectedSource: (source: CaptureSource) => void;
   onSetCameraReady: (ready: boolean) => void;
   onToggleCamera: () => void;
+  onNativeCameraChange: (deviceId: string) => void;
   onToggleMicrophone: () => void;
   onToggleSystemAudio: () => void;
   onQualityPresetChange: (value: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60") => void;
   onCodecChange: (value: "h264" | "hevc") => void;
+  onToggleAutoSourceFallback: () => void;
   showOpenRecordingWidgetButton: boolean;
   showRetryFinalizationButton: boolean;
   onOpenRecordingWidget: () => void;
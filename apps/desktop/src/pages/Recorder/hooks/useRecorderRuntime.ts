@@ -6,7 +6,9 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import { useRecordingStore, useRuntimeDiagnosticsStore } from "../../../stores";
 import {
   DiskSpaceStatus,
+  CaptureDevice,
   CaptureSource,
+  PendingFinalization,
   RecordingOptions as RecordingOptionsType,
   StartRecordingResult,
 } from "../../../types/project";
@@ -212,6 +214,7 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
   const [preferredDisplaySourceId, setPreferredDisplaySourceId] = useState<string | null>(null);
   const [preferredDisplaySourceOrdinal, setPreferredDisplaySourceOrdinal] = useState<number | null>(null);
   const [preferredWindowSourceId, setPreferredWindowSourceId] = useState<string | null>(null);
+  const [nativeCameras, setNativeCameras] = useState<CaptureDevice[]>([]);
   const pendingTrayQuickRecordRef = useRef(false);
   const loadSourcesInFlightRef = useRef(false);
   const { countdown, startCountdown } = useRecordingCountdown();
@@ -230,10 +233,12 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     sources,
     isLoadingSources,
     captureCamera,
+    cameraDeviceId,
     captureMicrophone,
     captureSystemAudio,
     qualityPreset,
     codec,
+    autoSourceFallback,
     hasPermission,
     cameraReady,
     setSourceType,
@@ -241,10 +246,12 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     setSources,
     setIsLoadingSources,
     setCaptureCamera,
+    setCameraDeviceId,
     setCaptureMicrophone,
     setCaptureSystemAudio,
     setQualityPreset,
     setCodec,
+    setAutoSourceFallback,
     setHasPermission,
     setCameraReady,
     beginRecordingStart,
@@ -306,6 +313,37 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     });
   }, [appendLifecycleEvent, runtimeTimeoutSettings]);
 
+  useEffect(() => {
+    if (normalizeScopedProjectId(retryFinalizationProjectId)) {
+      return;
+    }
+    let cancelled = false;
+    async function restorePendingFinalizationRetryContext() {
+      try {
+        const pending = await invoke<PendingFinalization[]>("list_pending_finalizations");
+        if (cancelled || pending.length === 0) {
+          return;
+        }
+        const projectId = pending[0].projectId;
+        setRetryFinalizationProjectId(projectId);
+        setPendingFinalizationRetryProjectId(projectId);
+        appendLifecycleEvent({
+          source: "recorder",
+          event: "recording-finalization-retry-context-restored",
+          summary: "Restored a failed finalization saved before the app restarted.",
+          level: "warning",
+          projectId,
+        });
+      } catch (error) {
+        console.error("Failed to load pending finalizations:", error);
+      }
+    }
+    void restorePendingFinalizationRetryContext();
+    return () => {
+      cancelled = true;
+    };
+  }, [appendLifecycleEvent, retryFinalizationProjectId]);
+
   useEffect(() => {
     const retryProjectId = normalizeScopedProjectId(retryFinalizationProjectId);
     if (!retryProjectId) {
@@ -404,10 +442,12 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
           (persisted.sourceType === "window" ? fallbackSelectedSourceId : null)
       );
       setCaptureCamera(persisted.captureCamera);
+      setCameraDeviceId(persisted.cameraDeviceId ?? null);
       setCaptureMicrophone(persisted.captureMicrophone);
       setCaptureSystemAudio(persisted.captureSystemAudio);
       setQualityPreset(persisted.qualityPreset ?? "1080p30");
       setCodec(persisted.codec ?? "h264");
+      setAutoSourceFallback(persisted.autoSourceFallback ?? false);
       setPreferencesLoaded(true);
     }
 
@@ -417,11 +457,13 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     };
   }, [
     setCaptureCamera,
+    setCameraDeviceId,
     setCaptureMicrophone,
     setCaptureSystemAudio,
     setSourceType,
     setQualityPreset,
     setCodec,
+    setAutoSourceFallback,
   ]);
 
   useEffect(() => {
@@ -463,10 +505,12 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
       selectedDisplaySourceOrdinal: preferredDisplaySourceOrdinal,
       selectedWindowSourceId: preferredWindowSourceId,
       captureCamera,
+      cameraDeviceId,
       captureMicrophone,
       captureSystemAudio,
       qualityPreset,
       codec,
+      autoSourceFallback,
     });
   }, [
     preferencesLoaded,
@@ -476,10 +520,12 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     preferredDisplaySourceOrdinal,
     preferredWindowSourceId,
     captureCamera,
+    cameraDeviceId,
     captureMicrophone,
     captureSystemAudio,
     qualityPreset,
     codec,
+    autoSourceFallback,
   ]);
 
   useEffect(() => {
@@ -488,6 +534,33 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     }
   }, [sourceType, hasPermission, preferredSourceId, preferredSourceOrdinal]);
 
+  useEffect(() => {
+    if (!hasPermission) return;
+    let cancelled = false;
+    // Only Linux lists cameras here; elsewhere the webview records the camera.
+    invoke<CaptureDevice[]>("list_capture_sources", { sourceType: "camera" })
+      .then((cameras) => {
+        if (!cancelled) setNativeCameras(cameras);
+      })
+      .catch((error) => {
+        console.error("Failed to list cameras:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [hasPermission]);
+
+  const nativeCameraDeviceId =
+    nativeCameras.find((camera) => camera.id === cameraDeviceId)?.id ??
+    nativeCameras[0]?.id ??
+    null;
+
+  useEffect(() => {
+    if (nativeCameraDeviceId) {
+      setCameraReady(true);
+    }
+  }, [nativeCameraDeviceId, setCameraReady]);
+
   useEffect(() => {
     if (!hasPermission || recordingState !== "idle") {
       return;
@@ -832,6 +905,7 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
       sourceType: "display" | "window";
       sourceId: string;
       sourceOrdinal?: number | null;
+      reason?: "source-lost" | "capture-exited";
     }>("recording-source-fallback", (event) => {
       const activeProjectId = resolveActiveProjectId();
       if (!activeProjectId || event.payload.projectId !== activeProjectId) {
@@ -1035,8 +1109,10 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
             ? resolvedSource.preferredDisplayOrdinal
             : null,
         captureCamera,
+        cameraDeviceId: captureCamera ? nativeCameraDeviceId : null,
         captureMicrophone,
         captureSystemAudio,
+        autoSourceFallback,
         qualityPreset,
         codec,
       };
@@ -1289,18 +1365,23 @@ export function useRecorderRuntime({ onRecordingStoppedNavigate }: UseRecorderRu
     sources,
     isLoadingSources,
     captureCamera,
+    nativeCameras,
+    nativeCameraDeviceId,
     captureMicrophone,
     captureSystemAudio,
     qualityPreset,
     codec,
+    autoSourceFallback,
     cameraReady,
     setSourceType,
     setSelectedSource,
     setCaptureCamera,
+    setCameraDeviceId,
     setCaptureMicrophone,
     setCaptureSystemAudio,
     setQualityPreset,
     setCodec,
+    setAutoSourceFallback,
     setCameraReady,
     requestPermission,
     showOpenRecordingWidgetButton,
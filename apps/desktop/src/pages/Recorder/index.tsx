@@ -20,16 +20,20 @@ export function RecorderPage() {
     sources,
     isLoadingSources,
     captureCamera,
+    nativeCameras,
+    nativeCameraDeviceId,
     captureMicrophone,
     captureSystemAudio,
-    qualityPreset, codec, cameraReady,
+    qualityPreset, codec, autoSourceFallback, cameraReady,
     setSourceType,
     setSelectedSource,
     setCaptureCamera,
+    setCameraDeviceId,
     setCaptureMicrophone,
     setCaptureSystemAudio,
     setQualityPreset,
     setCodec,
+    setAutoSourceFallback,
     setCameraReady,
     requestPermission,
     showOpenRecordingWidgetButton,
@@ -62,6 +66,8 @@ export function RecorderPage() {
       sources={sources}
       isLoadingSources={isLoadingSources}
       captureCamera={captureCamera}
+      nativeCameras={nativeCameras}
+      nativeCameraDeviceId={nativeCameraDeviceId}
       isActivelyRecording={isActivelyRecording}
       projectId={projectId}
       recordingStartTimeMs={recordingStartTimeMs}
@@ -70,16 +76,19 @@ export function RecorderPage() {
       cameraReady={cameraReady}
       qualityPreset={qualityPreset}
       codec={codec}
+      autoSourceFallback={autoSourceFallback}
       isRecording={isRecording}
       onOpenVideos={() => navigate("/videos")}
       onSetSourceType={setSourceType}
       onSetSelectedSource={setSelectedSource}
       onSetCameraReady={setCameraReady}
       onToggleCamera={() => setCaptureCamera(!captureCamera)}
+      onNativeCameraChange={setCameraDeviceId}
       onToggleMicrophone={() => setCaptureMicrophone(!captureMicrophone)}
       onToggleSystemAudio={() => setCaptureSystemAudio(!captureSystemAudio)}
       onQualityPresetChange={setQualityPreset}
       onCodecChange={setCodec}
+      onToggleAutoSourceFallback={() => setAutoSourceFallback(!autoSourceFallback)}
       showOpenRecordingWidgetButton={showOpenRecordingWidgetButton}
       showRetryFinalizationButton={showRetryFinalizationButton}
       onOpenRecordingWidget={handleOpenRecordingWidget}
@@ -1,4 +1,4 @@
-import { Loader2, Pause, Play, Square } from "lucide-react";
+import { Bookmark, Loader2, Pause, Play, Square } from "lucide-react";
 import { cn } from "@/lib/utils";
 import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip";
 
@@ -9,6 +9,7 @@ interface WidgetPanelProps {
   statusLabel: string;
   permissionError: string | null;
   onTogglePause: () => void;
+  onAddMarker: () => void;
   onStopRecording: () => void;
 }
 
@@ -29,6 +30,7 @@ export function WidgetPanel({
   statusLabel,
   permissionError,
   onTogglePause,
+  onAddMarker,
   onStopRecording,
 }: WidgetPanelProps) {
   return (
@@ -102,6 +104,23 @@ export function WidgetPanel({
               {isRecording ? "Pause (⌘⇧P)" : "Resume (⌘⇧P)"}
             </TooltipContent>
           </Tooltip>
+          <Tooltip>
+            <TooltipTrigger asChild>
+              <button
+                onClick={onAddMarker}
+                disabled={isStopping}
+                className={cn(
+                  "flex size-8 items-center justify-center rounded-md text-muted-foreground transition-all duration-200 hover:bg-muted hover:text-foreground",
+                  isStopping && "cursor-not-allowed opacity-40"
+                )}
+              >
+                <Bookmark className="size-4" strokeWidth={2} />
+              </button>
+            </TooltipTrigger>
+            <TooltipContent side="bottom" className="text-xs">
+              Add Marker (⌘⇧M)
+            </TooltipContent>
+          </Tooltip>
           <Tooltip>
             <TooltipTrigger asChild>
               <button
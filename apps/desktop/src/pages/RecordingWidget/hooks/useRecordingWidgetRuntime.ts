@@ -3,7 +3,8 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useRecordingStore, RecordingState } from "../../../stores";
 import {
-  DiskSpaceStatus,
+  DiskSpaceReport,
+  RecordingHealth,
   RecordingSessionSnapshot,
   RecordingSourceStatus,
 } from "../../../types/project";
@@ -16,7 +17,8 @@ import {
   clearPendingRecordingSourceFallbackNotice,
   getPendingRecordingSourceFallbackNotice,
 } from "../../../lib/recordingSourceFallbackNotice";
-import { formatBytesAsGiB, resolveMinimumFreeBytes } from "../../../lib/diskSpace";
+import { describeDiskSpaceReport } from "../../../lib/diskSpace";
+import { describeRecordingHealth } from "../../../lib/recordingHealth";
 import { toErrorMessage } from "../../../lib/errorMessage";
 import {
   normalizeScopedProjectId,
@@ -55,11 +57,12 @@ export function useRecordingWidgetRuntime() {
   } = useRecordingStore();
 
   const intervalRef = useRef<number | null>(null);
-  const autoStopForDiskRef = useRef(false);
   const lastAutoSegmentAtRef = useRef(0);
   const autoSegmentInFlightRef = useRef(false);
   const sourceStatusPollInFlightRef = useRef(false);
   const sourceUnavailableNoticeRef = useRef<string | null>(null);
+  const diskSpaceNoticeRef = useRef<string | null>(null);
+  const healthNoticeRef = useRef<string | null>(null);
   const runtimeTimeoutSettings = useMemo(() => loadRuntimeTimeoutSettings(), []);
   const [permissionError, setPermissionError] = useState<string | null>(null);
   const [finalizingStatus, setFinalizingStatus] =
@@ -208,6 +211,7 @@ export function useRecordingWidgetRuntime() {
       sourceType: "display" | "window";
       sourceId: string;
       sourceOrdinal?: number | null;
+      reason?: "source-lost" | "capture-exited";
     }>("recording-source-fallback", (event) => {
       const activeProjectId = resolveActiveProjectId();
       const eventProjectId = normalizeScopedProjectId(event.payload.projectId);
@@ -331,32 +335,48 @@ export function useRecordingWidgetRuntime() {
   }
 
   useEffect(() => {
-    if (state !== "recording" && state !== "paused") return;
-    const intervalId = window.setInterval(async () => {
+    // The backend watchdog pauses or stops the take itself; this only surfaces its reports.
+    const unlisten = listen<DiskSpaceReport>("recording-disk-space", (event) => {
       const activeProjectId = resolveActiveProjectId();
-      if (!activeProjectId) return;
-      try {
-        const diskStatus = await invoke<DiskSpaceStatus>("check_recording_disk_space");
-        if (!diskStatus.sufficient && !autoStopForDiskRef.current) {
-          const minimumRequiredGb = formatBytesAsGiB(resolveMinimumFreeBytes(diskStatus));
-          autoStopForDiskRef.current = true;
-          setPermissionError(
-            `Recording stopped automatically because free disk space dropped below ${minimumRequiredGb} GB.`
-          );
-          const didStop = await stopRecording();
-          if (!didStop) {
-            autoStopForDiskRef.current = false;
-          }
+      const eventProjectId = normalizeScopedProjectId(event.payload.projectId);
+      if (!shouldHandleProjectScopedEvent(activeProjectId, eventProjectId)) return;
+      const message = describeDiskSpaceReport(event.payload);
+      const previousNotice = diskSpaceNoticeRef.current;
+      diskSpaceNoticeRef.current = message;
+      setPermissionError((current) => {
+        if (!current || current === previousNotice) {
+          return message;
         }
-      } catch (error) {
-        console.error("Failed to check recording disk space:", error);
-        setPermissionError((current) =>
-          current ?? "Unable to verify available disk space during recording."
-        );
+        return current;
+      });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [projectId]);
+
+  useEffect(() => {
+    const unlisten = listen<{ projectId: string; health: RecordingHealth }>(
+      "recording-health",
+      (event) => {
+        const activeProjectId = resolveActiveProjectId();
+        const eventProjectId = normalizeScopedProjectId(event.payload.projectId);
+        if (!shouldHandleProjectScopedEvent(activeProjectId, eventProjectId)) return;
+        const message = describeRecordingHealth(event.payload.health);
+        const previousNotice = healthNoticeRef.current;
+        healthNoticeRef.current = message;
+        setPermissionError((current) => {
+          if (!current || current === previousNotice) {
+            return message;
+          }
+          return current;
+        });
       }
-    }, 5000);
-    return () => clearInterval(intervalId);
-  }, [state, projectId]);
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [projectId]);
 
   useEffect(() => {
     if (state !== "stopping") {
@@ -379,7 +399,8 @@ export function useRecordingWidgetRuntime() {
 
   useEffect(() => {
     if (state === "idle") {
-      autoStopForDiskRef.current = false;
+      diskSpaceNoticeRef.current = null;
+      healthNoticeRef.current = null;
       lastAutoSegmentAtRef.current = 0;
       autoSegmentInFlightRef.current = false;
       sourceStatusPollInFlightRef.current = false;
@@ -411,7 +432,7 @@ export function useRecordingWidgetRuntime() {
           return;
         }
         const warningMessage =
-          sourceStatus.sourceType === "display"
+          sourceStatus.sourceType !== "window"
             ? `The selected display is disconnected. Recording will continue on ${fallbackDisplayLabel(
                 sourceStatus.fallbackSource?.sourceId ?? sourceStatus.sourceId,
                 sourceStatus.fallbackSource?.sourceOrdinal
@@ -492,6 +513,21 @@ export function useRecordingWidgetRuntime() {
     }
   }
 
+  async function addMarker() {
+    if (state === "stopping") return;
+    const currentProjectId = resolveActiveProjectId();
+    if (!currentProjectId) {
+      setPermissionError("No active recording session was found.");
+      return;
+    }
+    try {
+      await invoke("add_recording_marker", { projectId: currentProjectId });
+    } catch (error) {
+      console.error("Failed to add marker:", error);
+      setPermissionError(toErrorMessage(error, "Failed to add marker."));
+    }
+  }
+
   useEffect(() => {
     const unlistenStartStop = listen("global-shortcut-start-stop", () => {
       const hasActiveProject = Boolean(resolveActiveProjectId());
@@ -527,6 +563,7 @@ export function useRecordingWidgetRuntime() {
     isStopping,
     statusLabel,
     togglePause,
+    addMarker,
     stopRecording,
   };
 }
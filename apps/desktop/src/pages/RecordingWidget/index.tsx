@@ -9,6 +9,7 @@ export function RecordingWidget() {
     isStopping,
     statusLabel,
     togglePause,
+    addMarker,
     stopRecording,
   } = useRecordingWidgetRuntime();
 
@@ -20,6 +21,7 @@ export function RecordingWidget() {
       statusLabel={statusLabel}
       permissionError={permissionError}
       onTogglePause={togglePause}
+      onAddMarker={addMarker}
       onStopRecording={stopRecording}
     />
   );
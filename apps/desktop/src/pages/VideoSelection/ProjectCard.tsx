@@ -31,9 +31,8 @@ export function ProjectCard({
   const [thumbnailError, setThumbnailError] = useState(false);
   const [isRenaming, setIsRenaming] = useState(false);
   const [draftName, setDraftName] = useState(project.name);
-  const thumbnailSrc = project.screenVideoPath
-    ? convertFileSrc(project.screenVideoPath)
-    : "";
+  const thumbnailVideoPath = project.screenVideoPath ?? project.cameraVideoPath;
+  const thumbnailSrc = thumbnailVideoPath ? convertFileSrc(thumbnailVideoPath) : "";
 
   useEffect(() => {
     if (!isRenaming) {
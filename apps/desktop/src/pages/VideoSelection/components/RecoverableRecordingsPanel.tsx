@@ -0,0 +1,51 @@
+import { RecoverableRecording } from "../../../types/project";
+import { formatBytesAsGiB } from "../../../lib/diskSpace";
+import { Button } from "@/components/ui/button";
+
+interface RecoverableRecordingsPanelProps {
+  recordings: RecoverableRecording[];
+  busyProjectId: string | null;
+  onRecover: (projectId: string) => void;
+  onDiscard: (projectId: string) => void;
+}
+
+export function RecoverableRecordingsPanel({
+  recordings,
+  busyProjectId,
+  onRecover,
+  onDiscard,
+}: RecoverableRecordingsPanelProps) {
+  return (
+    <div className="mb-4 space-y-2 rounded-lg border border-amber-500/40 bg-amber-500/10 px-3 py-2 animate-fade-up">
+      <p className="text-xs text-amber-200">
+        {recordings.length === 1
+          ? "A recording was interrupted before it was saved."
+          : `${recordings.length} recordings were interrupted before they were saved.`}
+      </p>
+      {recordings.map((recording) => (
+        <div key={recording.projectId} className="flex flex-wrap items-center gap-2">
+          <span className="text-xs text-muted-foreground">
+            Recording {recording.projectId.slice(0, 8)} · {recording.screenSegmentPaths.length}{" "}
+            {recording.screenSegmentPaths.length === 1 ? "segment" : "segments"} ·{" "}
+            {formatBytesAsGiB(recording.sizeBytes)} GiB
+          </span>
+          <Button
+            size="sm"
+            disabled={busyProjectId !== null}
+            onClick={() => onRecover(recording.projectId)}
+          >
+            {busyProjectId === recording.projectId ? "Recovering..." : "Recover"}
+          </Button>
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={busyProjectId !== null}
+            onClick={() => onDiscard(recording.projectId)}
+          >
+            Discard
+          </Button>
+        </div>
+      ))}
+    </div>
+  );
+}
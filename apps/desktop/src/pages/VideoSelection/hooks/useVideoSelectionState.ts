@@ -3,7 +3,7 @@ import { useLocation, useNavigate } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
 import { LogicalSize } from "@tauri-apps/api/dpi";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { Project } from "../../../types/project";
+import { Project, RecoverableRecording } from "../../../types/project";
 import { useBatchExportQueue } from "./useBatchExportQueue";
 
 export function useVideoSelectionState() {
@@ -14,6 +14,8 @@ export function useVideoSelectionState() {
   const [error, setError] = useState<string | null>(null);
   const [selectionMode, setSelectionMode] = useState(false);
   const [selectedProjectIds, setSelectedProjectIds] = useState<string[]>([]);
+  const [recoverableRecordings, setRecoverableRecordings] = useState<RecoverableRecording[]>([]);
+  const [recoveringProjectId, setRecoveringProjectId] = useState<string | null>(null);
   const batch = useBatchExportQueue({ selectedProjectIds, projects });
 
   const cameFromEditor = location.state?.from === "editor";
@@ -34,8 +36,50 @@ export function useVideoSelectionState() {
 
   useEffect(() => {
     void loadProjects();
+    void loadRecoverableRecordings();
   }, []);
 
+  async function loadRecoverableRecordings() {
+    try {
+      const result = await invoke<RecoverableRecording[]>("list_recoverable_recordings");
+      setRecoverableRecordings(result);
+    } catch (loadError) {
+      console.error("Failed to load recoverable recordings:", loadError);
+    }
+  }
+
+  async function handleRecoverRecording(projectId: string) {
+    setRecoveringProjectId(projectId);
+    try {
+      const project = await invoke<Project>("recover_recording", { projectId });
+      setRecoverableRecordings((prev) =>
+        prev.filter((recording) => recording.projectId !== projectId)
+      );
+      setProjects((prev) => [project, ...prev.filter((existing) => existing.id !== project.id)]);
+    } catch (recoverError) {
+      console.error("Failed to recover recording:", recoverError);
+      setError(`Failed to recover recording: ${String(recoverError)}`);
+    } finally {
+      setRecoveringProjectId(null);
+    }
+  }
+
+  async function handleDiscardRecoverableRecording(projectId: string) {
+    const confirmed = window.confirm("Delete the files of this interrupted recording?");
+    if (!confirmed) return;
+
+    setRecoverableRecordings((prev) =>
+      prev.filter((recording) => recording.projectId !== projectId)
+    );
+    try {
+      await invoke("discard_recoverable_recording", { projectId });
+    } catch (discardError) {
+      console.error("Failed to discard recording:", discardError);
+      setError(`Failed to discard recording: ${String(discardError)}`);
+      await loadRecoverableRecordings();
+    }
+  }
+
   async function loadProjects() {
     setIsLoading(true);
     setError(null);
@@ -136,6 +180,8 @@ export function useVideoSelectionState() {
 
   return {
     projects,
+    recoverableRecordings,
+    recoveringProjectId,
     isLoading,
     error,
     selectionMode,
@@ -145,6 +191,8 @@ export function useVideoSelectionState() {
     handleRenameProject,
     handleDeleteProject,
     handleOpenProjectInNewWindow,
+    handleRecoverRecording,
+    handleDiscardRecoverableRecording,
     toggleProjectSelection,
     handleBack,
     handleGoToRecorder,
@@ -5,6 +5,7 @@ import { ProjectCard } from "./ProjectCard";
 import { useExportStore } from "../../stores";
 import { VideoSelectionHeader } from "./components/VideoSelectionHeader";
 import { BatchExportToolbar } from "./components/BatchExportToolbar";
+import { RecoverableRecordingsPanel } from "./components/RecoverableRecordingsPanel";
 import { useVideoSelectionState } from "./hooks/useVideoSelectionState";
 
 function EmptyState({ onRecord }: { onRecord: () => void }) {
@@ -36,6 +37,8 @@ function EmptyState({ onRecord }: { onRecord: () => void }) {
 export function VideoSelectionPage() {
   const {
     projects,
+    recoverableRecordings,
+    recoveringProjectId,
     isLoading,
     error,
     selectionMode,
@@ -53,6 +56,8 @@ export function VideoSelectionPage() {
     handleRenameProject,
     handleDeleteProject,
     handleOpenProjectInNewWindow,
+    handleRecoverRecording,
+    handleDiscardRecoverableRecording,
     toggleProjectSelection,
     handleBack,
     handleGoToRecorder,
@@ -99,6 +104,14 @@ export function VideoSelectionPage() {
             onStopBatchExport={stopBatchExport}
           />
         )}
+        {recoverableRecordings.length > 0 && (
+          <RecoverableRecordingsPanel
+            recordings={recoverableRecordings}
+            busyProjectId={recoveringProjectId}
+            onRecover={handleRecoverRecording}
+            onDiscard={handleDiscardRecoverableRecording}
+          />
+        )}
         {error && (
           <div className="mb-4 rounded-lg bg-destructive/10 p-3 text-sm text-destructive animate-fade-up">
             {error}
@@ -18,10 +18,12 @@ interface RecordingStore {
   
   // Input options
   captureCamera: boolean;
+  cameraDeviceId: string | null;
   captureMicrophone: boolean;
   captureSystemAudio: boolean;
   qualityPreset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60";
   codec: "h264" | "hevc";
+  autoSourceFallback: boolean;
   
   // Permission state
   hasPermission: boolean | null;
@@ -40,10 +42,12 @@ interface RecordingStore {
   setSources: (sources: CaptureSource[]) => void;
   setIsLoadingSources: (loading: boolean) => void;
   setCaptureCamera: (enabled: boolean) => void;
+  setCameraDeviceId: (deviceId: string | null) => void;
   setCaptureMicrophone: (enabled: boolean) => void;
   setCaptureSystemAudio: (enabled: boolean) => void;
   setQualityPreset: (preset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60") => void;
   setCodec: (codec: "h264" | "hevc") => void;
+  setAutoSourceFallback: (enabled: boolean) => void;
   setHasPermission: (permission: boolean | null) => void;
   setCameraReady: (ready: boolean) => void;
   
@@ -66,10 +70,12 @@ export const useRecordingStore = create<RecordingStore>((set) => ({
   sources: [],
   isLoadingSources: false,
   captureCamera: false,
+  cameraDeviceId: null,
   captureMicrophone: false,
   captureSystemAudio: false,
   qualityPreset: "1080p30",
   codec: "h264",
+  autoSourceFallback: false,
   hasPermission: null,
   cameraReady: false,
   
@@ -84,10 +90,12 @@ export const useRecordingStore = create<RecordingStore>((set) => ({
   setSources: (sources) => set({ sources }),
   setIsLoadingSources: (loading) => set({ isLoadingSources: loading }),
   setCaptureCamera: (enabled) => set({ captureCamera: enabled }),
+  setCameraDeviceId: (cameraDeviceId) => set({ cameraDeviceId }),
   setCaptureMicrophone: (enabled) => set({ captureMicrophone: enabled }),
   setCaptureSystemAudio: (enabled) => set({ captureSystemAudio: enabled }),
   setQualityPreset: (qualityPreset) => set({ qualityPreset }),
   setCodec: (codec) => set({ codec }),
+  setAutoSourceFallback: (autoSourceFallback) => set({ autoSourceFallback }),
   setHasPermission: (permission) => set({ hasPermission: permission }),
   setCameraReady: (ready) => set({ cameraReady: ready }),
   
@@ -4,11 +4,16 @@ export interface Project {
   id: string;
   name: string;
   createdAt: string;
-  screenVideoPath: string;
+  /** Missing for camera-only and audio-only takes. */
+  screenVideoPath?: string;
   cameraVideoPath?: string;
   microphoneAudioPath?: string;
   cameraOffsetMs?: number;
   microphoneOffsetMs?: number;
+  systemAudioPath?: string;
+  systemAudioOffsetMs?: number;
+  cursorLogPath?: string;
+  normalizedScreenSegments?: NormalizedSegment[];
   duration: number;
   resolution: Resolution;
   edits: EditDecisionList;
@@ -19,6 +24,15 @@ export interface Resolution {
   height: number;
 }
 
+/** A screen segment re-encoded onto the project canvas before concatenation. */
+export interface NormalizedSegment {
+  index: number;
+  width: number;
+  height: number;
+  frameRate: string;
+  codec: string;
+}
+
 export interface EditDecisionList {
   segments: Segment[];
   zoom: ZoomEffect[];
@@ -27,6 +41,25 @@ export interface EditDecisionList {
   cameraOverlay: CameraOverlaySettings;
   audioMix: AudioMixSettings;
   colorCorrection: ColorCorrectionSettings;
+  cursorEffects: CursorEffectsSettings;
+  /** Bookmarks dropped while recording. */
+  markers?: Marker[];
+  /** Named chapters written into MP4/MOV exports. */
+  chapters?: Chapter[];
+}
+
+export interface Chapter {
+  id: string;
+  title: string;
+  /** Seconds into the recording; exports move it onto the edited timeline. */
+  startTime: number;
+}
+
+export interface Marker {
+  id: string;
+  /** Seconds into the recording. */
+  time: number;
+  label?: string | null;
 }
 
 export interface CameraOverlaySettings {
@@ -35,6 +68,7 @@ export interface CameraOverlaySettings {
   scale: number;
   customX: number;
   customY: number;
+  followZoom: boolean;
 }
 
 export interface AudioMixSettings {
@@ -49,6 +83,14 @@ export interface ColorCorrectionSettings {
   saturation: number;
 }
 
+export interface CursorEffectsSettings {
+  highlight: "none" | "halo" | "enlarged";
+  clickRipples: boolean;
+  size: number;
+  color: string;
+  opacity: number;
+}
+
 export interface Segment {
   id: string;
   startTime: number;
@@ -63,8 +105,13 @@ export interface ZoomEffect {
   scale: number;
   x: number;
   y: number;
+  easeIn: number;
+  easeOut: number;
+  easing: ZoomEasing;
 }
 
+export type ZoomEasing = "linear" | "ease-in" | "ease-out" | "ease-in-out";
+
 export interface SpeedEffect {
   id: string;
   startTime: number;
@@ -93,6 +140,8 @@ export interface ExportOptions {
   frameRate: 24 | 30 | 60;
   compression: "minimal" | "social" | "web" | "potato";
   resolution: "720p" | "1080p" | "4k";
+  /** Also write a YouTube-style chapter list next to the export. */
+  chapterList?: boolean;
 }
 
 // Capture source types
@@ -103,33 +152,95 @@ export interface CaptureSource {
   thumbnail?: string;
 }
 
+/** A camera or microphone the backend can record natively (Linux only). */
+export interface CaptureDevice {
+  id: string;
+  name: string;
+}
+
+/** Capture rectangle in pixels, relative to its display's top-left corner. */
+export interface CaptureRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
 export interface RecordingOptions {
+  /** Display id when `sourceType` is `region`. */
   sourceId: string;
-  sourceType: "display" | "window";
+  sourceType: "display" | "window" | "region";
   preferredDisplayOrdinal?: number | null;
+  /** Record the screen; off for camera-only and audio-only takes. Defaults to on. */
+  captureScreen?: boolean;
   captureCamera: boolean;
+  /** V4L2 device recorded by the backend on Linux instead of the webview camera. */
+  cameraDeviceId?: string | null;
   captureMicrophone: boolean;
+  /** Pulse source recorded by the backend on Linux instead of the webview microphone. */
+  microphoneDeviceId?: string | null;
   captureSystemAudio: boolean;
+  region?: CaptureRegion | null;
+  /** Keep only the last N seconds of screen until `save_replay` is called (Linux only). */
+  replayBufferSeconds?: number | null;
+  diskSpace?: Partial<DiskSpacePolicy>;
+  /** Continue on a fallback display or window if the recorded one disappears mid-take. */
+  autoSourceFallback?: boolean;
   qualityPreset: "720p30" | "1080p30" | "1080p60" | "4k30" | "4k60";
   codec: "h264" | "hevc";
 }
 
 export interface StartRecordingResult {
   projectId: string;
-  screenVideoPath: string;
+  screenVideoPath: string | null;
   cameraVideoPath?: string;
   recordingStartTimeMs: number;
   resolvedSourceId: string;
   fallbackSource?: RecordingSourceFallback | null;
 }
 
+/** A stopped recording whose finalization failed and can still be retried, even after a restart. */
+export interface PendingFinalization {
+  projectId: string;
+  screenVideoPath: string | null;
+  screenSegmentPaths: string[];
+  cameraVideoPath?: string | null;
+  cameraSegmentPaths: string[];
+  microphoneAudioPath?: string | null;
+  microphoneSegmentPaths: string[];
+  systemAudioPath?: string | null;
+  systemAudioSegmentPaths: string[];
+  durationSeconds: number;
+  sourceWidth: number;
+  sourceHeight: number;
+  cameraOffsetMs?: number | null;
+  microphoneOffsetMs?: number | null;
+  systemAudioOffsetMs?: number | null;
+  /** Start of each backend camera and audio segment relative to its screen segment. */
+  segmentOffsets?: { cameraMs: number[]; microphoneMs: number[]; systemAudioMs?: number[] };
+  cursorLogPath?: string | null;
+  fragmentedCapture: boolean;
+  markerTimesMs?: number[];
+}
+
+/** A take left on disk without a project file, e.g. after a crash mid-recording. */
+export interface RecoverableRecording {
+  projectId: string;
+  screenSegmentPaths: string[];
+  cameraSegmentPaths: string[];
+  microphoneSegmentPaths: string[];
+  systemAudioSegmentPaths: string[];
+  cursorLogPath?: string | null;
+  sizeBytes: number;
+}
+
 export interface RecordingSourceFallback {
   sourceId: string;
   sourceOrdinal?: number | null;
 }
 
 export interface RecordingSourceStatus {
-  sourceType: "display" | "window";
+  sourceType: "display" | "window" | "region";
   sourceId: string;
   available: boolean;
   fallbackSource?: RecordingSourceFallback | null;
@@ -137,9 +248,21 @@ export interface RecordingSourceStatus {
 
 export type BackendRecordingState = "recording" | "paused" | "stopped";
 
+/** Encoder statistics parsed from the Linux capture's ffmpeg `-progress` output. */
+export interface RecordingHealth {
+  frames: number;
+  fps: number;
+  droppedFrames: number;
+  duplicatedFrames: number;
+  speed: number | null;
+  bitrateKbps: number | null;
+  fallingBehind: boolean;
+}
+
 export interface RecordingSessionSnapshot {
   state: BackendRecordingState;
   elapsedSeconds: number;
+  health?: RecordingHealth | null;
 }
 
 export interface DiskSpaceStatus {
@@ -147,3 +270,49 @@ export interface DiskSpaceStatus {
   minimumRequiredBytes: number;
   sufficient: boolean;
 }
+
+export type LowDiskSpaceAction = "pause" | "stop";
+
+/** Free-space limits for a take; omitted fields use the backend defaults (5 GB / 1 GB / pause). */
+export interface DiskSpacePolicy {
+  minFreeBytes: number;
+  floorBytes: number;
+  action: LowDiskSpaceAction;
+}
+
+/** Emitted as `recording-disk-space` whenever a take's free-space level changes. */
+export interface DiskSpaceReport {
+  projectId: string;
+  level: "ok" | "low" | "critical";
+  freeBytes: number;
+  floorBytes: number;
+  bytesPerSecond: number;
+  secondsRemaining: number | null;
+  action: LowDiskSpaceAction | null;
+}
+
+export type ShortcutAction =
+  | "start-stop"
+  | "pause-resume"
+  | "add-marker"
+  | "open-recorder"
+  | "open-projects"
+  | "quick-record";
+
+/** Returned by the shortcut commands and emitted as `shortcut-bindings-changed`. */
+export interface ShortcutBindingStatus {
+  action: ShortcutAction;
+  accelerator: string | null;
+  defaultAccelerator: string | null;
+  /** Why the OS refused to register the shortcut, if it did. */
+  registrationError: string | null;
+}
+
+/** Local control API settings; scripts authenticate with `Authorization: Bearer <token>`. */
+export interface ControlApiStatus {
+  enabled: boolean;
+  port: number;
+  token: string;
+  settingsPath: string;
+  listeningPort: number | null;
+}